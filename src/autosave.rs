@@ -0,0 +1,346 @@
+//! Autosaving, so that a run isn't lost if the game crashes or is closed, and recording runs so that they can be replayed.
+//!
+//! Enemies' moves and [randomised maps][crate::map::init_randomized] only depend on their seeds and the turn number,
+//! so the choices the player makes are enough to get back to exactly the same point.
+//! Rather than storing the whole state of the game, a save is the map seed and every choice made so far.
+//! To continue an autosave, the choices are replayed without showing anything to catch up.
+//! To [replay][AutosaveMenu::replay] a recording, they are replayed with every screen shown, to reproduce the run exactly.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use crate::menu::{Error, KeyPress, Menu, MenuSignal, OptionList, Screen, ScrollSpeed, Status};
+
+mod tests;
+
+/// The name of the environment variable which sets where the autosave is kept
+const AUTOSAVE_VAR: &str = "AUTOSAVE_PATH";
+/// Where the autosave is kept if `AUTOSAVE_PATH` is not set
+const DEFAULT_AUTOSAVE_PATH: &str = "time-loop-autosave.txt";
+/// How a cancelled choice is written in a save
+const CANCEL: &str = "cancel";
+
+/// Gets where the autosave is kept, from the `AUTOSAVE_PATH` environment variable or in the current directory
+pub fn path_from_env() -> PathBuf {
+    std::env::var_os(AUTOSAVE_VAR).map_or_else(|| PathBuf::from(DEFAULT_AUTOSAVE_PATH), PathBuf::from)
+}
+
+/// A saved run, which can be replayed to get back to where it was saved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Save {
+    /// The seed the maps were shuffled with, or [`None`] if they weren't
+    pub seed: Option<u64>,
+    /// Every choice made in the run, in order. [`None`] is a cancelled choice.
+    pub choices: Vec<Option<usize>>,
+}
+
+impl Save {
+    /// Gets the text of the save file, with the seed on the first line and then one choice on each line
+    fn to_text(&self) -> String {
+        let seed = self.seed.map_or_else(|| "none".to_string(), |seed| seed.to_string());
+
+        std::iter::once(format!("seed {seed}"))
+            .chain(self.choices.iter().map(|choice| match choice {
+                Some(choice) => choice.to_string(),
+                None => CANCEL.to_string(),
+            }))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Parses the text of a save file, as written by [`Save::to_text`].
+    /// Returns [`None`] if the text isn't a valid save.
+    fn from_text(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+
+        let seed = match lines.next()?.strip_prefix("seed ")? {
+            "none" => None,
+            seed => Some(seed.parse().ok()?),
+        };
+
+        let choices = lines
+            .map(|line| match line {
+                CANCEL => Some(None),
+                choice => choice.parse().ok().map(Some),
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Self { seed, choices })
+    }
+
+    /// Reads the save at `path`, or returns [`None`] if there isn't a valid one
+    pub fn load(path: &Path) -> Option<Self> {
+        Self::from_text(&std::fs::read_to_string(path).ok()?)
+    }
+}
+
+/// The owned parts of a [`Screen`] which was skipped while catching up, so that it can be shown once the replay is over
+#[derive(Debug)]
+struct SkippedScreen {
+    /// The [`Screen::banner`]
+    banner: Option<String>,
+    /// The [`Screen::title`]
+    title: String,
+    /// The [`Screen::content`]
+    content: String,
+}
+
+/// A [`Menu`] which records every choice made through it, and saves them at each [checkpoint][Menu::checkpoint].
+/// If it was started from a [`Save`], it first makes the saved choices without showing anything, then shows the last screen again.
+pub struct AutosaveMenu<M> {
+    /// The [`Menu`] which is actually shown to the player
+    menu: M,
+    /// Where to save the run, or [`None`] if it shouldn't be saved
+    path: Option<PathBuf>,
+    /// Where to [record][AutosaveMenu::with_recording] the run, or [`None`] if it isn't being recorded
+    record_path: Option<PathBuf>,
+    /// Whether screens are shown while the saved choices are replayed, rather than skipped to catch up
+    show_replayed_screens: bool,
+    /// The run so far
+    save: Save,
+    /// How many choices were in the save when it was last written
+    saved_choices: usize,
+    /// Saved choices which haven't been replayed yet
+    replay: VecDeque<Option<usize>>,
+    /// The last screen which was skipped while replaying
+    skipped_screen: Option<SkippedScreen>,
+}
+
+impl<M: Menu> AutosaveMenu<M> {
+    /// Wraps a [`Menu`] to autosave to `path`.
+    /// If there is already a save there, the player is asked whether to continue it, which is done by replaying it.
+    ///
+    /// ### Params:
+    /// * `menu`: the [`Menu`] to show the game on
+    /// * `path`: where the autosave is kept
+    /// * `seed`: the map seed to use if the player starts a new run
+    pub fn start(mut menu: M, path: PathBuf, seed: Option<u64>) -> Result<Self, MenuSignal> {
+        let mut save = Save { seed, choices: Vec::new() };
+
+        if let Some(saved) = Save::load(&path) {
+            let options = ["Continue".to_string(), "Start a new run".to_string()];
+            let list = OptionList::new(&options, "You find a run you didn't finish");
+
+            if menu.show_option_list(list)? == 0 {
+                save.seed = saved.seed;
+                let mut autosave_menu = Self::wrap(menu, Some(path), save);
+                autosave_menu.saved_choices = saved.choices.len();
+                autosave_menu.replay = saved.choices.into();
+                return Ok(autosave_menu);
+            }
+        }
+
+        Ok(Self::wrap(menu, Some(path), save))
+    }
+
+    /// Wraps a [`Menu`] to replay a recorded run, showing every screen so that the run is reproduced exactly.
+    /// Once the recording runs out, the player carries on from there. The replayed run isn't autosaved.
+    pub fn replay(menu: M, recording: Save) -> Self {
+        let mut autosave_menu = Self::wrap(menu, None, Save { seed: recording.seed, choices: Vec::new() });
+        autosave_menu.replay = recording.choices.into();
+        autosave_menu.show_replayed_screens = true;
+        autosave_menu
+    }
+
+    /// Also writes every choice to `path`, which is kept once the run is over so that it can be [replayed][AutosaveMenu::replay].
+    /// The recording is written at each [checkpoint][Menu::checkpoint], and once more when the menu is dropped so that it has every choice.
+    pub fn with_recording(mut self, path: PathBuf) -> Self {
+        self.record_path = Some(path);
+        self
+    }
+
+    /// Wraps a [`Menu`] without anything to replay
+    ///
+    /// ### Params:
+    /// * `menu`: the [`Menu`] to show the game on
+    /// * `path`: where to autosave, or [`None`] not to
+    /// * `save`: the run so far
+    const fn wrap(menu: M, path: Option<PathBuf>, save: Save) -> Self {
+        Self {
+            menu,
+            path,
+            record_path: None,
+            show_replayed_screens: false,
+            save,
+            saved_choices: 0,
+            replay: VecDeque::new(),
+            skipped_screen: None,
+        }
+    }
+
+    /// Gets the [`Menu`] which is actually shown to the player
+    #[cfg(test)]
+    pub const fn menu(&self) -> &M {
+        &self.menu
+    }
+
+    /// Gets the seed the maps should be shuffled with, which is the saved seed if a save is being continued
+    pub const fn seed(&self) -> Option<u64> {
+        self.save.seed
+    }
+
+    /// Deletes the autosave, e.g. once the run is over so that it isn't offered to be continued
+    pub fn delete_save(&mut self) {
+        if let Some(path) = &self.path {
+            // If there's no save to delete, there's nothing to do
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Makes the next saved choice, or returns [`None`] if the replay is over.
+    /// If the saved choice isn't one of the options, or is a cancel when the list can't be cancelled,
+    /// the game must have changed since it was saved, so the replay is stopped.
+    #[allow(clippy::option_option)] // The outer option is whether the replay is over, and the inner one is the choice
+    fn next_replayed_choice(&mut self, num_options: usize, cancellable: bool) -> Option<Option<usize>> {
+        let choice = self.replay.pop_front()?;
+
+        let valid = match choice {
+            Some(choice) => choice < num_options,
+            None => cancellable,
+        };
+
+        if !valid {
+            self.replay.clear();
+            return None;
+        }
+
+        self.save.choices.push(choice);
+        Some(choice)
+    }
+
+    /// Shows the last screen which was skipped while replaying, if the replay has just finished and no screen has been shown since.
+    /// This puts the player back in context before they make their first choice.
+    fn show_skipped_screen(&mut self) -> Result<(), Error> {
+        if let Some(skipped) = self.skipped_screen.take() {
+            self.menu.try_show_screen(Screen {
+                banner: skipped.banner.as_deref(),
+                title: &skipped.title,
+                content: &skipped.content,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<M: Menu> Menu for AutosaveMenu<M> {
+    fn new() -> Result<Self, std::io::Error> {
+        Ok(Self::wrap(M::new()?, None, Save { seed: None, choices: Vec::new() }))
+    }
+
+    fn try_show_option_list(&mut self, list: OptionList) -> Result<usize, Error> {
+        if let Some(Some(choice)) = self.next_replayed_choice(list.options.len(), false) {
+            return Ok(choice);
+        }
+
+        self.show_skipped_screen()?;
+        let choice = self.menu.try_show_option_list(list)?;
+        self.save.choices.push(Some(choice));
+        Ok(choice)
+    }
+
+    fn try_show_option_list_cancellable(&mut self, list: OptionList) -> Result<Option<usize>, Error> {
+        if let Some(choice) = self.next_replayed_choice(list.options.len(), true) {
+            return Ok(choice);
+        }
+
+        self.show_skipped_screen()?;
+        let choice = self.menu.try_show_option_list_cancellable(list)?;
+        self.save.choices.push(choice);
+        Ok(choice)
+    }
+
+    fn try_show_screen(&mut self, screen: Screen) -> Result<KeyPress, Error> {
+        // Once the replay is over, the screens being shown put the player back in context instead
+        if self.replay.is_empty() || self.show_replayed_screens {
+            self.skipped_screen = None;
+            return self.menu.try_show_screen(screen);
+        }
+
+        self.skipped_screen = Some(SkippedScreen {
+            banner: screen.banner.map(String::from),
+            title: screen.title.to_string(),
+            content: screen.content.to_string(),
+        });
+        Ok(KeyPress::Enter)
+    }
+
+    fn scroll_speed(&self) -> ScrollSpeed {
+        self.menu.scroll_speed()
+    }
+
+    fn set_scroll_speed(&mut self, speed: ScrollSpeed) {
+        self.menu.set_scroll_speed(speed);
+    }
+
+    fn content_width(&self) -> Option<usize> {
+        self.menu.content_width()
+    }
+
+    fn set_status(&mut self, status: Option<Status>) {
+        self.menu.set_status(status);
+    }
+
+    fn catching_up(&self) -> bool {
+        !self.replay.is_empty() && !self.show_replayed_screens
+    }
+
+    fn flash_damage(&mut self) {
+        // Catching up to a save shouldn't flash or ring for fights which have already happened
+        if !self.catching_up() {
+            self.menu.flash_damage();
+        }
+    }
+
+    fn damage_bell(&self) -> bool {
+        self.menu.damage_bell()
+    }
+
+    fn set_damage_bell(&mut self, bell: bool) {
+        self.menu.set_damage_bell(bell);
+    }
+
+    fn best_weapon_first(&self) -> bool {
+        self.menu.best_weapon_first()
+    }
+
+    fn set_best_weapon_first(&mut self, best_first: bool) {
+        self.menu.set_best_weapon_first(best_first);
+    }
+
+    fn numbered_options(&self) -> bool {
+        self.menu.numbered_options()
+    }
+
+    fn set_numbered_options(&mut self, numbered: bool) {
+        self.menu.set_numbered_options(numbered);
+    }
+
+    fn checkpoint(&mut self) {
+        // Only write the save if something has happened since it was last written, and not while catching up to it
+        if self.save.choices.len() == self.saved_choices || !self.replay.is_empty() {
+            return;
+        }
+
+        // Failing to save shouldn't stop the game, so the next checkpoint just tries again
+        let text = self.save.to_text();
+        let saved = [&self.path, &self.record_path]
+            .into_iter()
+            .flatten()
+            .all(|path| std::fs::write(path, &text).is_ok());
+
+        if saved {
+            self.saved_choices = self.save.choices.len();
+        }
+    }
+}
+
+impl<M> Drop for AutosaveMenu<M> {
+    fn drop(&mut self) {
+        // Choices made since the last checkpoint, such as in a fight the player quit during, are only kept in the recording.
+        // Can't return a Result from drop, and there's nothing else to do if the recording can't be written.
+        if let Some(path) = &self.record_path {
+            let _ = std::fs::write(path, self.save.to_text());
+        }
+    }
+}
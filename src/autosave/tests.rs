@@ -0,0 +1,26 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that a [`Save`] is read back the same as it was written
+#[test]
+fn test_save_text() {
+    let save = Save {
+        seed: Some(1234),
+        choices: vec![Some(1), None, Some(0)],
+    };
+    assert_eq!(save.to_text(), "seed 1234\n1\ncancel\n0");
+    assert_eq!(Save::from_text(&save.to_text()), Some(save));
+
+    let save = Save { seed: None, choices: Vec::new() };
+    assert_eq!(Save::from_text(&save.to_text()), Some(save));
+}
+
+/// Tests that text which isn't a save isn't read as one
+#[test]
+fn test_invalid_save_text() {
+    assert_eq!(Save::from_text(""), None);
+    assert_eq!(Save::from_text("1\n2"), None);
+    assert_eq!(Save::from_text("seed x\n1"), None);
+    assert_eq!(Save::from_text("seed none\n1\nleft"), None);
+}
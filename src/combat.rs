@@ -1,23 +1,30 @@
 //! Implements combat-related functionality, such as enemies and health
 
 mod health;
+mod status_effects;
+pub mod tests;
 
 use std::{
     cmp::Ordering,
     collections::hash_map::DefaultHasher,
+    fmt::Write,
     hash::{Hash, Hasher},
 };
 
 use crate::{
-    items::Item,
-    menu::{Menu, Screen},
-    player::Player, config, 
+    items::{take_one_food, Item, Weapon},
+    effects::Effects,
+    menu::{Menu, MenuSignal, OptionList, Screen},
+    player::{LastAction, Player, BATTLE_IRREVERSIBLE_REASON}, config, difficulty::Difficulty, strings,
 };
 
 pub use health::{Damage, Health};
+pub use status_effects::StatusEffect;
+
+use status_effects::tick_status_effects;
 
 /// An enemy which can be battled
-#[derive(Debug, Hash)]
+#[derive(Debug)]
 pub struct Enemy {
     /// The enemy's name
     pub name: &'static str,
@@ -31,38 +38,73 @@ pub struct Enemy {
     pub health: Health,
     /// The maximum health the enemy can reach
     pub max_health: Health,
+    /// The [`StatusEffect`]s currently affecting the enemy
+    pub status_effects: Vec<StatusEffect>,
+    /// The [`Difficulty`] the game is being played on, which affects how aggressively the enemy fights
+    pub difficulty: Difficulty,
+    /// How the enemy picks its actions
+    pub ai: EnemyAi,
+    /// The [`Action`] the player took on the last turn of the current battle, if there has been one
+    pub last_player_action: Option<Action>,
+    /// The weapons the player has [knocked out of the enemy's hands][Action::Disarm] in the current battle.
+    /// These are out of the enemy's reach until the battle ends.
+    pub disarmed_weapons: Vec<Item>,
+    /// Whether the enemy has dropped below [`ENRAGE_HEALTH_PERCENT`][config::ENRAGE_HEALTH_PERCENT] of their max health,
+    /// so they fight desperately with mostly straight attacks. Once set, this stays set so the player is only told once,
+    /// until the enemy recovers when the player [flees][Action::Flee].
+    pub enraged: bool,
+    /// The number of upcoming turns of the current battle on which the enemy is [stunned][Enemy::stun] and can only do [nothing][Action::Nothing]
+    pub stunned_turns: usize,
+}
+
+// The AI, its memory of the player's actions and whether it's enraged or stunned aren't hashed, so that they only change the enemy's actions where the AI uses them on purpose,
+// rather than by changing every pseudorandom choice
+impl Hash for Enemy {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.description.hash(state);
+        self.inventory.hash(state);
+        self.health.hash(state);
+        self.max_health.hash(state);
+        self.status_effects.hash(state);
+        self.difficulty.hash(state);
+    }
+}
+
+/// How an [`Enemy`] picks its actions in battle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnemyAi {
+    /// The enemy picks its actions pseudorandomly
+    Random,
+    /// The enemy picks its actions pseudorandomly, but expects a player who dodged last turn to dodge the same way again,
+    /// so [aims its attacks][Enemy::predict_dodge] to catch them
+    Predictive,
 }
 
 impl Enemy {
+    /// Stuns the [`Enemy`] so that they do [nothing][Action::Nothing] for the given number of turns, starting from the next turn of the battle.
+    /// If they are already stunned for longer, this doesn't shorten it.
+    pub fn stun(&mut self, turns: usize) {
+        self.stunned_turns = self.stunned_turns.max(turns);
+    }
+
     /// Gets a string describing the enemy carrying out a provided action
     pub fn describe_combat_action(&self, action: Action) -> String {
         use Action::*;
 
         match action {
-            AttackLeft(w) => format!(
-                "The {} attacks to the left with their {}",
-                self.name,
-                self.inventory[w].get_name()
-            ),
-            AttackRight(w) => format!(
-                "The {} attacks to the right with their {}",
-                self.name,
-                self.inventory[w].get_name()
-            ),
-            AttackStraight(w) => format!(
-                "The {} attacks in front of them with their {}",
-                self.name,
-                self.inventory[w].get_name()
-            ),
-            EatFood(f) => format!(
-                "The {} attempts to eat their {}",
-                self.name,
-                self.inventory[f].get_name()
-            ),
-
-            DodgeLeft => format!("The {} dodges to the left", self.name),
-            DodgeRight => format!("The {} dodges to the right", self.name),
-            Nothing => format!("The {} does nothing", self.name),
+            AttackLeft(w) => strings::format("combat.enemy_action.attack_left", &[&self.name, &self.inventory[w].get_name()]),
+            AttackRight(w) => strings::format("combat.enemy_action.attack_right", &[&self.name, &self.inventory[w].get_name()]),
+            AttackStraight(w) => strings::format("combat.enemy_action.attack_straight", &[&self.name, &self.inventory[w].get_name()]),
+            EatFood(f) => strings::format("combat.enemy_action.eat_food", &[&self.name, &self.inventory[f].get_name()]),
+
+            DodgeLeft => strings::format("combat.enemy_action.dodge_left", &[&self.name]),
+            DodgeRight => strings::format("combat.enemy_action.dodge_right", &[&self.name]),
+            Flee => strings::format("combat.enemy_action.flee", &[&self.name]),
+            Throw(w) => strings::format("combat.enemy_action.throw", &[&self.name, &self.inventory[w].get_name()]),
+            Disarm => strings::format("combat.enemy_action.disarm", &[&self.name]),
+            ThrowFood(f) => strings::format("combat.enemy_action.throw_food", &[&self.name, &self.inventory[f].get_name()]),
+            Nothing => strings::format("combat.enemy_action.nothing", &[&self.name]),
         }
     }
 }
@@ -74,14 +116,35 @@ impl Enemy {
 pub enum BattleResult {
     /// The player won the battle
     PlayerWin,
-    /// The player lost the battle
-    PlayerLoss,
+    /// The player lost the battle, and was killed by the given [`Killer`]
+    PlayerLoss(Killer),
     /// The player ran out of turns
     MaxTurnsReached,
+    /// The player ran away from the battle
+    PlayerFled,
+}
+
+/// What killed the player when they lost a battle, to tell them how they died
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Killer {
+    /// The name of the [`Enemy`] who killed the player
+    pub enemy: &'static str,
+    /// The name of the weapon which landed the final hit, or [`None`] if the player was killed by a [`StatusEffect`]
+    pub weapon: Option<&'static str>,
+}
+
+impl Killer {
+    /// Gets a sentence saying what killed the player and on which turn of the loop
+    pub fn describe(self, turn: usize) -> String {
+        match self.weapon {
+            Some(weapon) => strings::format("combat.killer.weapon", &[&self.enemy, &weapon, &turn]),
+            None => strings::format("combat.killer.status_effect", &[&self.enemy, &turn]),
+        }
+    }
 }
 
 /// An action which either a player or an enemy can take during a battle
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
     /// The combatant does nothing
     Nothing,
@@ -103,6 +166,61 @@ pub enum Action {
     /// The combatant dodges to the right.
     /// This means they will not be hit by [straight attacks][Action::AttackStraight], but they will be hit by [attacks to the left][Action::AttackRight]
     DodgeRight,
+    /// The combatant tries to run away from the battle.
+    /// This will succeed unless the opponent lands a [straight attack][Action::AttackStraight] on the same turn.
+    Flee,
+    /// The combatant throws the [throwable][Weapon::throwable] weapon at the given index in their inventory straight ahead.
+    /// This hits before any straight attack, regardless of speed, unless the opponent dodges.
+    /// The weapon is used up until the end of the battle. Only the player throws weapons.
+    Throw(usize),
+    /// The combatant lunges for their opponent's weapon, to knock it out of their hands until the end of the battle.
+    /// This is slow, so it loses to a [straight attack][Action::AttackStraight], and it can be dodged.
+    /// If it connects, it deals a [little damage][config::DISARM_DAMAGE]. Only the player disarms.
+    Disarm,
+    /// The combatant throws the food item at the given index in their inventory at their opponent's face.
+    /// This always hits, using up the food and the combatant's turn, and the opponent spends their next turn doing [nothing][Action::Nothing]
+    /// while they clean themselves up. Only the player throws food.
+    ThrowFood(usize),
+}
+
+/// A hint about the [`Action`] an [`Enemy`] is about to take, shown to the player before they choose their own action.
+/// A tell doesn't say exactly what the enemy will do - an enemy shifting their weight to the left could be about to attack or dodge that way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tell {
+    /// The enemy is about to attack or dodge to the left
+    Left,
+    /// The enemy is about to attack or dodge to the right
+    Right,
+    /// The enemy is about to attack straight
+    Straight,
+    /// The enemy is about to do nothing
+    Hesitant,
+}
+
+impl Tell {
+    /// All tells, used to pick a misleading one
+    const ALL: [Self; 4] = [Self::Left, Self::Right, Self::Straight, Self::Hesitant];
+
+    /// Gets the [`Tell`] which truthfully hints at an [`Action`], or [`None`] if the action has no tell
+    pub const fn for_action(action: Action) -> Option<Self> {
+        match action {
+            Action::AttackLeft(_) | Action::DodgeLeft => Some(Self::Left),
+            Action::AttackRight(_) | Action::DodgeRight => Some(Self::Right),
+            Action::AttackStraight(_) => Some(Self::Straight),
+            Action::Nothing => Some(Self::Hesitant),
+            Action::EatFood(_) | Action::Flee | Action::Throw(_) | Action::Disarm | Action::ThrowFood(_) => None,
+        }
+    }
+
+    /// Gets a sentence describing an enemy with the given name showing the tell
+    pub fn describe(self, enemy_name: &str) -> String {
+        match self {
+            Self::Left => strings::format("combat.tell.left", &[&enemy_name]),
+            Self::Right => strings::format("combat.tell.right", &[&enemy_name]),
+            Self::Straight => strings::format("combat.tell.straight", &[&enemy_name]),
+            Self::Hesitant => strings::format("combat.tell.hesitant", &[&enemy_name]),
+        }
+    }
 }
 
 impl Enemy {
@@ -115,6 +233,68 @@ impl Enemy {
         s.finish()
     }
 
+    /// Pick which weapon in the [`Enemy`]'s inventory to use, based on a hash from [`hash_with_turn`][Enemy::hash_with_turn].
+    /// The enemy will pseudorandomly either go for the fastest weapon to win clashes, or the hardest-hitting weapon to try to get a kill.
+    /// Returns [`None`] if the enemy has no weapons.
+    fn choose_weapon(&self, hash: u64) -> Option<usize> {
+        let weapons = self
+            .inventory
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| match item {
+                Item::Weapon(w) => Some((i, w)),
+                _ => None,
+            });
+
+        // Use a different part of the hash than is used to pick the action, so the two choices are independent
+        let (index, _) = if (hash >> 3) & 1 == 0 {
+            weapons.min_by_key(|(_, w)| w.speed)?
+        } else {
+            weapons.max_by_key(|(_, w)| w.straight_damage)?
+        };
+
+        Some(index)
+    }
+
+    /// If the [`Enemy`] has no weapons, takes the weapon with the highest [straight damage][Weapon::straight_damage] out of `items`.
+    /// Only weapons are taken, so items such as the [`Maps`][Item::Maps] are left for the player.
+    ///
+    /// ### Returns:
+    /// The name of the weapon which was taken, if any
+    fn take_best_weapon(&mut self, items: &mut Vec<Item>) -> Option<&'static str> {
+        if self.inventory.iter().any(|item| matches!(item, Item::Weapon(_))) {
+            return None;
+        }
+
+        let (index, _) = items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| match item {
+                Item::Weapon(w) => Some((i, w.straight_damage)),
+                _ => None,
+            })
+            .max_by_key(|(_, damage)| *damage)?;
+
+        let weapon = items.remove(index);
+        let name = weapon.get_name();
+        self.inventory.push(weapon);
+
+        Some(name)
+    }
+
+    /// Checks whether the [`Enemy`] has just dropped low enough on health to become [enraged][Enemy::enraged].
+    ///
+    /// ### Returns:
+    /// A sentence telling the player, if the enemy has become enraged for the first time
+    fn check_enraged(&mut self) -> Option<String> {
+        if self.enraged || self.health.is_0() || self.health.as_usize() * 100 >= self.max_health.as_usize() * config::ENRAGE_HEALTH_PERCENT {
+            return None;
+        }
+
+        self.enraged = true;
+        Some(strings::format("combat.enraged", &[&self.name]))
+    }
+
     /// Determine what action the [`Enemy`] will take this turn
     fn choose_combat_action(&mut self, turn_number: usize) -> Action {
         // If enemy is at less than half health and has food, then eat it
@@ -128,23 +308,24 @@ impl Enemy {
             }
         }
 
-        // Find the index of the first weapon in the inventory, if there is one
-        let weapon_index = self
-            .inventory
-            .iter()
-            .position(|i| matches!(i, Item::Weapon(_)));
         // Get a hash of self using the turn number
         let hash = self.hash_with_turn(turn_number);
+        // Pick which weapon to use this turn, if there are any
+        let weapon_index = self.choose_weapon(hash);
 
-        // Pseudorandomly pick an action
-        match weapon_index {
-            Some(weapon_index) => match hash % 8 {
-                0 => Action::AttackLeft(weapon_index),
-                1..=3 => Action::AttackStraight(weapon_index),
-                4 => Action::AttackRight(weapon_index),
-                5 => Action::DodgeLeft,
-                6 => Action::DodgeRight,
-                7 => Action::Nothing,
+        // Pseudorandomly pick an action. Enemies attack less on easier difficulties and more on harder ones.
+        // An enraged enemy mostly attacks straight, using a part of the hash which isn't used for anything else.
+        let action = match weapon_index {
+            Some(weapon_index) if self.enraged && (hash >> 24) % 100 < config::ENRAGED_STRAIGHT_ATTACK_PERCENT => {
+                Action::AttackStraight(weapon_index)
+            }
+            Some(weapon_index) => match (self.difficulty, hash % 8) {
+                (_, 0) => Action::AttackLeft(weapon_index),
+                (Difficulty::Easy, 1..=2) | (Difficulty::Normal, 1..=3) | (Difficulty::Hard, 1..=4) => Action::AttackStraight(weapon_index),
+                (Difficulty::Easy, 3) | (Difficulty::Normal, 4) | (Difficulty::Hard, 5) => Action::AttackRight(weapon_index),
+                (Difficulty::Easy, 4) | (Difficulty::Normal, 5) | (Difficulty::Hard, 6) => Action::DodgeLeft,
+                (Difficulty::Easy, 5) | (Difficulty::Normal, 6) | (Difficulty::Hard, 7) => Action::DodgeRight,
+                (Difficulty::Easy, 6..=7) | (Difficulty::Normal, 7) => Action::Nothing,
                 _ => unreachable!(),
             },
             None => match hash % 7 {
@@ -153,97 +334,538 @@ impl Enemy {
                 5..=6 => Action::DodgeRight,
                 _ => unreachable!(),
             },
+        };
+
+        match (self.ai, weapon_index) {
+            (EnemyAi::Predictive, Some(weapon_index)) => self.predict_dodge(action, weapon_index),
+            _ => action,
         }
     }
+
+    /// Changes the [`Action`] an [`Enemy`] picked to catch a player who dodged last turn, expecting them to dodge the same way again.
+    /// Attacks to either side are aimed the way the player dodged, and doing nothing becomes an attack that way.
+    /// Straight attacks and dodges are left alone, so the enemy is still not completely predictable.
+    ///
+    /// ### Params:
+    /// * `action`: the action the enemy picked
+    /// * `weapon_index`: the index of the weapon the enemy picked to attack with
+    fn predict_dodge(&self, action: Action, weapon_index: usize) -> Action {
+        let attack_into_dodge = match self.last_player_action {
+            Some(Action::DodgeLeft) => Action::AttackLeft,
+            Some(Action::DodgeRight) => Action::AttackRight,
+            _ => return action,
+        };
+
+        match action {
+            Action::AttackLeft(_) | Action::AttackRight(_) | Action::Nothing => attack_into_dodge(weapon_index),
+            _ => action,
+        }
+    }
+
+    /// Gets the [`Tell`] the [`Enemy`] gives away before taking an action.
+    /// How often the tell is truthful depends on the enemy's [`Difficulty`] - otherwise, a different tell is given to mislead the player.
+    ///
+    /// This doesn't change the enemy's state, so it can be called between [`choose_combat_action`][Enemy::choose_combat_action] and the action being carried out.
+    ///
+    /// ### Params:
+    /// * `action`: the action the enemy chose this turn
+    /// * `turn_number`: the turn number which was passed to [`choose_combat_action`][Enemy::choose_combat_action]
+    ///
+    /// ### Returns:
+    /// The tell, or [`None`] if the action doesn't have one
+    pub fn tell(&self, action: Action, turn_number: usize) -> Option<Tell> {
+        let true_tell = Tell::for_action(action)?;
+
+        // Use parts of the hash which aren't used to pick the action or weapon, so whether the tell is truthful is independent of them
+        let hash = self.hash_with_turn(turn_number);
+        if (hash >> 8) % 100 < self.difficulty.tell_reliability() {
+            return Some(true_tell);
+        }
+
+        // Pick one of the other tells
+        let true_index = Tell::ALL.iter().position(|&t| t == true_tell)?;
+        #[allow(clippy::cast_possible_truncation)] // The value is less than 3
+        let offset = ((hash >> 16) % 3) as usize + 1;
+        Some(Tell::ALL[(true_index + offset) % Tell::ALL.len()])
+    }
 }
 
 /// Carries out a battle between the player and the enemy. If the player wins the battle, they will pick up any items which the enemy had at the end of the battle.
+/// Otherwise, the enemy is put back into the [`RoomState`][crate::rooms::RoomState] of the room the battle took place in.
 ///
 /// ### Params:
 /// * `player`: the [`Player`]'s current state
 /// * `enemy`: the [`Enemy`] to battle
 /// * `menu`: the [`Menu`] to display to
+/// * `effects`: the [`Effects`] to play when the player is hurt
 ///
 /// ### Returns:
 /// A [`BattleResult`] representing the outcome of the battle. If this is a [player loss][BattleResult::PlayerLoss], the player lost the battle and the loop should reset.
+/// If the user quit the game, a [`MenuSignal`] is returned instead.
 pub fn battle(
     player: &mut Player,
     mut enemy: Enemy,
     menu: &mut impl Menu,
-) -> BattleResult {
-    let screen = Screen {
-        title: &format!("You are spotted by the {}", enemy.name),
-        content: &format!(
-            "The {} sees you and blocks your path. They are {}",
-            enemy.name, enemy.description
-        ),
+    effects: &mut impl Effects,
+) -> Result<BattleResult, MenuSignal> {
+    menu.show_screen(Screen::new(
+        &strings::format("combat.spotted.title", &[&enemy.name]),
+        &strings::format("combat.spotted.content", &[&enemy.name, &enemy.description]),
+    ))?;
+
+    // Fighting can't be undone, even if the player got here by moving
+    player.last_action = Some(LastAction::Irreversible(BATTLE_IRREVERSIBLE_REASON));
+    // The enemy only remembers the player's moves from the current battle
+    enemy.last_player_action = None;
+
+    // The player may leave the room by fleeing, so keep track of where the enemy is
+    let battle_room = player.room;
+    // The log only lasts for this battle, so it doesn't grow over a long run
+    let mut log = Vec::new();
+    let result = fight(player, &mut enemy, &mut log, menu, effects)?;
+
+    offer_fight_review(&log, menu)?;
+
+    // Status effects and stuns only last for the battle they were inflicted in
+    player.status_effects.clear();
+    enemy.status_effects.clear();
+    player.stunned_turns = 0;
+    enemy.stunned_turns = 0;
+
+    // Thrown weapons are left on the floor of the room the battle took place in
+    let thrown_weapons = std::mem::take(&mut player.thrown_weapons);
+    let disarmed_weapons = std::mem::take(&mut enemy.disarmed_weapons);
+
+    if result == BattleResult::PlayerWin {
+        player.enemies_defeated += 1;
+        // The player picks up the weapons they knocked away along with the rest of the enemy's items
+        enemy.inventory.extend(disarmed_weapons);
+        win_battle(player, enemy, thrown_weapons, menu)?;
+    } else {
+        // The player has no chance to pick up what they threw, so it stays in the room.
+        // The same goes for the enemy's weapons, so they pick them back up at the start of the next battle.
+        player.room_graph.get_state_mut(battle_room).items.extend(thrown_weapons);
+        player.room_graph.get_state_mut(battle_room).items.extend(disarmed_weapons);
+
+        if result == BattleResult::PlayerFled {
+            flee_battle(player, &mut enemy, menu)?;
+        }
+
+        // The enemy is only removed from the room if the player beats them
+        player.room_graph.get_state_mut(battle_room).enemy = Some(enemy);
+    }
+
+    Ok(result)
+}
+
+/// Carries out a battle without showing anything, for measuring how likely a loadout is to win.
+/// The turns are resolved the same way as in [`battle`], but the player's [`Action`]s come from `strategy` instead of a [`Menu`].
+///
+/// Unlike [`battle`], the player isn't moved if they flee and doesn't pick anything up if they win.
+/// Their [`StatusEffect`]s and stuns are cleared at the end. Anything they threw or knocked out of the enemy's hands is left on the floor of the room,
+/// whoever won. The enemy is dropped at the end, along with its own status effects.
+///
+/// ### Params:
+/// * `player`: the [`Player`]'s state, which is changed by the battle
+/// * `enemy`: the [`Enemy`] to battle
+/// * `strategy`: chooses the player's [`Action`] each turn, given the number of turns the battle has lasted so far.
+///   It should only choose actions the player could choose in [`battle`], such as attacking with a weapon they have.
+#[cfg(test)]
+pub fn simulate_battle(player: &mut Player, mut enemy: Enemy, strategy: fn(&Player, &Enemy, usize) -> Action) -> BattleResult {
+    enemy.last_player_action = None;
+
+    let mut turn = 0;
+    let result = loop {
+        let start = start_turn(player, &mut enemy);
+        if let Some(result) = start.result {
+            break result;
+        }
+
+        let player_action = if start.player_stunned {
+            Action::Nothing
+        } else {
+            strategy(player, &enemy, turn)
+        };
+        let (_, result) = finish_turn(player, &mut enemy, &start, player_action);
+        turn += 1;
+
+        if let Some(result) = result {
+            break result;
+        }
+    };
+
+    player.status_effects.clear();
+    player.stunned_turns = 0;
+    let thrown_weapons = std::mem::take(&mut player.thrown_weapons);
+    player.get_room_state_mut().items.extend(thrown_weapons);
+    player.get_room_state_mut().items.append(&mut enemy.disarmed_weapons);
+
+    result
+}
+
+/// Applies the [`StatusEffect`]s affecting the player and the enemy at the start of a turn.
+/// Returns a description of the effects, or [`None`] if there weren't any.
+fn apply_status_effects(player: &mut Player, enemy: &mut Enemy) -> Option<String> {
+    let player_effects = tick_status_effects(&mut player.status_effects, &mut player.health);
+    let enemy_effects = tick_status_effects(&mut enemy.status_effects, &mut enemy.health);
+
+    if player_effects.is_empty() && enemy_effects.is_empty() {
+        return None;
+    }
+
+    let mut text = String::new();
+
+    for (name, damage) in player_effects {
+        text += &strings::format("combat.status_effect.player", &[&name, &damage]);
+    }
+    for (name, damage) in enemy_effects {
+        text += &strings::format("combat.status_effect.enemy", &[&enemy.name, &name, &damage]);
+    }
+
+    Some(text)
+}
+
+/// What happens at the start of a turn of a battle, before the player chooses what to do.
+/// This is worked out by [`start_turn`] without showing anything, so that it can be shown by [`fight`] or skipped when a battle is simulated for balance testing.
+struct TurnStart {
+    /// A description of the [`StatusEffect`]s which took effect and everyone's health afterwards, if any did
+    effects_text: Option<String>,
+    /// How the battle ended, if the [`StatusEffect`]s ended it. In this case, nobody acts this turn.
+    result: Option<BattleResult>,
+    /// A description of the enemy picking up a weapon from the floor, if they did
+    grab_text: Option<String>,
+    /// Whether the player is stunned, so they do [nothing][Action::Nothing] this turn
+    player_stunned: bool,
+    /// Whether the enemy is stunned, so they do [nothing][Action::Nothing] this turn
+    enemy_stunned: bool,
+    /// The [`Action`] the enemy will take this turn
+    enemy_action: Action,
+}
+
+/// Starts a turn of a battle, applying [`StatusEffect`]s and stuns and choosing the enemy's [`Action`]
+fn start_turn(player: &mut Player, enemy: &mut Enemy) -> TurnStart {
+    let mut start = TurnStart {
+        effects_text: None,
+        result: None,
+        grab_text: None,
+        player_stunned: false,
+        enemy_stunned: false,
+        enemy_action: Action::Nothing,
     };
 
-    menu.show_screen(screen);
+    // Apply status effects before either combatant acts, as these can end the battle on their own
+    if let Some(effects_text) = apply_status_effects(player, enemy) {
+        let enraged_text = enemy.check_enraged().map(|text| format!("\n{text}")).unwrap_or_default();
+        start.effects_text = Some(format!("{effects_text}{enraged_text}\n{}", health_text(player, enemy)));
+
+        if player.health.is_0() {
+            start.result = Some(BattleResult::PlayerLoss(Killer { enemy: enemy.name, weapon: None }));
+        } else if enemy.health.is_0() {
+            start.result = Some(BattleResult::PlayerWin);
+        }
+
+        if start.result.is_some() {
+            return start;
+        }
+    }
+
+    // An unarmed enemy grabs a weapon from the floor of the room if there is one, so that it can use it from this turn on
+    start.grab_text = enemy
+        .take_best_weapon(&mut player.get_room_state_mut().items)
+        .map(|weapon_name| strings::format("combat.enemy_grabs_weapon", &[&enemy.name, &weapon_name]));
+
+    // A stunned combatant does nothing this turn, and the stun wears off by a turn
+    start.player_stunned = take_stunned_turn(&mut player.stunned_turns);
+    start.enemy_stunned = take_stunned_turn(&mut enemy.stunned_turns);
+
+    // The enemy's action is chosen before the player's, so that the player can be shown its tell
+    if !start.enemy_stunned {
+        start.enemy_action = enemy.choose_combat_action(player.remaining_turns);
+    }
+
+    start
+}
+
+/// Carries out the player's and the enemy's [`Action`]s for a turn which was [started][start_turn], using up the turn.
+///
+/// ### Params:
+/// * `start`: the start of the turn, which says what the enemy does and who is stunned
+/// * `player_action`: the [`Action`] the player takes, which should be [nothing][Action::Nothing] if they are stunned
+///
+/// ### Returns:
+/// A description of the turn and everyone's health afterwards, and how the battle ended if it did
+fn finish_turn(player: &mut Player, enemy: &mut Enemy, start: &TurnStart, player_action: Action) -> (String, Option<BattleResult>) {
+    let enemy_action = start.enemy_action;
+
+    // Fleeing only fails if the enemy lands a straight hit
+    let fled = matches!(player_action, Action::Flee) && !matches!(enemy_action, Action::AttackStraight(_));
+
+    let turn_text = execute_actions(player, enemy, player_action, enemy_action);
+    enemy.last_player_action = Some(player_action);
 
-    // Loop until either the player or the enemy reaches 0 health or the player runs out of turns
+    // Every turn of the battle uses up time, including the last one
+    player.use_turn();
+
+    // Describe the result of the turn, including who was stunned and whether it pushed the enemy into desperation
+    let stun_text = format!(
+        "{}{}",
+        if start.player_stunned { strings::get("combat.stunned.player") } else { "" },
+        if start.enemy_stunned { strings::format("combat.stunned.enemy", &[&enemy.name]) } else { String::new() },
+    );
+    let enraged_text = enemy.check_enraged().map(|text| format!("\n{text}")).unwrap_or_default();
+    let turn_text = format!(
+        "{stun_text}{turn_text}{enraged_text}\n{}\n{}",
+        health_text(player, enemy),
+        strings::format("combat.time_left", &[&player.get_remaining_time()]),
+    );
+
+    let result = if player.health.is_0() {
+        let weapon = match enemy_action {
+            Action::AttackLeft(w) | Action::AttackStraight(w) | Action::AttackRight(w) => Some(enemy.inventory[w].get_name()),
+            _ => None,
+        };
+        Some(BattleResult::PlayerLoss(Killer { enemy: enemy.name, weapon }))
+    } else if enemy.health.is_0() {
+        Some(BattleResult::PlayerWin)
+    } else if fled {
+        Some(BattleResult::PlayerFled)
+    } else if player.remaining_turns == 0 {
+        Some(BattleResult::MaxTurnsReached)
+    } else {
+        None
+    };
+
+    (turn_text, result)
+}
+
+/// Describes the player's and the enemy's health, to show after anything which may have changed it
+fn health_text(player: &Player, enemy: &Enemy) -> String {
+    strings::format(
+        "combat.health",
+        &[&player.health, &player.max_health, &enemy.name, &enemy.health, &enemy.max_health],
+    )
+}
+
+/// Carries out the turns of a battle until either the player or the enemy reaches 0 health or the player runs out of turns.
+/// The text of each turn and status effect tick is pushed to `log`.
+fn fight(
+    player: &mut Player,
+    enemy: &mut Enemy,
+    log: &mut Vec<String>,
+    menu: &mut impl Menu,
+    effects: &mut impl Effects,
+) -> Result<BattleResult, MenuSignal> {
     loop {
-        // Get the player and enemy's actions
-        let player_action = player.choose_combat_action(menu);
-        let enemy_action = enemy.choose_combat_action(player.remaining_turns);
-
-        // Carry out the actions
-        let turn_text = execute_actions(player, &mut enemy, player_action, enemy_action);
-
-        // Show the result of the turn
-        let turn_text = format!(
-            "{turn_text}\nYou are now at {}/{} HP.\nThe {} is now at {}/{} HP",
-            player.health, player.max_health, enemy.name, enemy.health, enemy.max_health,
-        );
-
-        let screen = Screen {
-            title: "Turn Result",
-            content: &turn_text,
+        let start = start_turn(player, enemy);
+
+        if let Some(effects_text) = &start.effects_text {
+            let screen = Screen::new(strings::get("combat.title.status_effects"), effects_text);
+
+            menu.show_screen(screen)?;
+            log.push(effects_text.clone());
+        }
+        if let Some(result) = start.result {
+            return Ok(result);
+        }
+
+        if let Some(grab_text) = &start.grab_text {
+            let screen = Screen::new(strings::get("combat.title.enemy_arms"), grab_text);
+
+            menu.show_screen(screen)?;
+            log.push(grab_text.clone());
+        }
+
+        // A stunned enemy gives nothing away, as it's plain to see they can't act.
+        // Otherwise, the tell is for the action which was already chosen, so showing it doesn't change what the enemy does.
+        let tell = if start.enemy_stunned {
+            Some(strings::format("combat.tell.stunned", &[&enemy.name]))
+        } else {
+            enemy.tell(start.enemy_action, player.remaining_turns).map(|tell| tell.describe(enemy.name))
         };
 
-        menu.show_screen(screen);
+        // A stunned player doesn't get to choose
+        let player_action = if start.player_stunned {
+            Action::Nothing
+        } else {
+            player.choose_combat_action(tell.as_deref(), menu)?
+        };
 
-        if player.health.is_0() {
-            return BattleResult::PlayerLoss;
+        // Carry out the actions, letting the player know straight away if they were hurt
+        let health_before = player.health;
+        let (turn_text, result) = finish_turn(player, enemy, &start, player_action);
+        if player.health < health_before {
+            menu.flash_damage();
+            if !menu.catching_up() {
+                effects.on_hit();
+            }
         }
-        if enemy.health.is_0() {
-            win_battle(player, enemy, menu);
-            return BattleResult::PlayerWin;
+        player.show_remaining_time(menu);
+
+        let screen = Screen::new(strings::get("combat.title.turn_result"), &turn_text);
+
+        menu.show_screen(screen)?;
+        log.push(turn_text);
+
+        if let Some(result) = result {
+            return Ok(result);
         }
+    }
+}
+
+/// Asks the player whether they want to review the fight, and if so shows the whole `log` on one [`Screen`]
+fn offer_fight_review(log: &[String], menu: &mut impl Menu) -> Result<(), MenuSignal> {
+    if log.is_empty() {
+        return Ok(());
+    }
 
-        player.remaining_turns -= 1;
+    let options = &[strings::get("combat.review.continue").to_string(), strings::get("combat.review.review").to_string()];
+    let list = OptionList::new(options, strings::get("combat.review.title"));
 
-        if player.remaining_turns == 0 {
-            return BattleResult::MaxTurnsReached
+    if menu.show_option_list(list)? == 1 {
+        menu.show_screen(Screen::new(strings::get("combat.title.fight_log"), &log.join("\n\n")))?;
+    }
+
+    Ok(())
+}
+
+/// What happens when both combatants attack straight at the same time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StraightClash {
+    /// Only the player hits, as their weapon was much faster and interrupted the enemy's attack
+    PlayerHits,
+    /// Only the enemy hits, as their weapon was much faster and interrupted the player's attack
+    EnemyHits,
+    /// Both hit for full damage, as their weapons were just as fast and easy to control or the faster one was too heavy to get out of the way
+    BothHit,
+    /// Both hit, but the weapons were just as fast and the player's was [easier to control][speed_tiebreak], so the player only takes [reduced damage][Damage::glancing]
+    PlayerControls,
+    /// Both hit, but the weapons were just as fast and the enemy's was [easier to control][speed_tiebreak], so the enemy only takes [reduced damage][Damage::glancing]
+    EnemyControls,
+    /// Both hit for [reduced damage][Damage::glancing], as one weapon was only [slightly faster][config::GLANCING_HIT_MAX_SPEED_GAP]
+    BothGlance,
+}
+
+/// Works out who hits whom when both combatants attack straight, from the speeds of their weapons.
+/// The bigger the gap in speed, the more of an advantage the faster weapon has.
+/// A heavy weapon commits its wielder to the attack, so they get hit in full even if they were faster.
+fn straight_clash(p_weapon: &Weapon, e_weapon: &Weapon) -> StraightClash {
+    let gap = p_weapon.speed.abs_diff(e_weapon.speed);
+
+    match p_weapon.speed.cmp(&e_weapon.speed) {
+        Ordering::Equal => speed_tiebreak(p_weapon, e_weapon),
+        Ordering::Less if p_weapon.heavy => StraightClash::BothHit,
+        Ordering::Greater if e_weapon.heavy => StraightClash::BothHit,
+        _ if gap <= config::GLANCING_HIT_MAX_SPEED_GAP => StraightClash::BothGlance,
+        Ordering::Less => StraightClash::PlayerHits,
+        Ordering::Greater => StraightClash::EnemyHits,
+    }
+}
+
+/// Breaks a tie between two [`Weapon`]s of the same speed using their [`dodge_damage`][Weapon::dodge_damage], which shows how well each can be controlled.
+/// The wielder of the better controlled weapon only gets grazed, while weapons which are just as easy to control still both hit in full.
+fn speed_tiebreak(p_weapon: &Weapon, e_weapon: &Weapon) -> StraightClash {
+    match p_weapon.dodge_damage.cmp(&e_weapon.dodge_damage) {
+        Ordering::Equal => StraightClash::BothHit,
+        Ordering::Greater => StraightClash::PlayerControls,
+        Ordering::Less => StraightClash::EnemyControls,
+    }
+}
+
+/// Gets the damage a [`Weapon`] deals when it lands a clean straight hit, which is a [critical hit][Damage::critical] if the weapon is fast enough.
+///
+/// ### Returns:
+/// The damage dealt, and a message to start the turn's text with - "Critical hit! " if the hit was critical, or an empty string if not
+fn clean_hit_damage(weapon: &Weapon) -> (Damage, String) {
+    if weapon.speed <= config::CRITICAL_HIT_MAX_SPEED {
+        (weapon.straight_damage.critical(), format!("{} ", strings::get("combat.critical_hit")))
+    } else {
+        (weapon.straight_damage, String::new())
+    }
+}
+
+/// Adds the [`StatusEffect`] inflicted by a [`Weapon`] to the effects of the combatant it hit, if the weapon inflicts one.
+///
+/// ### Params:
+/// * `weapon`: the [`Weapon`] which landed a hit
+/// * `target_effects`: the [`StatusEffect`]s of the combatant who was hit
+/// * `enemy_name`: the name of the [`Enemy`] who was hit, or [`None`] if the player was hit
+///
+/// ### Returns:
+/// A line of text describing the effect, or an empty string if the weapon doesn't inflict one
+fn inflict_status_effect(weapon: &Weapon, target_effects: &mut Vec<StatusEffect>, enemy_name: Option<&str>) -> String {
+    match weapon.inflicts {
+        Some(effect) => {
+            target_effects.push(effect);
+            let text = match enemy_name {
+                Some(name) => strings::format("combat.inflicted.enemy", &[&name, &effect.name]),
+                None => strings::format("combat.inflicted.player", &[&effect.name]),
+            };
+            format!("\n{text}")
         }
+        None => String::new(),
     }
 }
 
 /// Shows the player a battle win screen and adds the enemy's items to the player's inventory.
-fn win_battle(player: &mut Player, enemy: Enemy, menu: &mut impl Menu) {
-    let mut result_text = "You won the battle!\n\n".to_string();
+fn win_battle(player: &mut Player, enemy: Enemy, thrown_weapons: Vec<Item>, menu: &mut impl Menu) -> Result<(), MenuSignal> {
+    let mut result_text = format!("{}\n\n", strings::get("combat.won"));
+
+    for item in &thrown_weapons {
+        result_text += &strings::format("combat.recover_thrown", &[&item.get_name()]);
+    }
+    if !thrown_weapons.is_empty() {
+        result_text += "\n";
+    }
 
     if !enemy.inventory.is_empty() {
-        result_text += &format!(
-            "You pick up the items that the {} was carrying:\n",
-            enemy.name
-        );
+        result_text += &strings::format("combat.loot", &[&enemy.name]);
     }
 
     for item in &enemy.inventory {
-        result_text += &format!("• {} - {}\n", item.get_name(), item.get_description());
+        writeln!(result_text, "• {{green}}{}{{/}} - {}", item.get_display_name(), item.get_description())
+            .expect("Writing to a String should never fail");
     }
 
-    let screen = Screen {
-        title: "Battle Result",
-        content: &result_text,
-    };
+    let screen = Screen::new(strings::get("combat.title.battle_result"), &result_text);
+
+    menu.show_screen(screen)?;
 
-    menu.show_screen(screen);
+    // Recovered weapons were already the player's, so they don't count as collected
+    player.inventory.extend(thrown_weapons);
 
+    // Anything the player can't carry is left on the floor to pick up later
     for item in enemy.inventory {
-        player.pick_up_item(item);
+        if let Err(item) = player.pick_up_item(item) {
+            player.get_room_state_mut().items.push(item);
+        }
     }
+
+    Ok(())
+}
+
+/// Restores the enemy to full health and moves the player back to the room they came from.
+/// This means the fight can be re-attempted from the start.
+fn flee_battle(player: &mut Player, enemy: &mut Enemy, menu: &mut impl Menu) -> Result<(), MenuSignal> {
+    // A recovered enemy has no reason to be desperate, so they can become enraged again in the next fight
+    enemy.health = enemy.max_health;
+    enemy.enraged = false;
+
+    let previous_room = player
+        .previous_room
+        .expect("Player should only be able to flee if they came from another room");
+
+    menu.show_screen(Screen::new(
+        &strings::format("combat.fled.title", &[&previous_room.get_name()]),
+        &strings::format(
+            "combat.fled.content",
+            &[&enemy.name, &previous_room.get_name(), &previous_room.get_description()],
+        ),
+    ))?;
+
+    player.previous_room = Some(player.room);
+    player.room = previous_room;
+
+    Ok(())
 }
 
 /// Carries out the actions performed by the player and enemy on a given turn.
@@ -256,6 +878,7 @@ fn win_battle(player: &mut Player, enemy: Enemy, menu: &mut impl Menu) {
 ///
 /// ### Returns:
 /// A string containing a short description of the result of the turn
+#[allow(clippy::too_many_lines)]
 fn execute_actions(
     player: &mut Player,
     enemy: &mut Enemy,
@@ -264,29 +887,62 @@ fn execute_actions(
 ) -> String {
     use Action::*;
 
+    // Describe the actions before they're carried out, as a thrown weapon is removed from the inventory
+    let player_description = player.describe_combat_action(player_action);
+    let enemy_description = enemy.describe_combat_action(enemy_action);
+
+    // Thrown food always hits, but it doesn't stop the enemy's action this turn, so the player is otherwise doing nothing
+    let (player_action, food_text) = match player_action {
+        ThrowFood(p) => {
+            let food = take_one_food(&mut player.inventory, p);
+            enemy.stun(1);
+            let text = format!("\n{}", strings::format("combat.result.throw_food", &[&food.name, &enemy.name]));
+            (Nothing, text)
+        }
+        _ => (player_action, String::new()),
+    };
+
     // Take the turn
     let result_text = match (player_action, enemy_action) {
+        // Only the player can throw weapons or disarm
+        (_, Throw(_)) => unreachable!("Enemies shouldn't throw weapons"),
+        (_, Disarm) => unreachable!("Enemies shouldn't disarm"),
+        (_, ThrowFood(_)) => unreachable!("Enemies shouldn't throw food"),
+        (ThrowFood(_), _) => unreachable!("Thrown food should already have been handled"),
+        // Player throws a weapon and the enemy dodges
+        (Throw(p), DodgeLeft | DodgeRight) => {
+            let weapon = throw_weapon(player, p);
+            strings::format("combat.result.throw_dodged", &[&weapon.name, &enemy.name])
+        }
+        // Player throws a weapon, which hits before any straight attack
+        (Throw(p), Nothing | AttackLeft(_) | AttackStraight(_) | AttackRight(_) | EatFood(_) | Flee) => {
+            let weapon = throw_weapon(player, p);
+            let damage = weapon.straight_damage;
+            enemy.health -= damage;
+            let effect_text = inflict_status_effect(weapon, &mut enemy.status_effects, Some(enemy.name));
+
+            let text = strings::format("combat.result.throw_hit", &[&weapon.name, &enemy.name, &damage]);
+            format!("{text}{effect_text}")
+        }
         // Player hits enemy straight
-        (AttackStraight(p), Nothing | AttackLeft(_) | AttackRight(_) | EatFood(_)) => {
+        (AttackStraight(p), Nothing | AttackLeft(_) | AttackRight(_) | EatFood(_) | Flee) => {
             let Item::Weapon(weapon) = &player.inventory[p] else {unreachable!()};
-            let damage = weapon.straight_damage;
+            let (damage, critical_text) = clean_hit_damage(weapon);
             enemy.health -= damage;
+            let effect_text = inflict_status_effect(weapon, &mut enemy.status_effects, Some(enemy.name));
 
-            format!(
-                "You hit the {} with your {} and dealt {} damage.",
-                enemy.name, weapon.name, damage
-            )
+            let text = strings::format("combat.result.player_hits", &[&enemy.name, &weapon.name, &damage]);
+            format!("{critical_text}{text}{effect_text}")
         }
         // Enemy hits player straight
-        (Nothing | AttackLeft(_) | AttackRight(_) | EatFood(_), AttackStraight(e)) => {
+        (Nothing | AttackLeft(_) | AttackRight(_) | EatFood(_) | Flee, AttackStraight(e)) => {
             let Item::Weapon(weapon) = &enemy.inventory[e] else {unreachable!()};
-            let damage = weapon.straight_damage;
+            let (damage, critical_text) = clean_hit_damage(weapon);
             player.health -= damage;
+            let effect_text = inflict_status_effect(weapon, &mut player.status_effects, None);
 
-            format!(
-                "You hit the {} with your {} and dealt {} damage.",
-                enemy.name, weapon.name, damage
-            )
+            let text = strings::format("combat.result.enemy_hits", &[&enemy.name, &weapon.name, &damage]);
+            format!("{critical_text}{text}{effect_text}")
         }
         // Both attack straight
         (AttackStraight(p), AttackStraight(e)) => {
@@ -297,57 +953,130 @@ fn execute_actions(
             let e_damage = e_weapon.straight_damage;
 
             // What happens when both combatants attack is determined by the speed values of their weapons
-            match p_weapon.speed.cmp(&e_weapon.speed) {
-                // If the player's weapon is faster, only the player hits
-                Ordering::Less => {
+            match straight_clash(p_weapon, e_weapon) {
+                // If the player's weapon is much faster, only the player hits
+                StraightClash::PlayerHits => {
+                    let (p_damage, critical_text) = clean_hit_damage(p_weapon);
                     enemy.health -= p_damage;
-                    "You both attacked, and you were faster and got away unscathed".to_string()
+                    let effect_text = inflict_status_effect(p_weapon, &mut enemy.status_effects, Some(enemy.name));
+                    format!("{critical_text}{}{effect_text}", strings::get("combat.result.clash.player_hits"))
+                }
+                // If the enemy's weapon is much faster, only the enemy hits
+                StraightClash::EnemyHits => {
+                    let (e_damage, critical_text) = clean_hit_damage(e_weapon);
+                    player.health -= e_damage;
+                    let effect_text = inflict_status_effect(e_weapon, &mut player.status_effects, None);
+                    format!("{critical_text}{}{effect_text}", strings::format("combat.result.clash.enemy_hits", &[&enemy.name]))
                 }
-                // If the enemy's weapon is faster, on the the enemy hits
-                Ordering::Greater => {
+                // If they have the same speed and control or the faster weapon is heavy, both get hit.
+                StraightClash::BothHit => {
+                    enemy.health -= p_damage;
                     player.health -= e_damage;
-                    format!("You both attacked, but the {} was faster and you couldn't get a hit in.", enemy.name)
+                    let p_effect_text = inflict_status_effect(p_weapon, &mut enemy.status_effects, Some(enemy.name));
+                    let e_effect_text = inflict_status_effect(e_weapon, &mut player.status_effects, None);
+
+                    let text = match p_weapon.speed.cmp(&e_weapon.speed) {
+                        Ordering::Equal => strings::get("combat.result.clash.both_hit").to_string(),
+                        Ordering::Less => strings::format("combat.result.clash.player_too_heavy", &[&p_weapon.name]),
+                        Ordering::Greater => strings::format("combat.result.clash.enemy_too_heavy", &[&enemy.name, &e_weapon.name]),
+                    };
+                    format!("{text}{p_effect_text}{e_effect_text}")
+                }
+                // If they have the same speed, the one with better control only gets grazed
+                StraightClash::PlayerControls => {
+                    let e_damage = e_damage.glancing();
+                    enemy.health -= p_damage;
+                    player.health -= e_damage;
+                    let p_effect_text = inflict_status_effect(p_weapon, &mut enemy.status_effects, Some(enemy.name));
+                    let e_effect_text = inflict_status_effect(e_weapon, &mut player.status_effects, None);
+
+                    let text = strings::format("combat.result.clash.player_controls", &[&p_weapon.name, &p_damage, &e_damage]);
+                    format!("{text}{p_effect_text}{e_effect_text}")
+                }
+                StraightClash::EnemyControls => {
+                    let p_damage = p_damage.glancing();
+                    enemy.health -= p_damage;
+                    player.health -= e_damage;
+                    let p_effect_text = inflict_status_effect(p_weapon, &mut enemy.status_effects, Some(enemy.name));
+                    let e_effect_text = inflict_status_effect(e_weapon, &mut player.status_effects, None);
+
+                    let text = strings::format(
+                        "combat.result.clash.enemy_controls",
+                        &[&enemy.name, &e_weapon.name, &p_damage, &e_damage],
+                    );
+                    format!("{text}{p_effect_text}{e_effect_text}")
                 }
-                // If they have the same speed, both get hit.
-                Ordering::Equal => {
+                // If one weapon is only slightly faster, neither attack is interrupted but neither lands cleanly
+                StraightClash::BothGlance => {
+                    let p_damage = p_damage.glancing();
+                    let e_damage = e_damage.glancing();
                     enemy.health -= p_damage;
                     player.health -= e_damage;
-                    "You both attacked with the same speed, and you both got hit.".to_string()
+                    let p_effect_text = inflict_status_effect(p_weapon, &mut enemy.status_effects, Some(enemy.name));
+                    let e_effect_text = inflict_status_effect(e_weapon, &mut player.status_effects, None);
+
+                    let text = strings::format("combat.result.clash.both_glance", &[&p_damage, &e_damage]);
+                    format!("{text}{p_effect_text}{e_effect_text}")
+                }
+            }
+        }
+        // Player tries to disarm the enemy but gets hit first
+        (Disarm, AttackStraight(e)) => {
+            let Item::Weapon(weapon) = &enemy.inventory[e] else {unreachable!()};
+            let (damage, critical_text) = clean_hit_damage(weapon);
+            player.health -= damage;
+            let effect_text = inflict_status_effect(weapon, &mut player.status_effects, None);
+
+            let text = strings::format("combat.result.disarm_hit", &[&enemy.name, &weapon.name, &damage]);
+            format!("{critical_text}{text}{effect_text}")
+        }
+        // Player tries to disarm the enemy but they dodge
+        (Disarm, DodgeLeft | DodgeRight) => {
+            strings::format("combat.result.disarm_dodged", &[&enemy.name])
+        }
+        // Player disarms the enemy
+        (Disarm, Nothing | AttackLeft(_) | AttackRight(_) | EatFood(_) | Flee) => {
+            enemy.health -= config::DISARM_DAMAGE;
+
+            match enemy.inventory.iter().position(|item| matches!(item, Item::Weapon(_))) {
+                Some(i) => {
+                    let weapon = enemy.inventory.remove(i);
+                    let text = strings::format(
+                        "combat.result.disarmed",
+                        &[&weapon.get_name(), &enemy.name, &config::DISARM_DAMAGE],
+                    );
+                    enemy.disarmed_weapons.push(weapon);
+                    text
                 }
+                None => strings::format("combat.result.disarm_unarmed", &[&enemy.name, &config::DISARM_DAMAGE]),
             }
         }
         // Both heal
         (EatFood(p), EatFood(e)) => {
-            let Item::Food(p_food) = player.inventory.remove(p) else {unreachable!()};
-            let Item::Food(e_food) = enemy.inventory.remove(e) else {unreachable!()};
+            let p_food = take_one_food(&mut player.inventory, p);
+            let e_food = take_one_food(&mut enemy.inventory, e);
 
             let p_inc = player.health.heal_to_max(p_food.heals_for, player.max_health);
             let e_inc = enemy.health.heal_to_max(e_food.heals_for, enemy.max_health);
 
-            format!(
-                "You both took some time out of the fight to eat some food - how peaceful.\nYou ate your {} and were healed {} HP. The {} ate their {} and was healed {} HP.",
-                p_food.name, p_inc, enemy.name, e_food.name, e_inc
+            strings::format(
+                "combat.result.both_eat",
+                &[&p_food.name, &p_inc, &enemy.name, &e_food.name, &e_inc],
             )
         }
         // Player heals
         (EatFood(p), _) => {
-            let Item::Food(p_food) = player.inventory.remove(p) else {unreachable!()};
+            let p_food = take_one_food(&mut player.inventory, p);
             let p_inc = player.health.heal_to_max(p_food.heals_for, player.max_health);
 
-            format!(
-                "You ate your {} and were healed by {} HP",
-                p_food.name, p_inc
-            )
+            strings::format("combat.result.player_eats", &[&p_food.name, &p_inc])
         }
         // Enemy heals
         (_, EatFood(e)) => {
-            let Item::Food(e_food) = enemy.inventory.remove(e) else {unreachable!()};
+            let e_food = take_one_food(&mut enemy.inventory, e);
             let e_inc = enemy.health.heal_to_max(e_food.heals_for, enemy.max_health);
 
-            format!(
-                "The {} ate their {} and was healed by {} HP",
-                enemy.name, e_food.name, e_inc
-            )
+            strings::format("combat.result.enemy_eats", &[&enemy.name, &e_food.name, &e_inc])
         }
         // Enemy dodges but player hits
         (AttackLeft(p), DodgeLeft) | (AttackRight(p), DodgeRight) => {
@@ -355,11 +1084,10 @@ fn execute_actions(
 
             let prev_enemy_health = enemy.health;
             enemy.health -= p_weapon.dodge_damage;
+            let effect_text = inflict_status_effect(p_weapon, &mut enemy.status_effects, Some(enemy.name));
 
-            format!(
-                "The {} dodged, but you caught them and dealt {} damage.",
-                enemy.name, prev_enemy_health - enemy.health
-            )
+            let text = strings::format("combat.result.enemy_caught", &[&enemy.name, &(prev_enemy_health - enemy.health)]);
+            format!("{text}{effect_text}")
         }
         // Player dodges but enemy hits
         (DodgeLeft, AttackLeft(e)) | (DodgeRight, AttackRight(e)) => {
@@ -367,29 +1095,62 @@ fn execute_actions(
 
             let prev_player_health = player.health;
             player.health -= e_weapon.dodge_damage;
+            let effect_text = inflict_status_effect(e_weapon, &mut player.status_effects, None);
 
-            format!(
-                "You dodged, but the {} caught you and dealt {} damage.",
-                enemy.name, prev_player_health - player.health
-            )
+            let text = strings::format("combat.result.player_caught", &[&enemy.name, &(prev_player_health - player.health)]);
+            format!("{text}{effect_text}")
+        }
+        // Player runs away
+        (Flee, Nothing | DodgeLeft | DodgeRight | AttackLeft(_) | AttackRight(_) | Flee) => {
+            strings::format("combat.result.fled", &[&enemy.name])
         }
         // Neither the player or the enemy attacks
-        (Nothing | DodgeLeft | DodgeRight, Nothing | DodgeLeft | DodgeRight) => {
-            "Neither of you attacked. What a waste of time.".to_string()
+        (Nothing | DodgeLeft | DodgeRight, Nothing | DodgeLeft | DodgeRight | Flee) => {
+            strings::get("combat.result.nothing").to_string()
         }
         // The player attacks but it is dodged
         (AttackLeft(_) | AttackStraight(_) | AttackRight(_), _) => {
-            "You attacked but it didn't connect".to_string()
+            strings::get("combat.result.player_missed").to_string()
         }
         // The enemy attacks but it is dodged
         (_, AttackLeft(_) | AttackStraight(_) | AttackRight(_)) => {
-            "The enemy attacked but it didn't connect.".to_string()
+            strings::get("combat.result.enemy_missed").to_string()
         }
     };
 
-    format!(
-        "{}\n{}\n{result_text}",
-        player.describe_combat_action(player_action),
-        enemy.describe_combat_action(enemy_action),
-    )
+    format!("{player_description}\n{enemy_description}\n{result_text}{food_text}")
+}
+
+/// Uses up one turn of a combatant's stun, if they are stunned.
+///
+/// ### Params:
+/// * `stunned_turns`: the [`Player`]'s or [`Enemy`]'s number of stunned turns
+///
+/// ### Returns:
+/// Whether the combatant is stunned this turn
+fn take_stunned_turn(stunned_turns: &mut usize) -> bool {
+    match stunned_turns.checked_sub(1) {
+        Some(remaining) => {
+            *stunned_turns = remaining;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Moves the [throwable][Weapon::throwable] weapon at the given index out of the [`Player`]'s inventory and into their [thrown weapons][Player::thrown_weapons].
+///
+/// ### Returns:
+/// The thrown weapon
+///
+/// ### Panics
+/// * If the item at the index is not a weapon
+fn throw_weapon(player: &mut Player, index: usize) -> &Weapon {
+    let item = player.inventory.remove(index);
+    player.thrown_weapons.push(item);
+
+    let Some(Item::Weapon(weapon)) = player.thrown_weapons.last() else {
+        panic!("Thrown item should have been a weapon");
+    };
+    weapon
 }
@@ -1,4 +1,5 @@
-//! Contains the [`Health`] and [`Damage`] types for representing HP
+//! Contains the [`Health`], [`Damage`] and [`HealthDelta`] types for representing HP.
+//! None of the arithmetic on them panics - results which would go out of range are clamped instead.
 
 use std::{
     fmt::Display,
@@ -13,6 +14,11 @@ pub struct Health(usize);
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Damage(usize);
 
+/// The signed difference between two [`Health`] values, for when the health could have gone either up or down.
+/// Positive values are an increase.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct HealthDelta(i64);
+
 impl Health {
     /// Creates a new [`Health`] from a number of HP
     pub const fn new(health: usize) -> Self {
@@ -27,7 +33,8 @@ impl Health {
     /// Increases the [`Health`] by the given [`Damage`], up to the given max health.
     /// Returns how much the health increased by.
     pub fn heal_to_max(&mut self, heal_by: Damage, max: Self) -> Damage {
-        let new_health = max.0.min(self.0 + heal_by.0);
+        // If the health is already over the max, it isn't lowered
+        let new_health = max.0.min(self.0.saturating_add(heal_by.0)).max(self.0);
         let diff = new_health - self.0;
         self.0 = new_health;
         Damage::new(diff)
@@ -44,6 +51,34 @@ impl Damage {
     pub const fn new(damage: usize) -> Self {
         Self(damage)
     }
+
+    /// Gets the [`Damage`] dealt by a critical hit, which is 1.5× the normal damage. Halves are rounded up.
+    pub const fn critical(self) -> Self {
+        Self((self.0 * 3).div_ceil(2))
+    }
+
+    /// Gets the [`Damage`] dealt by a glancing hit, which is half the normal damage. Halves are rounded up, so a glancing hit always does some damage.
+    pub const fn glancing(self) -> Self {
+        Self(self.0.div_ceil(2))
+    }
+
+    /// Gets the value of the damage as a [`usize`]. This is needed to do more advanced calculations, such as [comparing weapons][crate::items::Weapon::effective_damage].
+    pub const fn as_usize(self) -> usize {
+        self.0
+    }
+}
+
+impl HealthDelta {
+    /// Creates a new [`HealthDelta`] from a change in HP. Deltas usually come from subtracting one [`Health`] from another.
+    #[cfg(test)]
+    pub const fn new(delta: i64) -> Self {
+        Self(delta)
+    }
+
+    /// Gets the size of the change as a [`Damage`], whether the health went up or down
+    pub fn to_damage(self) -> Damage {
+        Damage(usize::try_from(self.0.unsigned_abs()).unwrap_or(usize::MAX))
+    }
 }
 
 impl Sub<Damage> for Health {
@@ -54,19 +89,25 @@ impl Sub<Damage> for Health {
     }
 }
 
+/// Adding doesn't know about the max health, so callers which need to respect it should use [`Health::heal_to_max`] instead.
+/// The result saturates at [`usize::MAX`] rather than overflowing.
 impl Add<Damage> for Health {
     type Output = Self;
 
     fn add(self, rhs: Damage) -> Self::Output {
-        Self(self.0 + rhs.0)
+        Self(self.0.saturating_add(rhs.0))
     }
 }
 
+/// Gets how much the health changed by going from `rhs` to `self`, which is negative if `self` is lower.
+/// Values too big for an [`i64`] are treated as [`i64::MAX`].
 impl Sub<Self> for Health {
-    type Output = Damage;
+    type Output = HealthDelta;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Damage(self.0 - rhs.0)
+        let lhs = i64::try_from(self.0).unwrap_or(i64::MAX);
+        let rhs = i64::try_from(rhs.0).unwrap_or(i64::MAX);
+        HealthDelta(lhs.saturating_sub(rhs))
     }
 }
 
@@ -76,9 +117,10 @@ impl SubAssign<Damage> for Health {
     }
 }
 
+/// Like [`Add`], this saturates rather than overflowing, and doesn't know about the max health
 impl AddAssign<Damage> for Health {
     fn add_assign(&mut self, rhs: Damage) {
-        self.0 += rhs.0;
+        self.0 = self.0.saturating_add(rhs.0);
     }
 }
 
@@ -93,3 +135,9 @@ impl Display for Damage {
         write!(f, "{}", self.0)
     }
 }
+
+impl Display for HealthDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
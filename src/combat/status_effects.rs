@@ -0,0 +1,36 @@
+//! Contains the [`StatusEffect`] type for lingering damage in battles
+
+use super::{Damage, Health};
+
+/// An effect which damages the player or an enemy at the start of each turn of a battle, such as bleeding.
+/// Status effects only last until the end of the battle they were inflicted in.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct StatusEffect {
+    /// The name of the effect, used in messages such as "You are bleeding"
+    pub name: &'static str,
+    /// How much damage the effect deals each turn
+    pub damage_per_turn: Damage,
+    /// How many more turns the effect will last for
+    pub remaining_turns: usize,
+}
+
+/// Applies one turn of each [`StatusEffect`] to the given [`Health`], removing any effects which have run out.
+/// Returns the name of each effect which was applied and how much damage it dealt.
+pub fn tick_status_effects(
+    effects: &mut Vec<StatusEffect>,
+    health: &mut Health,
+) -> Vec<(&'static str, Damage)> {
+    let mut applied = Vec::new();
+
+    for effect in effects.iter_mut() {
+        let prev_health = *health;
+        *health -= effect.damage_per_turn;
+        effect.remaining_turns = effect.remaining_turns.saturating_sub(1);
+
+        applied.push((effect.name, (prev_health - *health).to_damage()));
+    }
+
+    effects.retain(|effect| effect.remaining_turns > 0);
+
+    applied
+}
@@ -0,0 +1,809 @@
+#![cfg(test)]
+
+use crate::{config, difficulty::Difficulty, items::{Food, Weapon}, effects::{NoEffects, tests::MockEffects}, menu::tests::MockMenu, rooms::Room};
+
+use super::*;
+use super::health::HealthDelta;
+
+/// Creates an enemy with no items and the given health
+pub fn test_enemy(health: usize) -> Enemy {
+    Enemy {
+        name: "Test Enemy",
+        description: "",
+        inventory: Vec::new(),
+        health: Health::new(health),
+        max_health: Health::new(10),
+        status_effects: Vec::new(),
+        difficulty: Difficulty::Normal,
+        ai: EnemyAi::Random,
+        last_player_action: None,
+        disarmed_weapons: Vec::new(),
+        enraged: false,
+        stunned_turns: 0,
+    }
+}
+
+/// Creates a weapon with the given speed, dodge damage and weight, which deals 1 damage with a straight attack
+fn weapon(speed: usize, dodge_damage: usize, heavy: bool) -> Weapon {
+    Weapon {
+        name: "Test Weapon",
+        description: "",
+        straight_damage: Damage::new(1),
+        dodge_damage: Damage::new(dodge_damage),
+        speed,
+        inflicts: None,
+        throwable: false,
+        heavy,
+    }
+}
+
+/// Tests that turns spent in battle count down the same clock as passive turns
+#[test]
+fn test_battle_uses_turns() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+
+    // An enemy with no health is defeated after one turn
+    menu.push_choice(Some(0));
+    // Don't review the fight
+    menu.push_choice(Some(0));
+    let result = battle(&mut player, test_enemy(0), &mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, BattleResult::PlayerWin);
+    assert_eq!(player.remaining_turns, config::MAX_TURNS - 1);
+    assert_eq!(player.get_remaining_time(), "9:40");
+
+    // Going to the upper corridor takes another turn
+    menu.push_choice(Some(3));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.remaining_turns, config::MAX_TURNS - 2);
+}
+
+/// Tests that a battle which lasts until the player runs out of turns stops at 0 rather than underflowing
+#[test]
+fn test_battle_max_turns() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+    player.remaining_turns = 3;
+
+    for _ in 0..3 {
+        menu.push_choice(Some(0));
+    }
+    menu.push_choice(Some(0));
+
+    let result = battle(&mut player, test_enemy(10), &mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, BattleResult::MaxTurnsReached);
+    assert_eq!(player.remaining_turns, 0);
+    assert_eq!(player.get_remaining_time(), "0:00");
+
+    // The enemy wasn't beaten, so it should be back in the room
+    assert!(player.get_room_state().enemy.is_some());
+}
+
+/// Tests that a fight started at the beginning of a loop can't run past the time limit, even if neither side ever gets hurt
+#[test]
+fn test_battle_runs_out_of_time() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut builder = MockMenu::builder();
+    for _ in 0..config::MAX_TURNS {
+        builder = builder.choose_option("Do nothing");
+    }
+    let mut menu = builder.choose_option("Continue").build();
+
+    // The enemy has no weapons, so it can only dodge or do nothing
+    let result = battle(&mut player, test_enemy(10), &mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, BattleResult::MaxTurnsReached);
+    assert_eq!(player.turns_taken(), config::MAX_TURNS);
+    assert_eq!(menu.remaining_choices(), 0);
+}
+
+/// Tests that an enemy with multiple weapons uses all of them over the course of a battle
+#[test]
+fn test_enemy_uses_multiple_weapons() {
+    let mut enemy = test_enemy(10);
+    enemy.inventory = vec![
+        Item::Weapon(Weapon { straight_damage: Damage::new(2), ..weapon(1, 1, false) }),
+        Item::Weapon(Weapon { straight_damage: Damage::new(8), ..weapon(5, 4, false) }),
+    ];
+
+    let mut used = [false, false];
+
+    for turn in 1..=config::MAX_TURNS {
+        if let Action::AttackLeft(w) | Action::AttackStraight(w) | Action::AttackRight(w) =
+            enemy.choose_combat_action(turn)
+        {
+            used[w] = true;
+        }
+    }
+
+    assert_eq!(used, [true, true]);
+}
+
+/// Tests that tells are more often truthful on easier difficulties, and that getting a tell doesn't change the enemy's action
+#[test]
+fn test_tell_reliability() {
+    let mut truthful = [0, 0, 0];
+
+    for (i, difficulty) in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard].into_iter().enumerate() {
+        // Try enemies with different health to get a larger sample of turns
+        for health in 6..=10 {
+            let mut enemy = test_enemy(health);
+            enemy.difficulty = difficulty;
+
+            for turn in 1..=config::MAX_TURNS {
+                let action = enemy.choose_combat_action(turn);
+                let tell = enemy.tell(action, turn);
+
+                // Tells are deterministic and don't affect what the enemy does
+                assert_eq!(tell, enemy.tell(action, turn));
+                assert_eq!(
+                    format!("{action:?}"),
+                    format!("{:?}", enemy.choose_combat_action(turn))
+                );
+
+                if tell == Tell::for_action(action) {
+                    truthful[i] += 1;
+                }
+            }
+        }
+    }
+
+    assert!(truthful[0] > truthful[2], "{truthful:?}");
+}
+
+/// Tests that status effects tick even when neither combatant attacks, that a tick can end a battle,
+/// and that effects are cleared at the end of the battle
+#[test]
+fn test_status_effects() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+
+    let bleeding = StatusEffect {
+        name: "bleeding",
+        damage_per_turn: Damage::new(1),
+        remaining_turns: 5,
+    };
+
+    let mut enemy = test_enemy(2);
+    enemy.status_effects.push(bleeding);
+    player.status_effects.push(bleeding);
+
+    // The enemy bleeds once before the first turn and once before the second, which defeats them
+    menu.push_choice(Some(0));
+    menu.push_choice(Some(0));
+    let result = battle(&mut player, enemy, &mut menu, &mut NoEffects).unwrap();
+
+    assert_eq!(result, BattleResult::PlayerWin);
+    assert_eq!(player.remaining_turns, config::MAX_TURNS - 1);
+    assert_eq!(player.health, config::PLAYER_START_HEALTH - Damage::new(2));
+    assert!(player.status_effects.is_empty());
+}
+
+/// Tests the rounding of critical hit damage
+#[test]
+fn test_critical_damage() {
+    assert_eq!(Damage::new(0).critical(), Damage::new(0));
+    assert_eq!(Damage::new(1).critical(), Damage::new(2));
+    assert_eq!(Damage::new(2).critical(), Damage::new(3));
+    assert_eq!(Damage::new(5).critical(), Damage::new(8));
+    assert_eq!(Damage::new(10).critical(), Damage::new(15));
+}
+
+/// Tests that fleeing from an enemy which can't attack moves the player back and leaves the enemy in the room at full health
+#[test]
+fn test_flee() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+
+    player.previous_room = Some(Room::UpperCorridor);
+
+    // Fleeing is the option after the two dodges
+    menu.push_choice(Some(3));
+    menu.push_choice(Some(0));
+    let result = battle(&mut player, test_enemy(3), &mut menu, &mut NoEffects).unwrap();
+
+    assert_eq!(result, BattleResult::PlayerFled);
+    assert_eq!(player.room, Room::UpperCorridor);
+    assert_eq!(player.previous_room, Some(config::STARTING_ROOM));
+    assert_eq!(player.remaining_turns, config::MAX_TURNS - 1);
+
+    let enemy = player.room_graph.get_state(config::STARTING_ROOM).enemy.as_ref().unwrap();
+    assert_eq!(enemy.health, enemy.max_health);
+}
+
+/// Creates an enemy with a weapon, so that a battle against it can go either way
+fn armed_test_enemy() -> Enemy {
+    let mut enemy = test_enemy(6);
+    enemy.inventory = vec![Item::Weapon(Weapon { name: "Test Club", straight_damage: Damage::new(2), ..weapon(3, 1, false) })];
+    enemy
+}
+
+/// A [`simulate_battle`] strategy which always attacks straight ahead with the first item in the player's inventory
+fn attack_straight(_: &Player, _: &Enemy, _: usize) -> Action {
+    Action::AttackStraight(0)
+}
+
+/// A [`simulate_battle`] strategy which never does anything
+fn do_nothing(_: &Player, _: &Enemy, _: usize) -> Action {
+    Action::Nothing
+}
+
+/// A [`simulate_battle`] strategy which always tries to knock the enemy's weapon away
+fn disarm(_: &Player, _: &Enemy, _: usize) -> Action {
+    Action::Disarm
+}
+
+/// Tests that a simulated battle plays out the same as an interactive one where the player makes the same choices
+#[test]
+fn test_simulated_battle_matches_battle() {
+    let mut simulated = Player::init_with(Difficulty::Normal, config::STARTING_ROOM, vec![test_darts(2)]);
+    let simulated_result = simulate_battle(&mut simulated, armed_test_enemy(), attack_straight);
+
+    // Attack with the darts, then aim straight ahead, every turn
+    let mut player = Player::init_with(Difficulty::Normal, config::STARTING_ROOM, vec![test_darts(2)]);
+    let mut menu = MockMenu::default();
+    for _ in 0..simulated.turns_taken() {
+        menu.push_choice(Some(3));
+        menu.push_choice(Some(1));
+    }
+    // Don't review the fight
+    menu.push_choice(Some(0));
+
+    let result = battle(&mut player, armed_test_enemy(), &mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, simulated_result);
+    assert_eq!(player.health, simulated.health);
+    assert_eq!(player.turns_taken(), simulated.turns_taken());
+    assert_eq!(menu.remaining_choices(), 0);
+}
+
+/// Tests that simulated battles can be run many times over to compare how often different loadouts and strategies win
+#[test]
+fn test_simulated_win_rates() {
+    // The enemy's choices depend on the time, so starting each battle at a different time gives a spread of outcomes
+    let wins = |damage: usize, strategy: fn(&Player, &Enemy, usize) -> Action| {
+        (1..=config::MAX_TURNS)
+            .filter(|&turns| {
+                let mut player = Player::init_with(Difficulty::Normal, config::STARTING_ROOM, vec![test_darts(damage)]);
+                player.remaining_turns = turns;
+                simulate_battle(&mut player, armed_test_enemy(), strategy) == BattleResult::PlayerWin
+            })
+            .count()
+    };
+
+    let strong = wins(6, attack_straight);
+    let weak = wins(1, attack_straight);
+    assert!(strong > weak, "{strong} wins with strong darts, {weak} with weak darts");
+    assert_eq!(wins(6, do_nothing), 0);
+}
+
+/// Tests that a weapon knocked out of the enemy's hands in a simulated battle is left on the floor, rather than lost with the enemy
+#[test]
+fn test_simulated_disarm_leaves_weapon() {
+    let disarmed = (1..=config::MAX_TURNS).any(|turns| {
+        let mut player = Player::init_with(Difficulty::Normal, config::STARTING_ROOM, Vec::new());
+        player.remaining_turns = turns;
+        let _ = simulate_battle(&mut player, armed_test_enemy(), disarm);
+        player.get_room_state().items.iter().any(|item| item.get_name() == "Test Club")
+    });
+    assert!(disarmed);
+}
+
+/// Tests that the fight log can be reviewed at the end of a battle, and includes every turn
+#[test]
+fn test_fight_review() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+    player.remaining_turns = 2;
+
+    menu.push_choice(Some(0));
+    menu.push_choice(Some(0));
+    // Review the fight
+    menu.push_choice(Some(1));
+
+    let result = battle(&mut player, test_enemy(10), &mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, BattleResult::MaxTurnsReached);
+
+    let (title, content) = menu.last_screen().unwrap();
+    assert_eq!(title, "Fight Log");
+    assert_eq!(content.matches("You are now at").count(), 2);
+    assert!(content.contains("You have 0:00 left"));
+}
+
+/// Creates a throwable weapon which deals the given damage
+fn test_darts(damage: usize) -> Item {
+    Item::Weapon(Weapon {
+        name: "Test Darts",
+        straight_damage: Damage::new(damage),
+        throwable: true,
+        ..weapon(5, damage, false)
+    })
+}
+
+/// Tests that a thrown weapon hits before a faster straight attack, and is removed from the inventory
+#[test]
+fn test_throw_beats_straight_attack() {
+    let mut player = Player::init_with(Difficulty::Normal, config::STARTING_ROOM, vec![test_darts(3)]);
+
+    let mut enemy = test_enemy(10);
+    enemy.inventory.push(Item::Weapon(Weapon { straight_damage: Damage::new(5), ..weapon(0, 5, false) }));
+
+    let text = execute_actions(&mut player, &mut enemy, Action::Throw(0), Action::AttackStraight(0));
+
+    assert!(text.starts_with("You throw your Test Darts"));
+    assert_eq!(enemy.health, Health::new(7));
+    assert_eq!(player.health, config::PLAYER_START_HEALTH);
+    assert!(player.inventory.is_empty());
+    assert_eq!(player.thrown_weapons.len(), 1);
+}
+
+/// Tests that a thrown weapon is recovered if the player wins, without counting as a collected item
+#[test]
+fn test_throw_recovered_after_win() {
+    let mut player = Player::init_with(Difficulty::Normal, config::STARTING_ROOM, vec![test_darts(1)]);
+    let mut menu = MockMenu::default();
+
+    // Throwing is the option after attacking with the weapon
+    menu.push_choice(Some(4));
+    menu.push_choice(Some(0));
+    let result = battle(&mut player, test_enemy(0), &mut menu, &mut NoEffects).unwrap();
+
+    assert_eq!(result, BattleResult::PlayerWin);
+    assert!(player.thrown_weapons.is_empty());
+    assert!(matches!(&player.inventory[..], [Item::Weapon(w)] if w.name == "Test Darts"));
+    assert_eq!(player.items_collected, 0);
+}
+
+/// Tests that a thrown weapon is left in the room if the player doesn't win
+#[test]
+fn test_throw_left_in_room() {
+    let mut player = Player::init_with(Difficulty::Normal, config::STARTING_ROOM, vec![test_darts(1)]);
+    let mut menu = MockMenu::default();
+    player.remaining_turns = 1;
+
+    menu.push_choice(Some(4));
+    menu.push_choice(Some(0));
+    let result = battle(&mut player, test_enemy(10), &mut menu, &mut NoEffects).unwrap();
+
+    assert_eq!(result, BattleResult::MaxTurnsReached);
+    assert!(player.inventory.is_empty());
+    assert!(player
+        .get_room_state()
+        .items
+        .iter()
+        .any(|item| item.get_name() == "Test Darts"));
+}
+
+/// Tests that an unarmed enemy takes the best weapon from the room, but leaves other items and doesn't take more once armed
+#[test]
+fn test_enemy_takes_best_weapon() {
+    let mut enemy = test_enemy(10);
+    let mut items = vec![test_darts(1), Item::Maps, test_darts(3)];
+
+    assert_eq!(enemy.take_best_weapon(&mut items), Some("Test Darts"));
+    assert!(matches!(&enemy.inventory[..], [Item::Weapon(w)] if w.straight_damage == Damage::new(3)));
+
+    // The enemy is armed now, so it leaves the other weapon
+    assert_eq!(enemy.take_best_weapon(&mut items), None);
+    assert_eq!(items.len(), 2);
+
+    // Quest items are never taken
+    let mut enemy = test_enemy(10);
+    let mut items = vec![Item::Maps, Item::EscapePodKeys];
+    assert_eq!(enemy.take_best_weapon(&mut items), None);
+    assert_eq!(items.len(), 2);
+}
+
+/// Tests that an enemy picks up a weapon from the room during a battle, and keeps it afterwards
+#[test]
+fn test_enemy_takes_weapon_in_battle() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+    player.remaining_turns = 1;
+    player.get_room_state_mut().items.push(test_darts(1));
+
+    menu.push_choice(Some(0));
+    menu.push_choice(Some(0));
+    let _ = battle(&mut player, test_enemy(10), &mut menu, &mut NoEffects).unwrap();
+
+    assert!(player.get_room_state().items.is_empty());
+    let enemy = player.get_room_state().enemy.as_ref().unwrap();
+    assert_eq!(enemy.inventory.len(), 1);
+}
+
+/// Tests that a straight attack with a heavy weapon is always hit by an opposing straight attack, regardless of speed
+#[test]
+fn test_heavy_weapon_always_hit() {
+    for (player_speed, enemy_speed) in [(0, 9), (5, 5), (9, 0)] {
+        let mut player = Player::init_with(Difficulty::Normal, config::STARTING_ROOM, vec![Item::Weapon(weapon(player_speed, 1, true))]);
+
+        let mut enemy = test_enemy(10);
+        enemy.inventory.push(Item::Weapon(weapon(enemy_speed, 1, false)));
+
+        execute_actions(&mut player, &mut enemy, Action::AttackStraight(0), Action::AttackStraight(0));
+        assert!(player.health < config::PLAYER_START_HEALTH, "{player_speed} vs {enemy_speed}");
+
+        // The enemy is only hit if the player's weapon wasn't slower
+        assert_eq!(enemy.health < Health::new(10), player_speed <= enemy_speed);
+    }
+
+    // The same is true of enemies with heavy weapons
+    let mut player = Player::init_with(Difficulty::Normal, config::STARTING_ROOM, vec![Item::Weapon(weapon(9, 1, false))]);
+    let mut enemy = test_enemy(10);
+    enemy.inventory.push(Item::Weapon(weapon(0, 1, true)));
+
+    execute_actions(&mut player, &mut enemy, Action::AttackStraight(0), Action::AttackStraight(0));
+    assert!(enemy.health < Health::new(10));
+    assert_eq!(player.health, config::PLAYER_START_HEALTH - Damage::new(1));
+}
+
+/// Tests who hits whom when both combatants attack straight, depending on how far apart their weapons' speeds are
+#[test]
+fn test_straight_clash() {
+    for (player_speed, enemy_speed, expected) in [
+        (3, 3, StraightClash::BothHit),
+        (3, 4, StraightClash::BothGlance),
+        (4, 3, StraightClash::BothGlance),
+        (1, 5, StraightClash::PlayerHits),
+        (2, 4, StraightClash::PlayerHits),
+        (5, 1, StraightClash::EnemyHits),
+        (4, 2, StraightClash::EnemyHits),
+    ] {
+        assert_eq!(
+            straight_clash(&weapon(player_speed, 1, false), &weapon(enemy_speed, 1, false)),
+            expected,
+            "{player_speed} vs {enemy_speed}"
+        );
+    }
+
+    // A faster heavy weapon is always hit back in full, however close the speeds are
+    assert_eq!(straight_clash(&weapon(3, 1, true), &weapon(4, 1, false)), StraightClash::BothHit);
+    assert_eq!(straight_clash(&weapon(5, 1, false), &weapon(1, 1, true)), StraightClash::BothHit);
+    // A slower heavy weapon makes no difference
+    assert_eq!(straight_clash(&weapon(3, 1, false), &weapon(4, 1, true)), StraightClash::BothGlance);
+    assert_eq!(straight_clash(&weapon(1, 1, false), &weapon(5, 1, true)), StraightClash::PlayerHits);
+}
+
+/// Tests that a tie in speed is broken by which weapon is easier to control, and that an exact tie still has both combatants hit in full
+#[test]
+fn test_speed_tiebreak() {
+    // Both weapons are as fast as each other, and differ only in how easy they are to control
+    let controlled_weapon = |dodge_damage| Weapon { straight_damage: Damage::new(4), ..weapon(3, dodge_damage, false) };
+
+    assert_eq!(straight_clash(&controlled_weapon(3), &controlled_weapon(2)), StraightClash::PlayerControls);
+    assert_eq!(straight_clash(&controlled_weapon(2), &controlled_weapon(3)), StraightClash::EnemyControls);
+    assert_eq!(straight_clash(&controlled_weapon(2), &controlled_weapon(2)), StraightClash::BothHit);
+
+    // The better controlled weapon's wielder only takes a glancing hit
+    let mut player = Player::init_with(Difficulty::Normal, config::STARTING_ROOM, vec![Item::Weapon(controlled_weapon(3))]);
+    let mut enemy = test_enemy(10);
+    enemy.inventory.push(Item::Weapon(controlled_weapon(2)));
+    execute_actions(&mut player, &mut enemy, Action::AttackStraight(0), Action::AttackStraight(0));
+    assert_eq!(player.health, config::PLAYER_START_HEALTH - Damage::new(4).glancing());
+    assert_eq!(enemy.health, Health::new(6));
+
+    // The eating knife and the ISPD taser are just as fast and easy to control, so neither gets the upper hand
+    let graph = crate::map::init(Difficulty::Normal);
+    let Item::Weapon(knife) = &graph.get_state(Room::Kitchen).items[1] else { panic!("The kitchen should have the eating knife") };
+    let Item::Weapon(taser) = &graph.get_state(Room::EngineRoom).enemy.as_ref().unwrap().inventory[0] else {
+        panic!("The mechanic should have the ISPD taser")
+    };
+    assert_eq!((knife.name, taser.name), ("Eating Knife", "ISPD Taser"));
+    assert_eq!(straight_clash(knife, taser), StraightClash::BothHit);
+    assert_eq!(straight_clash(taser, knife), StraightClash::BothHit);
+}
+
+/// Tests that glancing hits deal half damage, rounded up so that they always deal some
+#[test]
+fn test_glancing_hits() {
+    let mut player = Player::init(Difficulty::Normal);
+    player.inventory.push(Item::Weapon(Weapon { straight_damage: Damage::new(4), ..weapon(3, 1, false) }));
+
+    let mut enemy = test_enemy(10);
+    enemy.inventory.push(Item::Weapon(weapon(4, 1, false)));
+
+    execute_actions(&mut player, &mut enemy, Action::AttackStraight(0), Action::AttackStraight(0));
+    assert_eq!(enemy.health, Health::new(8));
+    assert_eq!(player.health, config::PLAYER_START_HEALTH - Damage::new(1));
+
+    assert_eq!(Damage::new(5).glancing(), Damage::new(3));
+    assert_eq!(Damage::new(0).glancing(), Damage::new(0));
+}
+
+/// Tests that every enemy in the map, including the skipper, is armed and attacks on at least some turns
+#[test]
+fn test_enemies_attack() {
+    for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+        let mut map = crate::map::init(difficulty);
+
+        for room in [Room::StrategyRoom, Room::MessHall, Room::EngineRoom, Room::CargoHold] {
+            let enemy = map.get_state_mut(room).enemy.as_mut().unwrap();
+
+            let attacks = (1..=config::MAX_TURNS)
+                .filter(|&turn| matches!(
+                    enemy.choose_combat_action(turn),
+                    Action::AttackLeft(_) | Action::AttackStraight(_) | Action::AttackRight(_)
+                ))
+                .count();
+
+            assert!(attacks > 0, "The {} never attacked on {difficulty:?}", enemy.name);
+        }
+    }
+}
+
+/// Tests that a [predictive][EnemyAi::Predictive] enemy tends to attack the way the player dodged last turn
+#[test]
+fn test_predictive_enemy() {
+    // Counts the enemy's attacks to the left and right over every turn of a loop
+    let count_attacks = |ai, last_player_action| {
+        let mut enemy = test_enemy(10);
+        enemy.inventory.push(Item::Weapon(weapon(3, 1, false)));
+        enemy.ai = ai;
+        enemy.last_player_action = last_player_action;
+
+        let actions: Vec<_> = (1..=config::MAX_TURNS).map(|turn| enemy.choose_combat_action(turn)).collect();
+        let left = actions.iter().filter(|a| matches!(a, Action::AttackLeft(_))).count();
+        let right = actions.iter().filter(|a| matches!(a, Action::AttackRight(_))).count();
+        (left, right)
+    };
+
+    let (left, right) = count_attacks(EnemyAi::Predictive, Some(Action::DodgeLeft));
+    let (random_left, _) = count_attacks(EnemyAi::Random, Some(Action::DodgeLeft));
+    assert_eq!(right, 0);
+    assert!(left > random_left, "{left} vs {random_left}");
+
+    let (left, right) = count_attacks(EnemyAi::Predictive, Some(Action::DodgeRight));
+    let (_, random_right) = count_attacks(EnemyAi::Random, Some(Action::DodgeRight));
+    assert_eq!(left, 0);
+    assert!(right > random_right, "{right} vs {random_right}");
+
+    // Without a dodge to go on, a predictive enemy acts just like a random one
+    assert_eq!(count_attacks(EnemyAi::Predictive, None), count_attacks(EnemyAi::Random, None));
+    assert_eq!(
+        count_attacks(EnemyAi::Predictive, Some(Action::Nothing)),
+        count_attacks(EnemyAi::Random, Some(Action::Nothing))
+    );
+}
+
+/// Tests that disarming an enemy knocks their weapon out of reach, so they stop attacking, and that it can fail
+#[test]
+fn test_disarm() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut enemy = test_enemy(10);
+    enemy.inventory.push(Item::Weapon(weapon(3, 1, false)));
+
+    // A straight attack hits before the disarm
+    execute_actions(&mut player, &mut enemy, Action::Disarm, Action::AttackStraight(0));
+    assert_eq!(enemy.inventory.len(), 1);
+    assert!(player.health < config::PLAYER_START_HEALTH);
+
+    // A dodge avoids it
+    execute_actions(&mut player, &mut enemy, Action::Disarm, Action::DodgeLeft);
+    assert_eq!(enemy.inventory.len(), 1);
+    assert_eq!(enemy.health, Health::new(10));
+
+    let text = execute_actions(&mut player, &mut enemy, Action::Disarm, Action::Nothing);
+    assert!(text.contains("You knocked the Test Weapon out of the Test Enemy's hands"));
+    assert!(enemy.inventory.is_empty());
+    assert_eq!(enemy.disarmed_weapons.len(), 1);
+    assert_eq!(enemy.health, Health::new(10) - config::DISARM_DAMAGE);
+
+    // Without a weapon, the enemy can't attack for the rest of the battle
+    for turn in 1..=config::MAX_TURNS {
+        let action = enemy.choose_combat_action(turn);
+        assert!(!matches!(action, Action::AttackLeft(_) | Action::AttackStraight(_) | Action::AttackRight(_)), "{action:?}");
+    }
+}
+
+/// Tests that a disarmed weapon is left in the room if the player doesn't win, and that the enemy picks it back up in the next battle
+#[test]
+fn test_disarmed_weapon_left_in_room() {
+    let mut player = Player::init(Difficulty::Normal);
+    player.previous_room = Some(Room::UpperCorridor);
+    let mut enemy = test_enemy(10);
+    enemy.inventory.push(Item::Weapon(weapon(3, 1, false)));
+    enemy.disarmed_weapons.push(enemy.inventory.remove(0));
+
+    let mut menu = MockMenu::builder().choose_option("Try to run away").choose_option("Continue").build();
+    let result = battle(&mut player, enemy, &mut menu, &mut NoEffects).unwrap();
+
+    assert_eq!(result, BattleResult::PlayerFled);
+    let room = player.room_graph.get_state(config::STARTING_ROOM);
+    assert!(room.items.iter().any(|item| item.get_name() == "Test Weapon"));
+    assert!(room.enemy.as_ref().unwrap().disarmed_weapons.is_empty());
+}
+
+/// Tests that the menu and the effects are told when the player is hurt, and only then
+#[test]
+fn test_damage_flashes() {
+    // Beating an enemy without being touched doesn't flash
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::builder().choose_option("Do nothing").choose_option("Continue").build();
+    let mut effects = MockEffects::default();
+    let _ = battle(&mut player, test_enemy(0), &mut menu, &mut effects).unwrap();
+    assert_eq!(menu.flashes(), 0);
+    assert_eq!(effects.hits, 0);
+
+    // An armed enemy gets some hits in over a long enough fight
+    let mut player = Player::init(Difficulty::Normal);
+    player.remaining_turns = 10;
+    let mut builder = MockMenu::builder();
+    for _ in 0..10 {
+        builder = builder.choose_option("Do nothing");
+    }
+    let mut menu = builder.choose_option("Continue").build();
+    let mut enemy = test_enemy(10);
+    enemy.inventory.push(Item::Weapon(weapon(3, 1, false)));
+    let _ = battle(&mut player, enemy, &mut menu, &mut effects).unwrap();
+
+    assert!(player.health < config::PLAYER_START_HEALTH);
+    assert!(menu.flashes() > 0);
+    assert_eq!(effects.hits, menu.flashes());
+}
+
+/// Tests that an enemy becomes enraged once below the health cutoff, only says so once, and then attacks straight much more often
+#[test]
+fn test_enraged_enemy() {
+    let mut enemy = test_enemy(3);
+    enemy.inventory.push(Item::Weapon(weapon(3, 1, false)));
+    assert_eq!(enemy.check_enraged(), None);
+
+    let straight_attacks = |enemy: &mut Enemy| {
+        (1..=config::MAX_TURNS)
+            .filter(|&turn| matches!(enemy.choose_combat_action(turn), Action::AttackStraight(_)))
+            .count()
+    };
+
+    // Compare at the same health, so that both use the same pseudorandom choices
+    enemy.health = Health::new(2);
+    let calm_attacks = straight_attacks(&mut enemy);
+
+    assert_eq!(enemy.check_enraged().as_deref(), Some("The Test Enemy grits their teeth."));
+    assert!(enemy.enraged);
+    assert_eq!(enemy.check_enraged(), None);
+
+    let enraged_attacks = straight_attacks(&mut enemy);
+    assert!(enraged_attacks > calm_attacks, "{enraged_attacks} vs {calm_attacks}");
+    assert!(enraged_attacks * 100 >= config::MAX_TURNS * 50, "{enraged_attacks}");
+}
+
+/// Tests that an enemy the player runs away from calms down along with recovering their health, so they can become enraged again
+#[test]
+fn test_enraged_enemy_recovers_after_flee() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+    player.previous_room = Some(Room::UpperCorridor);
+
+    let mut enemy = test_enemy(2);
+    assert!(enemy.check_enraged().is_some());
+
+    // Run away, then don't review the fight
+    menu.push_choice(Some(3));
+    menu.push_choice(Some(0));
+    let result = battle(&mut player, enemy, &mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, BattleResult::PlayerFled);
+
+    let mut enemy = player.room_graph.get_state_mut(config::STARTING_ROOM).enemy.take().unwrap();
+    assert_eq!(enemy.health, enemy.max_health);
+    assert!(!enemy.enraged);
+
+    // Going back for another fight, the enemy is told to be enraged again once they're hurt enough
+    enemy.health = Health::new(2);
+    assert_eq!(enemy.check_enraged().as_deref(), Some("The Test Enemy grits their teeth."));
+}
+
+/// Tests that food thrown at an enemy is used up and stuns them for their next turn
+#[test]
+fn test_throw_food() {
+    let mut player = Player::init(Difficulty::Normal);
+    player.inventory.push(Item::Food(Food {
+        name: "Soup",
+        description: "",
+        heals_for: Damage::new(2),
+        count: 2,
+    }));
+    let mut enemy = test_enemy(10);
+    enemy.inventory.push(Item::Weapon(weapon(3, 1, false)));
+
+    // The food doesn't stop the enemy's attack on the turn it's thrown
+    let text = execute_actions(&mut player, &mut enemy, Action::ThrowFood(0), Action::AttackStraight(0));
+    assert!(text.contains("Your Soup splattered across the Test Enemy's visor"));
+    assert!(player.health < config::PLAYER_START_HEALTH);
+    assert_eq!(enemy.stunned_turns, 1);
+    let Item::Food(food) = &player.inventory[0] else { panic!("Food should still be in the inventory") };
+    assert_eq!(food.count, 1);
+
+    // The last of the food is removed from the inventory once thrown
+    execute_actions(&mut player, &mut enemy, Action::ThrowFood(0), Action::Nothing);
+    assert!(player.inventory.is_empty());
+}
+
+/// Tests that a stunned enemy does nothing for as many turns as they were stunned for, and that the stun doesn't outlast the battle
+#[test]
+fn test_stunned_enemy() {
+    let mut player = Player::init(Difficulty::Normal);
+    player.remaining_turns = 3;
+    let mut menu = MockMenu::builder()
+        .choose_option("Do nothing")
+        .choose_option("Do nothing")
+        .choose_option("Do nothing")
+        .choose_option("Continue")
+        .build();
+
+    // The enemy is armed and on hard difficulty, so it would usually attack
+    let mut enemy = test_enemy(10);
+    enemy.difficulty = Difficulty::Hard;
+    enemy.inventory.push(Item::Weapon(weapon(3, 1, false)));
+    enemy.stun(3);
+    // A shorter stun doesn't cut a longer one short
+    enemy.stun(1);
+    assert_eq!(enemy.stunned_turns, 3);
+
+    let result = battle(&mut player, enemy, &mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, BattleResult::MaxTurnsReached);
+    assert_eq!(player.health, config::PLAYER_START_HEALTH);
+
+    let stunned_screens = menu.screens().iter().filter(|(_, content)| content.contains("The Test Enemy is stunned and can't act.")).count();
+    assert_eq!(stunned_screens, 3);
+    assert_eq!(player.get_room_state().enemy.as_ref().unwrap().stunned_turns, 0);
+}
+
+/// Tests that a stunned player isn't asked what to do, and does nothing
+#[test]
+fn test_stunned_player() {
+    let mut player = Player::init(Difficulty::Normal);
+    player.remaining_turns = 2;
+    player.stun(1);
+    // Only the second turn needs a choice
+    let mut menu = MockMenu::builder().choose_option("Do nothing").choose_option("Continue").build();
+
+    let result = battle(&mut player, test_enemy(10), &mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, BattleResult::MaxTurnsReached);
+    assert_eq!(menu.remaining_choices(), 0);
+    assert!(menu.screens().iter().any(|(_, content)| content.starts_with("You are stunned and can't act.\nYou do nothing")));
+    assert_eq!(player.stunned_turns, 0);
+}
+
+/// Tests that a stun wears off one turn at a time
+#[test]
+fn test_take_stunned_turn() {
+    let mut stunned_turns = 2;
+    assert!(take_stunned_turn(&mut stunned_turns));
+    assert!(take_stunned_turn(&mut stunned_turns));
+    assert!(!take_stunned_turn(&mut stunned_turns));
+    assert_eq!(stunned_turns, 0);
+}
+
+/// Tests describing what killed the player, with and without a weapon
+#[test]
+fn test_killer_description() {
+    let killer = Killer { enemy: "Cook", weapon: Some("Standard Blaster") };
+    assert_eq!(killer.describe(3), "Killed by the Cook's Standard Blaster on turn 3.");
+
+    // A status effect doesn't come from a weapon hit on that turn
+    let killer = Killer { enemy: "Cook", weapon: None };
+    assert_eq!(killer.describe(12), "Killed by the Cook on turn 12.");
+}
+
+/// Tests that [`Health`] arithmetic clamps rather than panicking or overflowing
+#[test]
+fn test_health_arithmetic_is_panic_safe() {
+    // Subtracting a higher health gives a negative delta
+    assert_eq!(Health::new(3) - Health::new(5), HealthDelta::new(-2));
+    assert_eq!(Health::new(5) - Health::new(3), HealthDelta::new(2));
+    assert_eq!((Health::new(3) - Health::new(5)).to_damage(), Damage::new(2));
+
+    // Subtracting damage stops at 0
+    assert_eq!(Health::new(3) - Damage::new(5), Health::new(0));
+
+    // Adding saturates instead of overflowing
+    assert_eq!(Health::new(usize::MAX) + Damage::new(1), Health::new(usize::MAX));
+    let mut health = Health::new(usize::MAX - 1);
+    health += Damage::new(5);
+    assert_eq!(health, Health::new(usize::MAX));
+
+    // Healing doesn't overflow, and doesn't lower a health which is already over the max
+    let mut health = Health::new(usize::MAX - 1);
+    assert_eq!(health.heal_to_max(Damage::new(5), Health::new(usize::MAX)), Damage::new(1));
+    let mut health = Health::new(12);
+    assert_eq!(health.heal_to_max(Damage::new(5), Health::new(10)), Damage::new(0));
+    assert_eq!(health, Health::new(12));
+}
@@ -1,13 +1,41 @@
 //! Configuration constants for the game
 
-use crate::{combat::Health, rooms::Room};
+use crate::{
+    combat::{Damage, Health},
+    rooms::Room,
+};
 
 /// How much health the player should start with
 pub const PLAYER_START_HEALTH: Health = Health::new(10);
 /// What the player's max health should be at the start of the game
 pub const PLAYER_START_MAX_HEALTH: Health = Health::new(10);
+/// How many spaces the player's inventory has, or [`None`] for no limit. Stacked food only takes up one space.
+/// Nothing can be dropped yet, so there is no limit for now.
+pub const MAX_INVENTORY_SIZE: Option<usize> = None;
 /// Which room the player should start in
 pub const STARTING_ROOM: Room = Room::Cells;
 
 /// The maximum number of turns the player can take before the loop resets
 pub const MAX_TURNS: usize = 30;
+/// Once the player has this many turns left or fewer, they are warned that time is running out
+pub const LOW_TIME_TURNS: usize = 3;
+/// The number of turns the player has to take after getting a hint before they can get another
+pub const HINT_COOLDOWN_TURNS: usize = 3;
+/// Every loop after the first has a 1 in this many chance of each of the [subtle changes][crate::map::apply_loop_variations] to the map
+pub const LOOP_VARIATION_CHANCE: usize = 4;
+
+/// How much health the player recovers every [few turns][HEALTH_REGEN_TURNS] out of combat
+pub const HEALTH_REGEN: Damage = Damage::new(1);
+/// The number of turns between the player recovering [some health][HEALTH_REGEN]. Only passive turns can trigger it, never combat turns.
+pub const HEALTH_REGEN_TURNS: usize = 3;
+
+/// A clean straight hit with a weapon at or below this speed will be a critical hit
+pub const CRITICAL_HIT_MAX_SPEED: usize = 2;
+/// The damage dealt by a successful [disarm][crate::combat::Action::Disarm]
+pub const DISARM_DAMAGE: Damage = Damage::new(1);
+/// If both combatants attack straight and their weapons' speeds are at most this far apart, both hits are glancing rather than the faster one interrupting the slower one
+pub const GLANCING_HIT_MAX_SPEED_GAP: usize = 1;
+/// Once an armed enemy is below this percentage of their max health, they become [enraged][crate::combat::Enemy::enraged]
+pub const ENRAGE_HEALTH_PERCENT: usize = 25;
+/// The percentage of turns on which an [enraged][crate::combat::Enemy::enraged] enemy attacks straight, whatever they would have done otherwise
+pub const ENRAGED_STRAIGHT_ATTACK_PERCENT: u64 = 75;
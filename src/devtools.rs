@@ -0,0 +1,102 @@
+//! Shortcuts for content authors, which are only built with the `devtools` feature.
+//!
+//! Before the game starts, a debug menu can show any of the game's fixed screens, such as the endings,
+//! or start the first loop in any [`Room`], so that they can be checked without playing up to them.
+
+use crate::difficulty::Difficulty;
+use crate::items::{Food, Item};
+use crate::menu::{Menu, MenuSignal, OptionList};
+use crate::player::Player;
+use crate::rooms::Room;
+use crate::{combat::Damage, map};
+
+/// An option in the [debug menu][open_debug_menu]
+#[derive(Debug, Clone, Copy)]
+enum DebugOption {
+    /// Show the [intro screen][crate::intro_screen]
+    IntroScreen,
+    /// Show the [screen when the loop resets][crate::loop_screen]
+    LoopScreen,
+    /// Show the [screen when the player runs out of time][crate::max_turns_screen]
+    MaxTurnsScreen,
+    /// Show the [ending][Player::show_win_screen], either with or without food
+    WinScreen {
+        /// Whether the player escaped with some food
+        with_food: bool,
+    },
+    /// Pick a [`Room`] to start the first loop in
+    WarpToRoom,
+    /// Start the game as normal
+    StartGame,
+}
+
+/// Shows the debug menu until the user starts the game.
+///
+/// ### Returns:
+/// The [`Room`] to start the first loop in, or [`None`] to start in the usual room
+pub fn open_debug_menu(menu: &mut impl Menu) -> Result<Option<Room>, MenuSignal> {
+    let options = [
+        DebugOption::IntroScreen,
+        DebugOption::LoopScreen,
+        DebugOption::MaxTurnsScreen,
+        DebugOption::WinScreen { with_food: true },
+        DebugOption::WinScreen { with_food: false },
+        DebugOption::WarpToRoom,
+        DebugOption::StartGame,
+    ];
+    let options_str = [
+        "Show the intro".to_string(),
+        "Show the loop resetting".to_string(),
+        "Show running out of time".to_string(),
+        "Show the ending with food".to_string(),
+        "Show the ending without food".to_string(),
+        "Start in a room".to_string(),
+        "Start the game".to_string(),
+    ];
+
+    loop {
+        let list = OptionList::new(&options_str, "Developer tools");
+
+        match options[menu.show_option_list(list)?] {
+            DebugOption::IntroScreen => {
+                menu.show_screen(crate::intro_screen())?;
+            }
+            DebugOption::LoopScreen => {
+                menu.show_screen(crate::loop_screen())?;
+            }
+            DebugOption::MaxTurnsScreen => {
+                menu.show_screen(crate::max_turns_screen())?;
+            }
+            DebugOption::WinScreen { with_food } => {
+                let mut player = Player::init(Difficulty::Normal);
+                if with_food {
+                    player.inventory.push(Item::Food(Food {
+                        name: "Test snack",
+                        description: "A snack which only exists to test the ending.",
+                        heals_for: Damage::new(1),
+                        count: 1,
+                    }));
+                }
+                player.show_win_screen(menu)?;
+            }
+            DebugOption::WarpToRoom => {
+                if let Some(room) = choose_room(menu)? {
+                    return Ok(Some(room));
+                }
+            }
+            DebugOption::StartGame => return Ok(None),
+        }
+    }
+}
+
+/// Asks the user which [`Room`] to start in, out of all the rooms on the map.
+/// Returns [`None`] if they cancel.
+fn choose_room(menu: &mut impl Menu) -> Result<Option<Room>, MenuSignal> {
+    let mut rooms: Vec<Room> = map::init(Difficulty::Normal).rooms.into_keys().collect();
+    rooms.sort_unstable();
+
+    let options_str: Vec<_> = rooms.iter().map(|room| room.get_name().to_string()).collect();
+    let list = OptionList::new(&options_str, "Which room do you start in?");
+
+    Ok(menu.show_option_list_cancellable(list)?.map(|choice| rooms[choice]))
+}
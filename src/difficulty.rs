@@ -0,0 +1,57 @@
+//! The difficulty levels the player can pick between at the start of the game
+
+use crate::menu::{Menu, MenuSignal, OptionList};
+
+/// How hard the game is. This affects how much health enemies have and how aggressively they fight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    /// Enemies have less health and attack less often
+    Easy,
+    /// The game as it was designed to be played
+    Normal,
+    /// Enemies have more health and attack more often
+    Hard,
+}
+
+impl Difficulty {
+    /// All difficulties, in the order they are shown to the player
+    const ALL: [Self; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    /// Gets the name of the difficulty to show the player
+    pub const fn get_name(self) -> &'static str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+        }
+    }
+
+    /// Scales an enemy's health for the difficulty, rounding to the nearest whole number.
+    /// Easy is 0.75x, Normal is 1x, and Hard is 1.5x.
+    pub const fn scale_health(self, health: usize) -> usize {
+        match self {
+            Self::Easy => (health * 3 + 2) / 4,
+            Self::Normal => health,
+            Self::Hard => (health * 3).div_ceil(2),
+        }
+    }
+
+    /// Gets the percentage chance that an enemy's [`Tell`][crate::combat::Tell] truthfully hints at their next action
+    pub const fn tell_reliability(self) -> u64 {
+        match self {
+            Self::Easy => 90,
+            Self::Normal => 75,
+            Self::Hard => 55,
+        }
+    }
+}
+
+/// Asks the player which [`Difficulty`] to play on
+pub fn choose_difficulty(menu: &mut impl Menu) -> Result<Difficulty, MenuSignal> {
+    let options_str = Difficulty::ALL.map(|d| d.get_name().to_string());
+    let list = OptionList::new(&options_str, "Choose a difficulty");
+
+    let choice = menu.show_option_list(list)?;
+
+    Ok(Difficulty::ALL[choice])
+}
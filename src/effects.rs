@@ -0,0 +1,64 @@
+//! Sounds and other effects played at key moments of the game, kept separate from the game logic.
+//!
+//! The game tells an [`Effects`] when something happens, and it's up to the implementation what to play.
+//! The default build uses [`NoEffects`], so it stays silent and has no extra dependencies.
+//! Building with the `beep` feature uses [`Beep`] instead, which rings the terminal bell when the player dies.
+//! The bell for being hurt is left to the [damage bell setting][crate::menu::Menu::damage_bell], so it never rings twice for one hit.
+
+pub mod tests;
+
+/// Something which plays effects at key moments of the game. Every method does nothing by default.
+pub trait Effects {
+    /// Called when the player is hurt in battle
+    fn on_hit(&mut self) {}
+
+    /// Called when the player goes through a door into a different room
+    fn on_room_change(&mut self) {}
+
+    /// Called when the player is killed, just before the loop resets
+    fn on_death(&mut self) {}
+}
+
+/// [`Effects`] which don't play anything
+#[cfg_attr(feature = "beep", allow(dead_code))] // Only used by tests when the `beep` feature is on
+#[derive(Debug, Default)]
+pub struct NoEffects;
+
+impl Effects for NoEffects {}
+
+/// [`Effects`] which ring the terminal bell when the player dies, so they work in any terminal without an audio library
+#[cfg(feature = "beep")]
+#[derive(Debug, Default)]
+pub struct Beep;
+
+#[cfg(feature = "beep")]
+impl Beep {
+    /// Rings the terminal bell the given number of times.
+    /// If the bell can't be written, the game carries on without it.
+    fn ring(times: usize) {
+        use std::io::Write;
+
+        let mut stdout = std::io::stdout().lock();
+        let _ = write!(stdout, "{}", "\x07".repeat(times));
+        let _ = stdout.flush();
+    }
+}
+
+#[cfg(feature = "beep")]
+impl Effects for Beep {
+    fn on_death(&mut self) {
+        Self::ring(3);
+    }
+}
+
+/// Gets the [`Effects`] for this build - [`Beep`] if the `beep` feature is on, otherwise [`NoEffects`]
+pub fn init() -> impl Effects {
+    #[cfg(feature = "beep")]
+    {
+        Beep
+    }
+    #[cfg(not(feature = "beep"))]
+    {
+        NoEffects
+    }
+}
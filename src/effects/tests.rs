@@ -0,0 +1,28 @@
+#![cfg(test)]
+
+use super::*;
+
+/// [`Effects`] which count how many times each effect was played, for tests
+#[derive(Debug, Default)]
+pub struct MockEffects {
+    /// The number of times [`Effects::on_hit`] was called
+    pub hits: usize,
+    /// The number of times [`Effects::on_room_change`] was called
+    pub room_changes: usize,
+    /// The number of times [`Effects::on_death`] was called
+    pub deaths: usize,
+}
+
+impl Effects for MockEffects {
+    fn on_hit(&mut self) {
+        self.hits += 1;
+    }
+
+    fn on_room_change(&mut self) {
+        self.room_changes += 1;
+    }
+
+    fn on_death(&mut self) {
+        self.deaths += 1;
+    }
+}
@@ -0,0 +1,55 @@
+//! The error which stops the game, returned from `main` so that it is reported once the terminal has been restored
+
+use std::path::PathBuf;
+
+use crate::menu::{self, MenuSignal};
+
+/// An error which stops the game
+pub enum GameError {
+    /// The [`Menu`][menu::Menu] couldn't be created or shown
+    Menu(menu::Error),
+    /// The [text pack][crate::strings::load_from_env] couldn't be loaded. This holds a message saying why.
+    TextPack(String),
+    /// The file given to `--replay` isn't a recorded run
+    Replay(PathBuf),
+}
+
+impl std::fmt::Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Menu(e) => write!(f, "The menu couldn't be shown: {e}"),
+            Self::TextPack(message) => write!(f, "{message}"),
+            Self::Replay(path) => write!(f, "{} is not a recorded run", path.display()),
+        }
+    }
+}
+
+// `main` reports its error with `Debug`, so this shows the same message as `Display` rather than the error's structure
+impl std::fmt::Debug for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<menu::Error> for GameError {
+    fn from(value: menu::Error) -> Self {
+        Self::Menu(value)
+    }
+}
+
+impl From<std::io::Error> for GameError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Menu(menu::Error::Io(value))
+    }
+}
+
+/// Gets what `main` should return when the game is stopped by a [`MenuSignal`].
+/// Quitting is a clean exit, while any other signal is an error.
+pub fn exit_with(signal: MenuSignal) -> Result<(), GameError> {
+    match signal {
+        MenuSignal::Quit => Ok(()),
+        MenuSignal::Error(e) => Err(e.into()),
+    }
+}
@@ -1,6 +1,7 @@
 //! Functionality related to items
 
-use crate::combat::Damage;
+use crate::combat::{Damage, StatusEffect};
+use crate::strings;
 
 /// A food item which heals the player when used
 #[derive(Debug, Hash)]
@@ -11,6 +12,20 @@ pub struct Food {
     pub description: &'static str,
     /// How much health the player or an enemy gains by eating the food
     pub heals_for: Damage,
+    /// How many of the food are stacked together. This is always at least 1.
+    pub count: usize,
+}
+
+/// A rare item which permanently raises the player's max health when used, and heals them fully.
+/// Like everything else, the boost only lasts until the end of the loop.
+#[derive(Debug, Hash)]
+pub struct Upgrade {
+    /// The name of the upgrade
+    pub name: &'static str,
+    /// A description of the upgrade
+    pub description: &'static str,
+    /// How much the player's max health goes up by
+    pub max_health_boost: Damage,
 }
 
 /// A weapon which can be used in a battle
@@ -27,6 +42,54 @@ pub struct Weapon {
     pub dodge_damage: Damage,
     /// The weapon's speed. A lower speed means the weapon will act faster.
     pub speed: usize,
+    /// A [`StatusEffect`] which the weapon inflicts on whoever it hits, if any
+    pub inflicts: Option<StatusEffect>,
+    /// Whether the weapon can be [thrown][crate::combat::Action::Throw], which uses it up until the end of the battle
+    pub throwable: bool,
+    /// Whether the weapon is heavy. Someone attacking straight with a heavy weapon is committed to the attack,
+    /// so they are hit by their opponent's straight attack even if their weapon is faster.
+    pub heavy: bool,
+}
+
+impl Weapon {
+    /// Gets a summary of the weapon's stats to show the player when they inspect it, with one stat on each line
+    pub fn stats_summary(&self) -> String {
+        let mut lines = vec![
+            strings::format("weapon.stats.straight_damage", &[&self.straight_damage]),
+            strings::format("weapon.stats.dodge_damage", &[&self.dodge_damage]),
+            strings::format("weapon.stats.speed", &[&self.speed]),
+        ];
+
+        if let Some(effect) = &self.inflicts {
+            lines.push(strings::format(
+                "weapon.stats.inflicts",
+                &[&effect.name, &effect.damage_per_turn, &effect.remaining_turns],
+            ));
+        }
+        if self.throwable {
+            lines.push(strings::get("weapon.stats.throwable").to_string());
+        }
+        if self.heavy {
+            lines.push(strings::get("weapon.stats.heavy").to_string());
+        }
+
+        lines.join("\n")
+    }
+
+    /// Gets a single number summing up how effective the weapon is, so that weapons can be compared and sorted when balancing them.
+    /// This is the average damage of a hit, weighted towards straight hits, plus all the damage of any [`StatusEffect`] the weapon inflicts.
+    /// Faster weapons get a small bonus for winning clashes, and heavy weapons get a penalty for being hit back.
+    #[allow(clippy::cast_precision_loss)] // Weapon stats are far too small to lose precision
+    pub fn effective_damage(&self) -> f64 {
+        let hit = (2.0 * self.straight_damage.as_usize() as f64 + self.dodge_damage.as_usize() as f64) / 3.0;
+        let effect = self
+            .inflicts
+            .map_or(0.0, |effect| (effect.damage_per_turn.as_usize() * effect.remaining_turns) as f64);
+        let speed_bonus = 1.0 + 1.0 / (self.speed as f64 + 3.0);
+        let heavy_penalty = if self.heavy { 0.8 } else { 1.0 };
+
+        (hit + effect) * speed_bonus * heavy_penalty
+    }
 }
 
 /// An item which can be stored in the [player][crate::player::Player]'s or an [enemy][crate::combat::Enemy]'s inventory
@@ -36,6 +99,8 @@ pub enum Item {
     Food(Food),
     /// A weapon
     Weapon(Weapon),
+    /// An item which raises the player's max health
+    Upgrade(Upgrade),
     /// The maps which are needed to fly the escape pod
     Maps,
     /// The keys to the escape pod, found by [searching the engine room][crate::map::RoomAction::EngineRoomTakeKeys]
@@ -46,34 +111,69 @@ pub enum Item {
     /// Shame - a joke item from trying to [hack the mainframe][crate::map::RoomAction::BridgeHackTheMainframe]
     Shame,
     /// The captain's diary - a joke item found by [searching the bunks][crate::map::RoomAction::BunksGetDiary]
-    /// Stores the page to read
+    /// Stores the number of pages which have been read
     CaptainsDiary(u8)
 }
 
 impl Item {
     /// Gets the name of the item
-    pub const fn get_name(&self) -> &'static str {
+    pub fn get_name(&self) -> &'static str {
         match self {
             Self::Food(f) => f.name,
             Self::Weapon(w) => w.name,
-            Self::Maps => "Galactic Maps 2168 Edition",
-            Self::EscapePodKeys => "Escape Pod Keys",
-            Self::Dust => "A thin layer of dust",
-            Self::Shame => "A sense of shame",
-            Self::CaptainsDiary(_) => "The Captain's Diary"
+            Self::Upgrade(u) => u.name,
+            Self::Maps => strings::get("item.maps.name"),
+            Self::EscapePodKeys => strings::get("item.escape_pod_keys.name"),
+            Self::Dust => strings::get("item.dust.name"),
+            Self::Shame => strings::get("item.shame.name"),
+            Self::CaptainsDiary(_) => strings::get("item.captains_diary.name")
+        }
+    }
+
+    /// Gets the name of the item to show in the inventory, including how many there are if several [`Food`] items are stacked
+    pub fn get_display_name(&self) -> String {
+        match self {
+            Self::Food(f) if f.count > 1 => strings::format("item.stack", &[&f.name, &f.count]),
+            _ => self.get_name().to_string(),
         }
     }
 
     /// Gets the description of the item
-    pub const fn get_description(&self) -> &'static str {
+    pub fn get_description(&self) -> &'static str {
         match self {
             Self::Food(f) => f.description,
             Self::Weapon(w) => w.description,
-            Self::Maps => "A map of the galaxy in the format which spacecraft use to plot routes",
-            Self::EscapePodKeys => "A key card labelled 'escape pod'. The label is beginning to wear.",
-            Self::Dust => "You'd think air vents would be clean like the rest of the ship, but evidently not. If this were an Arnithian ship, you could climb into the vents just fine.",
-            Self::Shame => "Maybe you're not cut out to be a soldier in the 22nd century. SQL databases have been resigned to museums for centennials.",
-            Self::CaptainsDiary(_) => "The diary you found underneath the bunks. It's physical paper and the handwriting is awful."
+            Self::Upgrade(u) => u.description,
+            Self::Maps => strings::get("item.maps.description"),
+            Self::EscapePodKeys => strings::get("item.escape_pod_keys.description"),
+            Self::Dust => strings::get("item.dust.description"),
+            Self::Shame => strings::get("item.shame.description"),
+            Self::CaptainsDiary(_) => strings::get("item.captains_diary.description")
         }
     }
 }
+
+/// Takes one of the [`Food`] at the given index out of an inventory.
+/// If several of the food are stacked there, the stack's count is decreased. Otherwise, the item is removed from the inventory.
+///
+/// ### Panics
+/// * If the item at the index is not [`Food`]
+pub fn take_one_food(inventory: &mut Vec<Item>, index: usize) -> Food {
+    let Item::Food(food) = &mut inventory[index] else {
+        panic!("Item should have been food");
+    };
+
+    if food.count > 1 {
+        food.count -= 1;
+
+        return Food {
+            count: 1,
+            ..*food
+        };
+    }
+
+    let Item::Food(food) = inventory.remove(index) else {
+        unreachable!()
+    };
+    food
+}
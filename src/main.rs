@@ -10,86 +10,243 @@
 
 //! A text-based adventure game
 
+mod autosave;
 mod combat;
 mod config;
+#[cfg(all(feature = "devtools", not(test)))]
+mod devtools;
+mod difficulty;
+mod effects;
+mod error;
 mod items;
 mod map;
 mod menu;
 mod player;
 mod rooms;
+mod settings;
+mod strings;
+mod tests;
 
+use std::path::PathBuf;
+
+use autosave::AutosaveMenu;
 use combat::{battle, BattleResult};
-use menu::{Screen, Menu};
-use player::Player;
+use effects::Effects;
+use error::GameError;
+use map::SeededRng;
+use menu::{Screen, Menu, MenuSignal};
+use player::{PassiveActionResult, Player};
 use rooms::Room;
 
-/// The screen to show at the beginning of the game
-const INTRO_SCREEN: Screen = Screen {
-    title: "Welcome Soldier",
-    content: 
-"The year is 2168. You are a fighter pilot for the Arnithian Galactic Republic. You were sent out in your experimental time-bending t-Jet to protect a cargo vessel, but an engine malfunction left you irradiated and stranded in space. \
-You wake up in a cell, confused and disoriented. You peer as far out of your cell as you can. There's someone in the room opposite you, but they're not looking at you. \
-You try the cell door. It's locked, obviously, but the control panel looks insecure. You pull off the screen and jump two wires inside. The door slides open. \
-You keep your eyes on the person in the other room, but they don't seem to notice you leaving. Suddenly they look up and see you standing there. They rush out and before you know it you're bleeding out on the floor, and then
-You wake up in a cell, confused and disoriented. You peer as far out of your cell as you can. There's someone in the room opposite you, but they're not looking at you. \
-You hot-wire the door, but then you're more cautious. You duck down below the level of the door and prepare for your final moments... again.",
-};
-
-/// The screen to show when the time loop resets
-const LOOP_SCREEN: Screen = Screen {
-    title: "\"ISPD agents will arrive in: 10 minutes\"",
-    content: "Well, here we go again... You break open the door and hope you don't get shot this time."
-};
-
-/// The screen to show when the player reaches their max turns
-const MAX_TURNS_SCREEN: Screen = Screen {
-    title: "\"Now boarding: ISPD agents\"",
-    content: "You groan. There's no way you're getting out of this alive. "
-};
-
-fn main() {
-    let mut menu = menu::init().unwrap();
-    let menu = &mut menu;
-
-    menu.show_screen(INTRO_SCREEN);
+/// The banner to show above the [intro screen][intro_screen] - the player's t-Jet
+const T_JET_BANNER: &str = r"      /\
+     /  \
+    | [] |
+  __|    |__
+ /__|____|__\
+    /_/\_\";
+
+/// Gets the screen to show at the beginning of the game
+fn intro_screen() -> Screen<'static> {
+    Screen::new(strings::get("screen.intro.title"), strings::get("screen.intro.content")).with_banner(T_JET_BANNER)
+}
+
+/// Gets the screen to show when the time loop resets
+fn loop_screen() -> Screen<'static> {
+    Screen::new(strings::get("screen.loop.title"), strings::get("screen.loop.content"))
+}
+
+/// Gets the screen to show when the player reaches their max turns
+fn max_turns_screen() -> Screen<'static> {
+    Screen::new(strings::get("screen.max_turns.title"), strings::get("screen.max_turns.content"))
+}
+
+/// The command line flag to record every choice to a file, followed by the file's path
+const RECORD_FLAG: &str = "--record";
+/// The command line flag to replay a recorded run, followed by the recording's path
+const REPLAY_FLAG: &str = "--replay";
+
+/// Gets the value after a command line flag, such as the path in `--replay <path>`.
+/// Returns [`None`] if the flag wasn't given or has no value after it.
+fn flag_value(args: &[String], flag: &str) -> Option<PathBuf> {
+    let i = args.iter().position(|arg| arg == flag)?;
+    args.get(i + 1).map(PathBuf::from)
+}
+
+fn main() -> Result<(), GameError> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // The text pack is loaded before the menu takes over the terminal, so that any error is reported before the game starts
+    strings::load_from_env().map_err(GameError::TextPack)?;
+
+    // The recording is read before the menu takes over the terminal, so that any error is reported before the game starts
+    let recording = match flag_value(&args, REPLAY_FLAG) {
+        Some(path) => {
+            let Some(recording) = autosave::Save::load(&path) else {
+                return Err(GameError::Replay(path));
+            };
+            Some(recording)
+        }
+        None => None,
+    };
+
+    let menu = menu::init()?;
+
+    // If the user quits, the game should just end, and any other signal is an error to report.
+    // Returning from main rather than calling `std::process::exit` means the menu is dropped and the terminal is restored first.
+    // Quitting keeps the autosave, so the run can be continued next time.
+    let menu = if let Some(recording) = recording {
+        AutosaveMenu::replay(menu, recording)
+    } else {
+        match AutosaveMenu::start(menu, autosave::path_from_env(), map::seed_from_env()) {
+            Ok(menu) => menu,
+            Err(signal) => return error::exit_with(signal),
+        }
+    };
+
+    let mut menu = match flag_value(&args, RECORD_FLAG) {
+        Some(path) => menu.with_recording(path),
+        None => menu,
+    };
+
+    let seed = menu.seed();
+    match play(&mut menu, &mut effects::init(), seed) {
+        Ok(_) => {
+            menu.delete_save();
+            Ok(())
+        }
+        Err(signal) => error::exit_with(signal),
+    }
+}
+
+/// Runs the game until the player escapes or quits, playing [`Effects`] at key moments.
+/// If `seed` is set, each loop's map is shuffled using the next number from it, so the whole run can be replayed from the seed.
+/// The seed also picks each loop's [variations][map::apply_loop_variations].
+/// Returns the [`Player`]'s state at the point they escaped.
+fn play(menu: &mut impl Menu, effects: &mut impl Effects, seed: Option<u64>) -> Result<Player, MenuSignal> {
+    // Content authors can check screens and rooms without playing up to them.
+    // The tests script every choice from the start of the game, so they never see the debug menu.
+    #[cfg(all(feature = "devtools", not(test)))]
+    let mut warp_to = devtools::open_debug_menu(menu)?;
+
+    let difficulty = difficulty::choose_difficulty(menu)?;
+    menu.show_screen(intro_screen())?;
+
+    // The number of time loops the player has been through
+    let mut loops = 0;
+    // The number of turns taken in previous loops
+    let mut previous_turns = 0;
+    // The numbers to shuffle each loop's map with
+    let mut map_rng = seed.map(SeededRng::new);
+    let mut player = Player::init(difficulty);
 
     // The outer time loop
     'time_loop: loop {
-        let mut player = Player::init();
+        // The run summary counts enemies and items across every loop, so they carry over into the new loop
+        let mut next_player = Player::init(difficulty);
+        next_player.enemies_defeated = player.enemies_defeated;
+        next_player.items_collected = player.items_collected;
+        player = next_player;
+        loops += 1;
+
+        if let Some(rng) = &mut map_rng {
+            player.room_graph = map::init_randomized(difficulty, rng.next_u64());
+        }
+        // Whether or not the map is shuffled, later loops are subtly different from the first
+        map::apply_loop_variations(&mut player.room_graph, seed.unwrap_or_default(), loops);
+
+        #[cfg(all(feature = "devtools", not(test)))]
+        if let Some(room) = warp_to.take() {
+            player.room = room;
+        }
 
-        player.print_room(menu);
+        player.show_remaining_time(menu);
+        player.print_room(menu)?;
 
         // The inner gameplay loop
         loop {
             if player.remaining_turns == 0 {
-                menu.show_screen(MAX_TURNS_SCREEN);
-                menu.show_screen(LOOP_SCREEN);
+                menu.show_screen(max_turns_screen())?;
+                player.show_run_summary(menu, loops, previous_turns + player.turns_taken())?;
+                menu.show_screen(loop_screen())?;
+                previous_turns += player.turns_taken();
                 continue 'time_loop;
             }
 
+            // `battle` puts the enemy back into the room unless the player beats them
             if let Some(enemy) = player.get_room_state_mut().enemy.take() {
-                let battle_result = battle(&mut player, enemy, menu);
+                let battle_result = battle(&mut player, enemy, menu, effects)?;
+                menu.checkpoint();
 
-                match battle_result {
-                    BattleResult::PlayerWin => (),
-                    BattleResult::PlayerLoss => {
-                        menu.show_screen(LOOP_SCREEN);
-                        continue 'time_loop;
-                    },
-                    BattleResult::MaxTurnsReached => {
-                        menu.show_screen(MAX_TURNS_SCREEN);
-                        menu.show_screen(LOOP_SCREEN);
+                if end_loop_after_battle(battle_result, &player, menu, effects, loops, previous_turns)? {
+                    previous_turns += player.turns_taken();
+                    continue 'time_loop;
+                }
+
+                // Winning or fleeing on the last turn still runs out the clock, which is checked at the top of the loop
+                if player.remaining_turns == 0 {
+                    continue;
+                }
+            }
+
+            match player.take_passive_action(menu, effects)? {
+                PassiveActionResult::Continue => (),
+                PassiveActionResult::ResetLoop => {
+                    menu.show_screen(loop_screen())?;
+                    previous_turns += player.turns_taken();
+                    continue 'time_loop;
+                }
+                PassiveActionResult::GuardBattle(battle_result) => {
+                    menu.checkpoint();
+
+                    if end_loop_after_battle(battle_result, &player, menu, effects, loops, previous_turns)? {
+                        previous_turns += player.turns_taken();
                         continue 'time_loop;
                     }
                 }
             }
 
-            player.take_passive_action(menu);
-
             if matches!(player.room, Room::Escape) {
-                player.show_win_screen(menu);
-                break 'time_loop;
+                // The time left doesn't matter once the player is off the ship
+                menu.set_status(None);
+                player.show_win_screen(menu)?;
+                player.show_run_summary(menu, loops, previous_turns + player.turns_taken())?;
+                return Ok(player);
             }
         }
     }
 }
+
+/// Shows the screens for the end of the loop if a battle ended it, playing the [`Effects`] for the [`Player`]'s death if they lost.
+///
+/// ### Params:
+/// * `loops`: the number of the current loop, for the run summary
+/// * `previous_turns`: the number of turns taken in previous loops, for the run summary
+///
+/// ### Returns:
+/// Whether the loop has ended, so the next one should start
+fn end_loop_after_battle(
+    battle_result: BattleResult,
+    player: &Player,
+    menu: &mut impl Menu,
+    effects: &mut impl Effects,
+    loops: usize,
+    previous_turns: usize,
+) -> Result<bool, MenuSignal> {
+    match battle_result {
+        BattleResult::PlayerWin | BattleResult::PlayerFled => return Ok(false),
+        BattleResult::PlayerLoss(killer) => {
+            if !menu.catching_up() {
+                effects.on_death();
+            }
+            menu.show_screen(Screen::new(strings::get("screen.killed.title"), &killer.describe(player.turns_taken())))?;
+        }
+        BattleResult::MaxTurnsReached => {
+            menu.show_screen(max_turns_screen())?;
+            player.show_run_summary(menu, loops, previous_turns + player.turns_taken())?;
+        }
+    }
+
+    menu.show_screen(loop_screen())?;
+    Ok(true)
+}
@@ -5,17 +5,25 @@ use std::collections::HashMap;
 mod actions;
 mod enemies;
 mod food;
+mod random;
+mod tests;
 mod transitions;
+mod triggers;
+mod upgrades;
 mod weapons;
 
 pub use actions::RoomAction;
+pub use random::SeededRng;
+pub use triggers::RoomTrigger;
 
+use crate::config::{LOOP_VARIATION_CHANCE, STARTING_ROOM};
+use crate::difficulty::Difficulty;
 use crate::rooms::{Room, RoomGraph, RoomState};
 
 use self::transitions::*;
 
-/// Initialise a new [`RoomGraph`]
-pub fn init() -> RoomGraph {
+/// Initialise a new [`RoomGraph`], with enemies set up for the given [`Difficulty`]
+pub fn init(difficulty: Difficulty) -> RoomGraph {
     // The bridge
     let bridge = RoomState::new(Room::Bridge, vec![BRIDGE_TO_UPPER_CORRIDOR])
         .add_item(weapons::intruders_blaster())
@@ -34,8 +42,9 @@ pub fn init() -> RoomGraph {
 
     // The strategy room
     let strategy_room = RoomState::new(Room::StrategyRoom, vec![STRATEGY_ROOM_TO_UPPER_CORRIDOR])
-        .with_enemy(enemies::skipper())
-        .add_action(RoomAction::StrategyRoomTakeMaps);
+        .with_enemy(enemies::skipper(difficulty))
+        .add_action(RoomAction::StrategyRoomTakeMaps)
+        .with_trigger(RoomTrigger::StrategyRoomStarCharts);
 
     // The cells
     let cells = RoomState::new(Room::Cells, vec![CELLS_TO_UPPER_CORRIDOR])
@@ -50,13 +59,14 @@ pub fn init() -> RoomGraph {
             MESS_HALL_TO_STAIRWELL,
         ],
     )
-    .with_enemy(enemies::cook())
+    .with_enemy(enemies::cook(difficulty))
     .add_action(RoomAction::MessHallWatchTheGame);
 
     // The kitchen
     let kitchen = RoomState::new(Room::Kitchen, vec![KITCHEN_TO_MESS_HALL])
         .add_item(food::bread_roll())
-        .add_item(weapons::eating_knife());
+        .add_item(weapons::eating_knife())
+        .add_action(RoomAction::Search);
 
     // The stairwell
     let stairwell = RoomState::new(
@@ -73,10 +83,12 @@ pub fn init() -> RoomGraph {
             CREW_AREA_TO_ESCAPE_POD,
             CREW_AREA_TO_LOWER_CORRIDOR,
         ],
-    );
+    )
+    .add_action(RoomAction::Search);
 
     // The store room
     let store_room = RoomState::new(Room::StoreRoom, vec![STORE_ROOM_TO_CREW_AREA])
+        .add_item(weapons::serrated_shiv())
         .add_action(RoomAction::StoreRoomFindChocolate);
 
     // The lower corridor
@@ -87,6 +99,7 @@ pub fn init() -> RoomGraph {
             LOWER_CORRIDOR_TO_BUNKS,
             LOWER_CORRIDOR_TO_WASH_ROOM,
             LOWER_CORRIDOR_TO_ENGINE_ROOM,
+            LOWER_CORRIDOR_TO_CARGO_HOLD,
         ],
     );
 
@@ -97,19 +110,25 @@ pub fn init() -> RoomGraph {
 
     // The wash room
     let wash_room = RoomState::new(Room::WashRoom, vec![WASH_ROOM_TO_LOWER_CORRIDOR])
-        .add_item(weapons::shaving_razor());
+        .add_item(weapons::shaving_razor())
+        .add_action(RoomAction::WashRoomSearchCabinet);
 
     // The engine room
     let engine_room = RoomState::new(Room::EngineRoom, vec![ENGINE_ROOM_TO_LOWER_CORRIDOR])
-        .with_enemy(enemies::mechanic())
+        .with_enemy(enemies::mechanic(difficulty))
         .add_action(RoomAction::EngineRoomTakeKeys)
         .add_item(weapons::wrench());
 
+    // The cargo hold
+    let cargo_hold = RoomState::new(Room::CargoHold, vec![CARGO_HOLD_TO_LOWER_CORRIDOR])
+        .with_enemy(enemies::loader_bot(difficulty))
+        .add_item(food::ration_pack());
+
     let escape_pod = RoomState::new(Room::EscapePod, vec![ESCAPE_POD_TO_CREW_AREA])
         .add_action(RoomAction::EscapePodTakeOff);
 
     // Construct a room graph from all the rooms
-    RoomGraph {
+    let graph = RoomGraph {
         rooms: HashMap::from([
             (Room::Bridge, bridge),
             (Room::UpperCorridor, upper_corridor),
@@ -124,7 +143,123 @@ pub fn init() -> RoomGraph {
             (Room::Bunks, bunks),
             (Room::WashRoom, wash_room),
             (Room::EngineRoom, engine_room),
+            (Room::CargoHold, cargo_hold),
             (Room::EscapePod, escape_pod),
         ]),
+    };
+
+    debug_validate(&graph);
+    graph
+}
+
+/// The environment variable which turns on [randomised maps][init_randomized], set to the seed for the whole run
+const MAP_SEED_VAR: &str = "MAP_SEED";
+
+/// Reads the seed for [randomised maps][init_randomized] from the `MAP_SEED` environment variable.
+/// Returns [`None`] if the variable is not set or is not a number, in which case the map shouldn't be randomised.
+pub fn seed_from_env() -> Option<u64> {
+    std::env::var(MAP_SEED_VAR).ok()?.parse().ok()
+}
+
+/// Initialise a new [`RoomGraph`] like [`init`], but with the items, the enemies,
+/// and the actions which give the player the maps and the escape pod keys shuffled between rooms.
+/// The same seed always gives the same layout.
+///
+/// To make sure the game can still be won, the maps and keys are only put in rooms which can be reached
+/// without going through the escape pod door. Nothing is put in the escape pod, and no enemy is put in the starting room.
+pub fn init_randomized(difficulty: Difficulty, seed: u64) -> RoomGraph {
+    let mut graph = init(difficulty);
+    let mut rng = SeededRng::new(seed);
+
+    // Go through the rooms in a fixed order, as a HashMap's order changes between runs
+    let mut rooms: Vec<_> = graph.rooms.keys().copied().collect();
+    rooms.sort();
+
+    // Take everything which gets shuffled out of the rooms
+    let mut items = Vec::new();
+    let mut enemies = Vec::new();
+    let mut escape_actions = Vec::new();
+    for &room in &rooms {
+        let state = graph.get_state_mut(room);
+        items.append(&mut state.items);
+        enemies.extend(state.enemy.take());
+
+        let (escape, others): (Vec<_>, _) = std::mem::take(&mut state.actions)
+            .into_iter()
+            .partition(|action| matches!(action, RoomAction::StrategyRoomTakeMaps | RoomAction::EngineRoomTakeKeys));
+        state.actions = others;
+        escape_actions.extend(escape);
+    }
+
+    let rooms: Vec<_> = rooms.into_iter().filter(|&room| room != Room::EscapePod).collect();
+    let unlocked_rooms = graph.reachable_rooms(STARTING_ROOM, false);
+    let escape_rooms: Vec<_> = rooms.iter().copied().filter(|room| unlocked_rooms.contains(room)).collect();
+    let mut enemy_rooms: Vec<_> = rooms.iter().copied().filter(|&room| room != STARTING_ROOM).collect();
+
+    // Each room can only have one enemy
+    rng.shuffle(&mut enemy_rooms);
+    for (enemy, room) in enemies.into_iter().zip(enemy_rooms) {
+        graph.get_state_mut(room).enemy = Some(enemy);
+    }
+
+    for item in items {
+        let room = rooms[rng.below(rooms.len())];
+        graph.get_state_mut(room).items.push(item);
+    }
+
+    for action in escape_actions {
+        let room = escape_rooms[rng.below(escape_rooms.len())];
+        graph.get_state_mut(room).actions.push(action);
+    }
+
+    debug_validate(&graph);
+    graph
+}
+
+/// Makes the subtle changes which some loops have, so that the ship doesn't feel quite the same every time round:
+/// * The door to the bunks is sometimes jammed, and has to be forced open
+/// * The enemy in the mess hall is sometimes away
+/// * On even loops, anything left in the kitchen has been moved to the mess hall
+///
+/// The first loop is never changed. The same seed and loop number always give the same changes, so runs can still be replayed.
+/// None of the changes stop the game being won - the jammed door can always be forced, and nothing the escape needs is moved or taken away.
+///
+/// ### Params:
+/// * `graph`: the map for the loop, which may already be [randomised][init_randomized]
+/// * `seed`: the seed for the run, or 0 if the maps aren't randomised
+/// * `loop_number`: which loop this is, starting from 1
+pub fn apply_loop_variations(graph: &mut RoomGraph, seed: u64, loop_number: usize) {
+    if loop_number <= 1 {
+        return;
+    }
+
+    let mut rng = SeededRng::for_loop(seed, loop_number);
+
+    if rng.below(LOOP_VARIATION_CHANCE) == 0 {
+        let lower_corridor = graph.get_state_mut(Room::LowerCorridor);
+        if let Some(door) = lower_corridor.connections.iter_mut().find(|t| t.to == Room::Bunks) {
+            *door = LOWER_CORRIDOR_TO_BUNKS_JAMMED;
+            lower_corridor.actions.push(RoomAction::LowerCorridorForceBunksDoor);
+        }
+    }
+
+    if rng.below(LOOP_VARIATION_CHANCE) == 0 {
+        graph.get_state_mut(Room::MessHall).enemy = None;
+    }
+
+    if loop_number.is_multiple_of(2) {
+        let moved = std::mem::take(&mut graph.get_state_mut(Room::Kitchen).items);
+        graph.get_state_mut(Room::MessHall).items.extend(moved);
+    }
+
+    debug_validate(graph);
+}
+
+/// Catches mistakes in the map while it's being worked on, by panicking in debug builds if the [`RoomGraph`] isn't [valid][RoomGraph::validate]
+fn debug_validate(graph: &RoomGraph) {
+    if cfg!(debug_assertions) {
+        if let Err(e) = graph.validate(STARTING_ROOM) {
+            panic!("The map is invalid: {e}");
+        }
     }
 }
@@ -1,8 +1,19 @@
 //! Contains the [`RoomAction`] type and related functionality
 
-use crate::{menu::Screen, player::Player, items::Item, rooms::{Room, RoomTransition}};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
 
-use super::food;
+use crate::{menu::{Menu, MenuSignal, OptionList, Screen}, player::Player, items::Item, rooms::Room, strings};
+
+use super::{
+    food,
+    random::SeededRng,
+    transitions::{CREW_AREA_TO_ESCAPE_POD_UNLOCKED, LOWER_CORRIDOR_TO_BUNKS},
+    upgrades,
+    weapons,
+};
 
 /// An action that can be performed in a room
 #[derive(Debug)]
@@ -15,6 +26,8 @@ pub enum RoomAction {
     EscapePodTakeOff,
     /// Find chocolate in the [`StoreRoom`][Room::StoreRoom]
     StoreRoomFindChocolate,
+    /// Find a [stim injector][upgrades::stim_injector] in the [`WashRoom`][Room::WashRoom]
+    WashRoomSearchCabinet,
 
     /// Try to climb into the air vents in the [`Cells`][Room::Cells]
     CellsClimbIntoVents,
@@ -23,8 +36,115 @@ pub enum RoomAction {
     /// Watch the half-G volleyball in the [`MessHall`][Room::MessHall]
     MessHallWatchTheGame,
     /// Find the [captain's diary][Item::CaptainsDiary] in the [`Bunks`][Room::Bunks]
-    BunksGetDiary
+    BunksGetDiary,
+    /// Force open the door to the [`Bunks`][Room::Bunks] from the [`LowerCorridor`][Room::LowerCorridor], on loops where it's [jammed][super::apply_loop_variations]
+    LowerCorridorForceBunksDoor,
+
+    /// Search whichever room the action is in, which may turn up something from the room's [`LootTable`].
+    /// This can be tried again until something is found.
+    Search,
+}
+
+/// Something which can be found by [searching][RoomAction::Search] a room
+#[derive(Debug)]
+pub(super) struct Loot {
+    /// How likely this is to be found, compared to the rest of the [`LootTable`]
+    weight: usize,
+    /// Creates the [`Item`] which is found
+    pub(super) item: fn() -> Item,
+    /// The [key][strings::get] of the title of the [`Screen`] the player is shown when they find it
+    title: &'static str,
+    /// The [key][strings::get] of the content of the [`Screen`] the player is shown when they find it
+    content: &'static str,
+}
+
+/// What can be found by [searching][RoomAction::Search] a room
+#[derive(Debug)]
+pub(super) struct LootTable {
+    /// How likely a search is to find nothing, compared to the weights of the [`Loot`]
+    nothing_weight: usize,
+    /// The things which can be found
+    loot: &'static [Loot],
+}
+
+impl LootTable {
+    /// Gets what is found by searching on a particular roll, or [`None`] if nothing is found
+    fn pick(&self, rng: &mut SeededRng) -> Option<&Loot> {
+        let total = self.nothing_weight + self.loot.iter().map(|loot| loot.weight).sum::<usize>();
+        let mut roll = rng.below(total);
+
+        for loot in self.loot {
+            if roll < loot.weight {
+                return Some(loot);
+            }
+            roll -= loot.weight;
+        }
+
+        None
+    }
+}
+
+/// What can be found by searching the [`Kitchen`][Room::Kitchen]
+const KITCHEN_LOOT: LootTable = LootTable {
+    nothing_weight: 3,
+    loot: &[
+        Loot {
+            weight: 2,
+            item: food::bread_roll,
+            title: "search.kitchen.bread_roll.title",
+            content: "search.kitchen.bread_roll.content",
+        },
+        Loot {
+            weight: 1,
+            item: food::bar_of_chocolate,
+            title: "search.kitchen.bar_of_chocolate.title",
+            content: "search.kitchen.bar_of_chocolate.content",
+        },
+    ],
+};
+
+/// What can be found by searching the [`CrewArea`][Room::CrewArea]
+const CREW_AREA_LOOT: LootTable = LootTable {
+    nothing_weight: 4,
+    loot: &[
+        Loot {
+            weight: 2,
+            item: food::ration_pack,
+            title: "search.crew_area.ration_pack.title",
+            content: "search.crew_area.ration_pack.content",
+        },
+        Loot {
+            weight: 1,
+            item: weapons::throwing_dart_set,
+            title: "search.crew_area.throwing_dart_set.title",
+            content: "search.crew_area.throwing_dart_set.content",
+        },
+    ],
+};
+
+/// The [`LootTable`] for searching a room which doesn't have anything to find
+const EMPTY_LOOT: LootTable = LootTable {
+    nothing_weight: 1,
+    loot: &[],
+};
+
+/// Gets the [`LootTable`] for [searching][RoomAction::Search] the given room
+const fn loot_table(room: Room) -> &'static LootTable {
+    match room {
+        Room::Kitchen => &KITCHEN_LOOT,
+        Room::CrewArea => &CREW_AREA_LOOT,
+        _ => &EMPTY_LOOT,
+    }
+}
+
+/// Gets what is found by [searching][RoomAction::Search] the given room on the given turn, or [`None`] if nothing is found.
+/// The roll is seeded from the room and the turn, so searching at the same point in every loop always finds the same thing.
+pub(super) fn search_result(room: Room, turn: usize) -> Option<&'static Loot> {
+    let mut s = DefaultHasher::new();
+    (room, turn).hash(&mut s);
+    let mut rng = SeededRng::new(s.finish());
 
+    loot_table(room).pick(&mut rng)
 }
 
 /// The result of a [`RoomAction`]
@@ -34,138 +154,265 @@ pub struct RoomActionResult<'a> {
     pub message: Option<Screen<'a>>,
     /// Whether this action should be listed on future turns in this room
     pub show_again: bool,
+    /// Whether the [`Player`] changed their mind part way through, so the action took no time
+    pub cancelled: bool,
 }
 
 impl<'a> RoomActionResult<'a> {
     /// Creates a new [`RoomActionResult`] from the given optional message and whether to show the action again 
     const fn new(message: Option<Screen<'a>>, show_again: bool) -> Self {
-        Self{ message, show_again }
+        Self{ message, show_again, cancelled: false }
+    }
+
+    /// Creates a [`RoomActionResult`] for when the [`Player`] changes their mind, which doesn't take a turn.
+    /// The action is shown again so that it can be taken later.
+    const fn cancelled() -> Self {
+        Self { message: None, show_again: true, cancelled: true }
+    }
+
+    /// Creates a [`RoomActionResult`] for when the [`Player`] finds an item but has no space for it.
+    /// The action is shown again so that the item can be taken once there's space.
+    ///
+    /// ### Params:
+    /// * `title`: what the [`Player`] found, which is shown as the message's title
+    fn hands_full(title: &'a str) -> Self {
+        let screen = Screen::new(title, strings::get("action.hands_full.content"));
+
+        Self::new(Some(screen), true)
     }
 }
 
 impl RoomAction {
     /// Gets the text which will be shown to the player when they are picking an action
-    pub const fn get_description(&self) -> &'static str {
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            Self::StrategyRoomTakeMaps => strings::get("action.strategy_room_take_maps.description"),
+            Self::EngineRoomTakeKeys => strings::get("action.engine_room_take_keys.description"),
+            Self::EscapePodTakeOff => strings::get("action.escape_pod_take_off.description"),
+            Self::StoreRoomFindChocolate => strings::get("action.store_room_find_chocolate.description"),
+            Self::WashRoomSearchCabinet => strings::get("action.wash_room_search_cabinet.description"),
+            Self::CellsClimbIntoVents => strings::get("action.cells_climb_into_vents.description"),
+            Self::BridgeHackTheMainframe => strings::get("action.bridge_hack_the_mainframe.description"),
+            Self::MessHallWatchTheGame => strings::get("action.mess_hall_watch_the_game.description"),
+            Self::BunksGetDiary => strings::get("action.bunks_get_diary.description"),
+            Self::LowerCorridorForceBunksDoor => strings::get("action.lower_corridor_force_bunks_door.description"),
+            Self::Search => strings::get("action.search.description"),
+        }
+    }
+
+    /// Gets why the action can't be taken yet, if it can't.
+    /// Actions which can't be taken are still shown, so that the player knows they're there.
+    pub fn unavailable_reason(&self, player: &Player) -> Option<&'static str> {
+        match self {
+            Self::EscapePodTakeOff if !player.inventory.iter().any(|item| matches!(item, Item::Maps)) => {
+                Some(strings::get("action.escape_pod_take_off.unavailable"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the [`Room`] which this action opens a new connection to, if any.
+    /// This lets [`RoomGraph::unreachable_rooms`][crate::rooms::RoomGraph::unreachable_rooms] count locked rooms as reachable.
+    pub const fn unlocks(&self) -> Option<Room> {
         match self {
-            Self::StrategyRoomTakeMaps => "Take the drive from the computer",
-            Self::EngineRoomTakeKeys => "Check out the cabinet in the corner",
-            Self::EscapePodTakeOff => "Take off",
-            Self::StoreRoomFindChocolate => "Search the tops of the shelves",
-            Self::CellsClimbIntoVents => "Climb into the air vent",
-            Self::BridgeHackTheMainframe => "Hack the mainframe",
-            Self::MessHallWatchTheGame => "Watch the game",
-            Self::BunksGetDiary => "Search underneath the beds"
+            Self::EngineRoomTakeKeys => Some(Room::EscapePod),
+            Self::LowerCorridorForceBunksDoor => Some(Room::Bunks),
+            _ => None,
         }
     }
+
     /// Runs the action
     /// 
     /// ### Params:
     /// * `player`: the [`Player`]'s state. This is used to e.g. add items to their inventory
-    pub fn execute(&self, player: &mut Player) -> RoomActionResult {
-        match self {
+    /// * `menu`: the [`Menu`] to display to, for actions which need to ask the user something
+    #[allow(clippy::too_many_lines)]
+    pub fn execute(&self, player: &mut Player, menu: &mut impl Menu) -> Result<RoomActionResult, MenuSignal> {
+        let result = match self {
             Self::StrategyRoomTakeMaps => {
-                player.pick_up_item(Item::Maps);
+                if player.pick_up_item(Item::Maps).is_err() {
+                    return Ok(RoomActionResult::hands_full(strings::get("action.strategy_room_take_maps.hands_full")));
+                }
 
-                let screen = Screen {
-                    title: "You take the drive",
-                    content: "You take the drive, and read its description - 'Galactic Maps 2168 Edition'",
-                };
+                let screen = Screen::new(
+                    strings::get("action.strategy_room_take_maps.title"),
+                    strings::get("action.strategy_room_take_maps.content"),
+                );
                 RoomActionResult::new(Some(screen), false)
             }
             Self::EngineRoomTakeKeys => {
+                // Check for space first, so that the door isn't unlocked without the key
+                if !player.has_space_for(&Item::EscapePodKeys) {
+                    return Ok(RoomActionResult::hands_full(strings::get("action.engine_room_take_keys.hands_full")));
+                }
+
                 let crew_area_state = player.room_graph.get_state_mut(Room::CrewArea);
 
-                let escape_pod_index = crew_area_state
+                let escape_pod_door = crew_area_state
                     .connections
-                    .iter()
-                    .position(|t|t.prompt_text == Some("Escape Pod")) 
-                    .unwrap();
-
-                crew_area_state.connections[escape_pod_index] = RoomTransition {
-                    message: "You walk up to the door, the same as any other. This time, it detects the key card in your pocket and slides open. \
-It clearly hasn't opened in scores and makes a grating sound. You would worry if there were anyone left alive.",
-                    prompt_text: None,
-                    to: Room::EscapePod
+                    .iter_mut()
+                    .find(|t| t.locked == Some(Room::EscapePod));
+
+                let screen = if let Some(door) = escape_pod_door {
+                    *door = CREW_AREA_TO_ESCAPE_POD_UNLOCKED;
+
+                    Screen::new(
+                        strings::get("action.engine_room_take_keys.title"),
+                        strings::get("action.engine_room_take_keys.content"),
+                    )
+                } else {
+                    // The map has been edited so there's no door to unlock, but the player should still get the key
+                    Screen::new(
+                        strings::get("action.engine_room_take_keys.no_door.title"),
+                        strings::get("action.engine_room_take_keys.no_door.content"),
+                    )
                 };
                 
-                player.pick_up_item(Item::EscapePodKeys);
+                player.pick_up_item(Item::EscapePodKeys).expect("Space should have been checked before unlocking the door");
 
-                let screen = Screen {
-                    title: "You look through the drawers",
-                    content: "You search every drawer. You don't find anything interesting until you get to the second-last one, which has a key card in it. You flip it over and it is labelled 'escape pod'.",
-                };
                 RoomActionResult::new(Some(screen), false)
             }
             Self::EscapePodTakeOff => {
-                let has_maps = player.inventory.iter().any(|item|matches!(&item, Item::Maps));
-
-                if !has_maps {
-                    let screen = Screen {
-                        title: "You try to launch, but there's an error.",
-                        content: "\"Maps out of date: pod cannot launch without in-date maps\". You try to override the message but you can't figure it out."
-                    };
-                    return RoomActionResult::new(Some(screen), true);
+                // The option is disabled without the maps, but the action can still be run directly
+                if self.unavailable_reason(player).is_some() {
+                    let screen = Screen::new(
+                        strings::get("action.escape_pod_take_off.no_maps.title"),
+                        strings::get("action.escape_pod_take_off.no_maps.content"),
+                    );
+                    return Ok(RoomActionResult::new(Some(screen), true));
                 }
 
-                let screen = Screen {
-                    title: "You plug in the maps and blast off",
-                    content: "It's a bit anticlimactic at first but then the thrusters kick in and you feel yourself shuddering home."
-                };
+                let has_food = player.inventory.iter().any(|item|matches!(&item, Item::Food(_)));
+
+                if !has_food {
+                    let options = [strings::get("action.escape_pod_take_off.no_food.option").to_string()];
+                    let list = OptionList::new(&options, strings::get("action.escape_pod_take_off.no_food.prompt"));
+
+                    if menu.show_option_list_cancellable(list)?.is_none() {
+                        return Ok(RoomActionResult::cancelled());
+                    }
+                }
+
+                let screen = Screen::new(
+                    strings::get("action.escape_pod_take_off.title"),
+                    strings::get("action.escape_pod_take_off.content"),
+                );
 
                 player.room = Room::Escape;
 
                 RoomActionResult::new(Some(screen), false)
             }
             Self::StoreRoomFindChocolate => {
-                player.pick_up_item(food::bar_of_chocolate());
-                let screen = Screen {
-                    title: "You run your hands around the top of each shelf in turn",
-                    content: "You eventually feel something - a thin, solid rectangle. You bring it into the light and read - 'Real Cacao'. You pocket it."
-                };
+                if player.pick_up_item(food::bar_of_chocolate()).is_err() {
+                    return Ok(RoomActionResult::hands_full(strings::get("action.store_room_find_chocolate.hands_full")));
+                }
+
+                let screen = Screen::new(
+                    strings::get("action.store_room_find_chocolate.title"),
+                    strings::get("action.store_room_find_chocolate.content"),
+                );
+
+                RoomActionResult::new(Some(screen), false)
+            }
+            Self::WashRoomSearchCabinet => {
+                if player.pick_up_item(upgrades::stim_injector()).is_err() {
+                    return Ok(RoomActionResult::hands_full(strings::get("action.wash_room_search_cabinet.hands_full")));
+                }
+
+                let screen = Screen::new(
+                    strings::get("action.wash_room_search_cabinet.title"),
+                    strings::get("action.wash_room_search_cabinet.content"),
+                );
 
                 RoomActionResult::new(Some(screen), false)
             }
             Self::CellsClimbIntoVents => {
-                player.pick_up_item(Item::Dust);
-                let screen = Screen {
-                    title: "You take out the grate and go to lift yourself up",
-                    content: "You push as hard as you can, but the opening's just not big enough."
+                let options = [
+                    strings::get("action.cells_climb_into_vents.push_harder").to_string(),
+                    strings::get("action.cells_climb_into_vents.give_up").to_string(),
+                ];
+                let list = OptionList::new(&options, strings::get("action.cells_climb_into_vents.prompt"));
+
+                let screen = if menu.show_option_list(list)? == 0 {
+                    // Dust isn't worth making space for
+                    let _ = player.pick_up_item(Item::Dust);
+                    Screen::new(
+                        strings::get("action.cells_climb_into_vents.push.title"),
+                        strings::get("action.cells_climb_into_vents.push.content"),
+                    )
+                } else {
+                    Screen::new(
+                        strings::get("action.cells_climb_into_vents.give_up.title"),
+                        strings::get("action.cells_climb_into_vents.give_up.content"),
+                    )
                 };
 
                 RoomActionResult::new(Some(screen), true)
             }
             Self::BridgeHackTheMainframe => {
-                player.pick_up_item(Item::Shame);
-                let screen = Screen {
-                    title: "You walk over to the computer",
-                    content: "You type ' OR 1 = 1'. Nothing happens. 
-You type 'a; DROP TABLE Prisoners'. Nothing happens. 
-You type '<script>alert(\"This is easier in the movies\")</script>'. Nothing happens.
-You leave the computer and pretend nothing ever happened (which it didn't)."
-                };
+                // Shame isn't worth making space for either
+                let _ = player.pick_up_item(Item::Shame);
+                let screen = Screen::new(
+                    strings::get("action.bridge_hack_the_mainframe.title"),
+                    strings::get("action.bridge_hack_the_mainframe.content"),
+                );
 
                 RoomActionResult::new(Some(screen), true)
             }
             Self::MessHallWatchTheGame => {
-                let screen = Screen {
-                    title: "You take a seat and watch the half-G volleyball",
-                    content: "That's half-G relative to Earth's g=9.8Nkg-1, of course, not the Arnithian standard of g=11Nkg-1. It's a quirk of history, really. \
-The Martian Moonmen are doing awfully well, but you know you should really be cheering for the Venutian Vikings instead. Even with half gravity it's impressive how high they punt the ball. \
-You look up and realise its been a long while since you sat down. That was a nice break, but you've got more important things to do."
-                };
+                let screen = Screen::new(
+                    strings::get("action.mess_hall_watch_the_game.title"),
+                    strings::get("action.mess_hall_watch_the_game.content"),
+                );
 
                 RoomActionResult::new(Some(screen), false)
             }
             Self::BunksGetDiary => {
-                player.pick_up_item(Item::CaptainsDiary(0));
+                if player.pick_up_item(Item::CaptainsDiary(0)).is_err() {
+                    return Ok(RoomActionResult::hands_full(strings::get("action.bunks_get_diary.hands_full")));
+                }
 
-                let screen = Screen {
-                    title: "You poke your head under the beds",
-                    content: "You see a small messy paper book. You take it out and read the title - 'Captain's Diary - Private'"
-                };
+                let screen = Screen::new(
+                    strings::get("action.bunks_get_diary.title"),
+                    strings::get("action.bunks_get_diary.content"),
+                );
             
                 RoomActionResult::new(Some(screen), false)
             }
-        }
+            Self::LowerCorridorForceBunksDoor => {
+                let lower_corridor_state = player.room_graph.get_state_mut(Room::LowerCorridor);
+
+                // Like the escape pod door, the jammed door keeps the player in the lower corridor
+                if let Some(door) = lower_corridor_state.connections.iter_mut().find(|t| t.to == Room::LowerCorridor) {
+                    *door = LOWER_CORRIDOR_TO_BUNKS;
+                }
+
+                let screen = Screen::new(
+                    strings::get("action.lower_corridor_force_bunks_door.title"),
+                    strings::get("action.lower_corridor_force_bunks_door.content"),
+                );
+
+                RoomActionResult::new(Some(screen), false)
+            }
+            Self::Search => {
+                let Some(loot) = search_result(player.room, player.turns_taken()) else {
+                    let screen = Screen::new(
+                        strings::get("action.search.nothing.title"),
+                        strings::get("action.search.nothing.content"),
+                    );
+                    return Ok(RoomActionResult::new(Some(screen), true));
+                };
+
+                if player.pick_up_item((loot.item)()).is_err() {
+                    return Ok(RoomActionResult::hands_full(strings::get("action.search.hands_full")));
+                }
+
+                // Once something's been found, there's nothing else worth searching for
+                let screen = Screen::new(strings::get(loot.title), strings::get(loot.content));
+                RoomActionResult::new(Some(screen), false)
+            }
+        };
+
+        Ok(result)
     }
 }
\ No newline at end of file
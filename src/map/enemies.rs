@@ -1,42 +1,84 @@
 //! Functions to create [enemies][Enemy]
 
-use crate::combat::{Enemy, Health};
+use crate::combat::{Enemy, EnemyAi, Health};
+use crate::difficulty::Difficulty;
+use crate::strings;
 
 use super::weapons;
 
-/// Creates a new 'cook' enemy
-pub(super) fn cook() -> Enemy {
+/// Creates a new 'cook' enemy, with health scaled for the given [`Difficulty`]
+pub(super) fn cook(difficulty: Difficulty) -> Enemy {
     Enemy {
-        name: "Cook",
-        description: "The ship's cook. There's not much to do when there aren't any troops, so they're sitting around watching the game.",
+        name: strings::get("enemy.cook.name"),
+        description: strings::get("enemy.cook.description"),
         inventory: vec![weapons::standard_blaster()],
 
-        health: Health::new(7),
-        max_health: Health::new(7),
+        health: Health::new(difficulty.scale_health(7)),
+        max_health: Health::new(difficulty.scale_health(7)),
+        status_effects: Vec::new(),
+        difficulty,
+        ai: EnemyAi::Random,
+        last_player_action: None,
+        disarmed_weapons: Vec::new(),
+        enraged: false,
+        stunned_turns: 0,
     }
 }
 
-/// Creates a new 'mechanic' enemy
-pub(super) fn mechanic() -> Enemy {
+/// Creates a new 'mechanic' enemy, with health scaled for the given [`Difficulty`]
+pub(super) fn mechanic(difficulty: Difficulty) -> Enemy {
     Enemy {
-        name: "Mechanic",
-        description: "The ship's mechanic. They check the ship every cycle and fix anything that's broken. They were previously a high ranking ISPD officer and still carry a taser everywhere with them. \
-At the moment they're checking the ship's comms, while listening to music through a pair of particularly bulky headphones. Bad practice, of course, but you don't mind.",
+        name: strings::get("enemy.mechanic.name"),
+        description: strings::get("enemy.mechanic.description"),
         inventory: vec![weapons::ispd_taser()],
 
-        health: Health::new(10),
-        max_health: Health::new(10),
+        health: Health::new(difficulty.scale_health(10)),
+        max_health: Health::new(difficulty.scale_health(10)),
+        status_effects: Vec::new(),
+        difficulty,
+        ai: EnemyAi::Random,
+        last_player_action: None,
+        disarmed_weapons: Vec::new(),
+        enraged: false,
+        stunned_turns: 0,
     }
 }
 
-/// Creates a new 'skipper' enemy
-pub(super) fn skipper() -> Enemy {
+/// Creates a new 'skipper' enemy, with health scaled for the given [`Difficulty`].
+/// The skipper is experienced in combat, so they [predict][EnemyAi::Predictive] which way the player will dodge.
+pub(super) fn skipper(difficulty: Difficulty) -> Enemy {
     Enemy {
-        name: "Skipper",
-        description: "The ship's captain. Having served in the 2143-2152 inter-system war, they have great experience in combat. On the other hand, they're very good at forgetting things.",
+        name: strings::get("enemy.skipper.name"),
+        description: strings::get("enemy.skipper.description"),
         inventory: vec![weapons::captains_blaster()],
 
-        health: Health::new(15),
-        max_health: Health::new(15),
+        health: Health::new(difficulty.scale_health(15)),
+        max_health: Health::new(difficulty.scale_health(15)),
+        status_effects: Vec::new(),
+        difficulty,
+        ai: EnemyAi::Predictive,
+        last_player_action: None,
+        disarmed_weapons: Vec::new(),
+        enraged: false,
+        stunned_turns: 0,
+    }
+}
+
+/// Creates a new 'loader-bot' enemy, with health scaled for the given [`Difficulty`]
+pub(super) fn loader_bot(difficulty: Difficulty) -> Enemy {
+    Enemy {
+        name: strings::get("enemy.loader_bot.name"),
+        description: strings::get("enemy.loader_bot.description"),
+        inventory: vec![weapons::hydraulic_clamp()],
+
+        health: Health::new(difficulty.scale_health(12)),
+        max_health: Health::new(difficulty.scale_health(12)),
+        status_effects: Vec::new(),
+        difficulty,
+        ai: EnemyAi::Random,
+        last_player_action: None,
+        disarmed_weapons: Vec::new(),
+        enraged: false,
+        stunned_turns: 0,
     }
 }
@@ -3,22 +3,35 @@
 use crate::{
     combat::Damage,
     items::{Food, Item},
+    strings,
 };
 
 /// Creates a new 'bread roll' item
-pub(super) const fn bread_roll() -> Item {
+pub(super) fn bread_roll() -> Item {
     Item::Food(Food {
-        name: "Bread roll",
-        description: "A soft white bread roll. It's tasty, but not substantial.",
+        name: strings::get("item.bread_roll.name"),
+        description: strings::get("item.bread_roll.description"),
         heals_for: Damage::new(5),
+        count: 1,
     })
 }
 
 /// Creates a new 'bar of chocolate' item
-pub(super) const fn bar_of_chocolate() -> Item {
+pub(super) fn bar_of_chocolate() -> Item {
     Item::Food(Food {
-        name: "Bar of Chocolate",
-        description: "A bar of dark chocolate. It says on the label that it's made from real cacao, bred from plants that trace their lineage all the way back to Earth!",
+        name: strings::get("item.bar_of_chocolate.name"),
+        description: strings::get("item.bar_of_chocolate.description"),
         heals_for: Damage::new(10),
+        count: 1,
     })
-}
\ No newline at end of file
+}
+
+/// Creates a new 'ration pack' item
+pub(super) fn ration_pack() -> Item {
+    Item::Food(Food {
+        name: strings::get("item.ration_pack.name"),
+        description: strings::get("item.ration_pack.description"),
+        heals_for: Damage::new(8),
+        count: 1,
+    })
+}
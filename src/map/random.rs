@@ -0,0 +1,57 @@
+//! Deterministic pseudo-random numbers for shuffling the map
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+/// A source of random-seeming numbers which always produces the same numbers from the same seed.
+/// Like the [`Enemy`][crate::combat::Enemy]'s choices, it's based on hashing:
+/// each number is the hash of the seed and how many numbers came before it.
+#[derive(Debug)]
+pub struct SeededRng {
+    /// The seed the numbers are generated from
+    seed: u64,
+    /// How many numbers have been generated so far
+    count: u64,
+}
+
+impl SeededRng {
+    /// Creates a new [`SeededRng`] from a seed
+    pub const fn new(seed: u64) -> Self {
+        Self { seed, count: 0 }
+    }
+
+    /// Creates a new [`SeededRng`] for one loop of a run, so that each loop gets different numbers from the same seed
+    pub fn for_loop(seed: u64, loop_number: usize) -> Self {
+        let mut s = DefaultHasher::new();
+        (seed, loop_number).hash(&mut s);
+        Self::new(s.finish())
+    }
+
+    /// Gets the next number
+    pub fn next_u64(&mut self) -> u64 {
+        let mut s = DefaultHasher::new();
+        (self.seed, self.count).hash(&mut s);
+        self.count += 1;
+        s.finish()
+    }
+
+    /// Gets a number in the range `0..n`
+    ///
+    /// ### Panics
+    /// * If `n` is 0
+    #[allow(clippy::cast_possible_truncation)] // The result is less than `n`, so it fits in a usize
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Range should not be empty");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Shuffles a slice in place, using the Fisher-Yates shuffle
+    pub(super) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
@@ -0,0 +1,523 @@
+#![cfg(test)]
+
+use super::*;
+use crate::{combat::Killer, effects::NoEffects, items::Item, menu::tests::MockMenu, player::Player, rooms::GraphError};
+
+/// Tests that enemies have strictly more health on [`Difficulty::Hard`] than on [`Difficulty::Easy`]
+#[test]
+fn test_difficulty_scales_enemy_health() {
+    for enemy in [enemies::cook, enemies::mechanic, enemies::skipper, enemies::loader_bot] {
+        let easy = enemy(Difficulty::Easy);
+        let normal = enemy(Difficulty::Normal);
+        let hard = enemy(Difficulty::Hard);
+
+        assert!(easy.max_health < normal.max_health);
+        assert!(normal.max_health < hard.max_health);
+        assert_eq!(hard.health, hard.max_health);
+    }
+
+    // The same should be true of the enemies placed in the map
+    let easy_map = init(Difficulty::Easy);
+    let hard_map = init(Difficulty::Hard);
+
+    for room in [Room::StrategyRoom, Room::MessHall, Room::EngineRoom, Room::CargoHold] {
+        let easy = easy_map.get_state(room).enemy.as_ref().unwrap();
+        let hard = hard_map.get_state(room).enemy.as_ref().unwrap();
+
+        assert!(easy.max_health < hard.max_health);
+    }
+}
+
+/// Tests the rounding of [`Difficulty::scale_health`]
+#[test]
+fn test_scale_health() {
+    assert_eq!(Difficulty::Easy.scale_health(7), 5);
+    assert_eq!(Difficulty::Easy.scale_health(10), 8);
+    assert_eq!(Difficulty::Normal.scale_health(10), 10);
+    assert_eq!(Difficulty::Hard.scale_health(7), 11);
+    assert_eq!(Difficulty::Hard.scale_health(15), 23);
+}
+
+/// Tests that every room transition leads to a room in the graph, and that the player can always go back the way they came
+#[test]
+fn test_transitions_connect_both_ways() {
+    let graph = init(Difficulty::Normal);
+
+    for (&room, state) in &graph.rooms {
+        // The escape pod is entered by a room action rather than a transition, so there's no way back to it
+        if room == Room::EscapePod {
+            continue;
+        }
+
+        for transition in &state.connections {
+            let to_state = graph.get_state(transition.to);
+            assert!(
+                to_state.connections.iter().any(|t| t.to == room),
+                "No way back from {:?} to {:?}",
+                transition.to,
+                room
+            );
+        }
+    }
+}
+
+/// Tests that the way back from a room with a direction is the opposite direction, and how directions are shown to the player
+#[test]
+fn test_transition_directions() {
+    use crate::rooms::Direction::*;
+
+    let graph = init(Difficulty::Normal);
+
+    for (&room, state) in &graph.rooms {
+        for transition in &state.connections {
+            let Some(direction) = transition.direction else { continue };
+            let back = graph.get_state(transition.to).connections.iter().find(|t| t.to == room).unwrap();
+
+            let opposite = match direction {
+                North => South,
+                South => North,
+                East => West,
+                West => East,
+                Up => Down,
+                Down => Up,
+            };
+            assert_eq!(back.direction, Some(opposite), "{room:?} to {:?}", transition.to);
+        }
+    }
+
+    let to_kitchen = graph.get_state(Room::MessHall).connections.iter().find(|t| t.to == Room::Kitchen).unwrap();
+    assert_eq!(to_kitchen.option_text(), "Go to the Kitchen (east)");
+    assert_eq!(to_kitchen.title(), "You go east to the Kitchen");
+
+    // Transitions without a direction are shown as they were before
+    let to_pod = graph.get_state(Room::CrewArea).connections.iter().find(|t| t.prompt_text == Some("Escape Pod")).unwrap();
+    assert_eq!(to_pod.option_text(), "Go to the Escape Pod");
+    assert_eq!(to_pod.title(), "You go to the Escape Pod");
+}
+
+/// Tests that [`RoomGraph::unreachable_rooms`] finds nothing wrong with the real map, but flags a room with no way in
+#[test]
+fn test_unreachable_rooms() {
+    let mut graph = init(Difficulty::Normal);
+    assert_eq!(graph.unreachable_rooms(STARTING_ROOM), Vec::new());
+
+    // Cut the cargo hold off from the rest of the ship
+    graph.get_state_mut(Room::LowerCorridor).connections.retain(|t| t.to != Room::CargoHold);
+    assert_eq!(graph.unreachable_rooms(STARTING_ROOM), vec![Room::CargoHold]);
+
+    // Without the engine room's key, the escape pod can't be reached either
+    graph.get_state_mut(Room::EngineRoom).actions.clear();
+    let mut unreachable = graph.unreachable_rooms(STARTING_ROOM);
+    unreachable.sort_by_key(|room| room.get_name());
+    assert_eq!(unreachable, vec![Room::CargoHold, Room::EscapePod]);
+}
+
+/// Tests that [`RoomGraph::missing_rooms`] flags a connection to a room which isn't in the map
+#[test]
+fn test_missing_rooms() {
+    let mut graph = init(Difficulty::Normal);
+    assert_eq!(graph.missing_rooms(), Vec::new());
+
+    graph.rooms.remove(&Room::CargoHold);
+    assert_eq!(graph.missing_rooms(), vec![(Room::LowerCorridor, Room::CargoHold)]);
+    // A missing room doesn't stop the rest of the map being searched
+    assert_eq!(graph.unreachable_rooms(STARTING_ROOM), Vec::new());
+}
+
+/// Tests that [`RoomGraph::validate`] accepts the real map and describes each kind of mistake
+#[test]
+fn test_validate() {
+    assert_eq!(init(Difficulty::Normal).validate(STARTING_ROOM), Ok(()));
+
+    // A state stored under the wrong room
+    let mut graph = init(Difficulty::Normal);
+    let kitchen = graph.rooms.remove(&Room::Kitchen).unwrap();
+    graph.rooms.insert(Room::Kitchen, RoomState::new(Room::Bunks, kitchen.connections));
+    assert_eq!(
+        graph.validate(STARTING_ROOM),
+        Err(GraphError::MismatchedRoom { key: Room::Kitchen, room: Room::Bunks })
+    );
+
+    // A connection to a room which isn't in the map
+    let mut graph = init(Difficulty::Normal);
+    graph.rooms.remove(&Room::CargoHold);
+    assert_eq!(
+        graph.validate(STARTING_ROOM),
+        Err(GraphError::MissingRoom { from: Room::LowerCorridor, to: Room::CargoHold })
+    );
+
+    // A room with no way in
+    let mut graph = init(Difficulty::Normal);
+    graph.get_state_mut(Room::LowerCorridor).connections.retain(|t| t.to != Room::CargoHold);
+    let error = graph.validate(STARTING_ROOM).unwrap_err();
+    assert_eq!(error, GraphError::UnreachableRooms(vec![Room::CargoHold]));
+    assert_eq!(error.to_string(), "these rooms can't be reached: [CargoHold]");
+}
+
+/// Tests the route out of the ship: taking the keys opens the escape pod door, and taking off from the pod wins the game
+#[test]
+fn test_escape_route() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+    let leads_to_pod = |player: &Player| {
+        player.room_graph.get_state(Room::CrewArea).connections.iter().any(|t| t.to == Room::EscapePod)
+    };
+
+    // The door is locked until the keys are taken
+    assert!(!leads_to_pod(&player));
+    RoomAction::EngineRoomTakeKeys.execute(&mut player, &mut menu).unwrap();
+    assert!(leads_to_pod(&player));
+
+    // Once the door is unlocked, the escape pod can be reached by connections alone
+    player.room_graph.get_state_mut(Room::EngineRoom).actions.clear();
+    assert_eq!(player.room_graph.validate(STARTING_ROOM), Ok(()));
+
+    player.room = Room::EscapePod;
+    player.inventory.push(Item::Maps);
+    player.inventory.push(food::bread_roll());
+    RoomAction::EscapePodTakeOff.execute(&mut player, &mut menu).unwrap();
+    assert_eq!(player.room, Room::Escape);
+}
+
+/// Tests that taking off is shown but can't be chosen until the player has the maps
+#[test]
+fn test_take_off_disabled_without_maps() {
+    let mut player = Player::init_with(Difficulty::Normal, Room::EscapePod, vec![food::bread_roll()]);
+
+    let mut menu = MockMenu::builder().choose_option("Look around the room again").build();
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    let (_, options) = menu.last_list().unwrap();
+    assert!(options.contains(&"Take off (the pod won't launch without in-date maps)".to_string()));
+
+    player.inventory.push(Item::Maps);
+    let mut menu = MockMenu::builder().choose_option("Take off").build();
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.room, Room::Escape);
+}
+
+/// Tests that searching for the chocolate with a full inventory leaves it to be found again once there's space
+#[test]
+fn test_chocolate_with_full_inventory() {
+    let mut player = Player::init_with(Difficulty::Normal, Room::StoreRoom, vec![Item::Dust]);
+    player.max_inventory_size = Some(1);
+
+    let mut menu = MockMenu::builder().choose_option("Search the tops of the shelves").build();
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert!(matches!(player.inventory[..], [Item::Dust]));
+    assert_eq!(player.items_collected, 0);
+    assert_eq!(menu.screens().last().unwrap().0, "You find a bar of chocolate, but your hands are full");
+
+    player.max_inventory_size = Some(2);
+    let mut menu = MockMenu::builder().choose_option("Search the tops of the shelves").build();
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.inventory.len(), 2);
+    assert_eq!(player.inventory[1].get_name(), "Bar of Chocolate");
+
+    // The action is done with now that the chocolate has been taken
+    let mut menu = MockMenu::builder().choose_option("Look around the room again").build();
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    let (_, options) = menu.last_list().unwrap();
+    assert!(!options.contains(&"Search the tops of the shelves".to_string()));
+}
+
+/// Tests that the strategy room's trigger fires the first time the player goes in, but not when they come back
+#[test]
+fn test_room_trigger_fires_once() {
+    let mut player = Player::init_with(Difficulty::Normal, Room::UpperCorridor, Vec::new());
+    let mut menu = MockMenu::builder()
+        .choose_option("Go to the Strategy Room")
+        .choose_option("Go to the Upper Corridor")
+        .choose_option("Go to the Strategy Room")
+        .build();
+
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.room, Room::StrategyRoom);
+    assert_eq!(menu.screens().last().unwrap().0, "Star charts cover every wall");
+    assert!(player.get_room_state().on_enter.is_none());
+
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    let fired = menu.screens().iter().filter(|(title, _)| title == "Star charts cover every wall").count();
+    assert_eq!(fired, 1);
+
+    // The next loop starts with a fresh map, so the trigger is back
+    let player = Player::init(Difficulty::Normal);
+    assert!(player.room_graph.get_state(Room::StrategyRoom).on_enter.is_some());
+}
+
+/// Tests that undoing the move into a room puts its [trigger][crate::rooms::RoomState::on_enter] back, so it fires again on the way back in
+#[test]
+fn test_undo_restores_room_trigger() {
+    let mut player = Player::init_with(Difficulty::Normal, Room::UpperCorridor, Vec::new());
+    let mut menu = MockMenu::builder()
+        .choose_option("Go to the Strategy Room")
+        .choose_option("Undo your last action")
+        .choose_option("Go to the Strategy Room")
+        .build();
+
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.room, Room::UpperCorridor);
+    assert!(player.room_graph.get_state(Room::StrategyRoom).on_enter.is_some());
+
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    let fired = menu.screens().iter().filter(|(title, _)| title == "Star charts cover every wall").count();
+    assert_eq!(fired, 2);
+}
+
+/// Pins how [effective][crate::items::Weapon::effective_damage] each weapon is compared to the others, so that balance changes are intentional.
+/// The darts are the fastest weapon but the weakest, and the taser is the strongest.
+#[test]
+fn test_weapon_effectiveness_order() {
+    let mut weapons: Vec<_> = [
+        weapons::intruders_blaster(),
+        weapons::captains_blaster(),
+        weapons::standard_blaster(),
+        weapons::ispd_taser(),
+        weapons::throwing_dart_set(),
+        weapons::shaving_razor(),
+        weapons::wrench(),
+        weapons::eating_knife(),
+        weapons::serrated_shiv(),
+        weapons::hydraulic_clamp(),
+    ]
+    .into_iter()
+    .map(|item| {
+        let Item::Weapon(w) = item else { panic!("{} should be a weapon", item.get_name()) };
+        w
+    })
+    .collect();
+
+    let fastest = weapons.iter().min_by_key(|w| w.speed).unwrap();
+    assert_eq!(fastest.name, "Set of Throwing Darts");
+
+    weapons.sort_by(|a, b| b.effective_damage().total_cmp(&a.effective_damage()));
+    let names: Vec<_> = weapons.iter().map(|w| w.name).collect();
+    assert_eq!(
+        names,
+        [
+            "ISPD Taser",
+            "Captain's Blaster",
+            "Eating Knife",
+            "Hydraulic Clamp",
+            "Serrated Shiv",
+            "Intruders Blaster",
+            "Wrench",
+            "Standard Issue Blaster",
+            "Shaving Razor",
+            "Set of Throwing Darts",
+        ]
+    );
+}
+
+/// Tests fighting the mechanic with the wrench from the start of a loop, without walking to the engine room first.
+/// The taser is faster and hits much harder, so trading straight attacks with the mechanic loses, and the mechanic is named as the killer.
+#[test]
+fn test_fight_mechanic_with_wrench() {
+    let mut player = Player::init_with(Difficulty::Normal, Room::EngineRoom, vec![weapons::wrench()]);
+    let mechanic = player.get_room_state_mut().enemy.take().unwrap();
+    assert_eq!(mechanic.name, "Mechanic");
+
+    let mut builder = MockMenu::builder();
+    for _ in 0..4 {
+        builder = builder.choose_option("Attack with your Wrench").choose_option("Attack Straight");
+    }
+    let mut menu = builder.choose_option("Continue").build();
+
+    let result = crate::combat::battle(&mut player, mechanic, &mut menu, &mut NoEffects).unwrap();
+    let killer = Killer {
+        enemy: "Mechanic",
+        weapon: Some("ISPD Taser"),
+    };
+    assert_eq!(result, crate::combat::BattleResult::PlayerLoss(killer));
+    assert_eq!(killer.describe(player.turns_taken()), "Killed by the Mechanic's ISPD Taser on turn 4.");
+    assert!(player.health.is_0());
+    assert_eq!(menu.remaining_choices(), 0);
+}
+
+/// Tests that taking the keys still works if the escape pod door has been moved or removed from the crew area
+#[test]
+fn test_take_keys_without_door() {
+    let mut menu = MockMenu::default();
+
+    // The door is found by the room it's locking rather than by its position
+    let mut player = Player::init(Difficulty::Normal);
+    player.room_graph.get_state_mut(Room::CrewArea).connections.reverse();
+    RoomAction::EngineRoomTakeKeys.execute(&mut player, &mut menu).unwrap();
+    assert!(player.room_graph.get_state(Room::CrewArea).connections.iter().any(|t| t.to == Room::EscapePod));
+
+    // Other doors which leave the player in the crew area aren't mistaken for the escape pod door
+    let mut player = Player::init(Difficulty::Normal);
+    player.room_graph.get_state_mut(Room::CrewArea).connections.insert(0, STORE_ROOM_TO_CREW_AREA);
+    RoomAction::EngineRoomTakeKeys.execute(&mut player, &mut menu).unwrap();
+    let connections = &player.room_graph.get_state(Room::CrewArea).connections;
+    assert_eq!(connections[0].to, Room::CrewArea);
+    assert!(connections.iter().any(|t| t.to == Room::EscapePod));
+
+    // With no door, the player is told about the problem but still gets the key
+    let mut player = Player::init(Difficulty::Normal);
+    player.room_graph.get_state_mut(Room::CrewArea).connections.retain(|t| t.locked.is_none());
+    let result = RoomAction::EngineRoomTakeKeys.execute(&mut player, &mut menu).unwrap();
+    assert_eq!(result.message.unwrap().title, "You find a key card, but nothing to use it on");
+    assert!(player.inventory.iter().any(|item| matches!(item, Item::EscapePodKeys)));
+}
+
+/// Gets the room which has the given action, if any room does
+fn room_with_action(graph: &RoomGraph, is_action: fn(&RoomAction) -> bool) -> Option<Room> {
+    graph.rooms.values().find(|state| state.actions.iter().any(is_action)).map(|state| state.room)
+}
+
+/// Tests that every randomised layout can be won: the keys can be found without going through the escape pod door,
+/// and once the door is unlocked the maps and the escape pod can both be reached
+#[test]
+fn test_randomized_layouts_are_winnable() {
+    for seed in 0..500 {
+        let graph = init_randomized(Difficulty::Normal, seed);
+
+        let locked = graph.reachable_rooms(STARTING_ROOM, false);
+        let unlocked = graph.reachable_rooms(STARTING_ROOM, true);
+
+        let keys_room = room_with_action(&graph, |a| matches!(a, RoomAction::EngineRoomTakeKeys)).unwrap();
+        let maps_room = room_with_action(&graph, |a| matches!(a, RoomAction::StrategyRoomTakeMaps)).unwrap();
+
+        assert!(locked.contains(&keys_room), "Seed {seed} put the keys behind the door they unlock");
+        assert!(unlocked.contains(&maps_room), "Seed {seed} put the maps out of reach");
+        assert!(unlocked.contains(&Room::EscapePod), "Seed {seed} made the escape pod unreachable");
+
+        assert!(graph.get_state(STARTING_ROOM).enemy.is_none(), "Seed {seed} put an enemy in the starting room");
+        assert!(graph.get_state(Room::EscapePod).items.is_empty());
+    }
+}
+
+/// Tests that randomising the map keeps every item and enemy, and that the same seed always gives the same layout
+#[test]
+fn test_randomized_layouts_are_reproducible() {
+    /// Describes where everything is in a map, in an order which doesn't depend on the `HashMap`
+    fn layout(graph: &RoomGraph) -> Vec<String> {
+        let mut layout: Vec<_> = graph
+            .rooms
+            .values()
+            .map(|state| {
+                let items: Vec<_> = state.items.iter().map(Item::get_name).collect();
+                let enemy = state.enemy.as_ref().map(|e| e.name);
+                format!("{:?}: {items:?} {enemy:?} {:?}", state.room, state.actions)
+            })
+            .collect();
+        layout.sort();
+        layout
+    }
+
+    /// Counts the items and enemies in a map
+    fn count(graph: &RoomGraph) -> (usize, usize) {
+        let items = graph.rooms.values().map(|state| state.items.len()).sum();
+        let enemies = graph.rooms.values().filter(|state| state.enemy.is_some()).count();
+        (items, enemies)
+    }
+
+    let fixed = init(Difficulty::Normal);
+    let first = init_randomized(Difficulty::Normal, 1);
+
+    assert_eq!(count(&first), count(&fixed));
+    assert_eq!(layout(&first), layout(&init_randomized(Difficulty::Normal, 1)));
+    assert_ne!(layout(&first), layout(&init_randomized(Difficulty::Normal, 2)));
+}
+
+/// Tests that loop variations never stop the game being won, only start from the second loop, and are the same for the same seed and loop
+#[test]
+fn test_loop_variations() {
+    let mut jammed = 0;
+    let mut enemy_away = 0;
+
+    for seed in 0..100 {
+        // The first loop is always the same as the normal map
+        let mut first = init(Difficulty::Normal);
+        apply_loop_variations(&mut first, seed, 1);
+        assert!(first.get_state(Room::MessHall).enemy.is_some());
+        assert_eq!(first.get_state(Room::Kitchen).items.len(), 2);
+
+        for loop_number in 2..6 {
+            let mut graph = init_randomized(Difficulty::Normal, seed);
+            apply_loop_variations(&mut graph, seed, loop_number);
+            assert_eq!(graph.validate(STARTING_ROOM), Ok(()));
+
+            let unlocked = graph.reachable_rooms(STARTING_ROOM, true);
+            let keys_room = room_with_action(&graph, |a| matches!(a, RoomAction::EngineRoomTakeKeys)).unwrap();
+            let maps_room = room_with_action(&graph, |a| matches!(a, RoomAction::StrategyRoomTakeMaps)).unwrap();
+            assert!(unlocked.contains(&keys_room) && unlocked.contains(&maps_room) && unlocked.contains(&Room::EscapePod));
+
+            if room_with_action(&graph, |a| matches!(a, RoomAction::LowerCorridorForceBunksDoor)).is_some() {
+                jammed += 1;
+            }
+            // Randomised maps often have nobody in the mess hall anyway
+            if graph.get_state(Room::MessHall).enemy.is_none() && init_randomized(Difficulty::Normal, seed).get_state(Room::MessHall).enemy.is_some() {
+                enemy_away += 1;
+            }
+            if loop_number.is_multiple_of(2) {
+                assert!(graph.get_state(Room::Kitchen).items.is_empty());
+            }
+
+            // The same seed and loop always give the same changes
+            let mut again = init_randomized(Difficulty::Normal, seed);
+            apply_loop_variations(&mut again, seed, loop_number);
+            assert_eq!(again.get_state(Room::MessHall).enemy.is_none(), graph.get_state(Room::MessHall).enemy.is_none());
+            assert_eq!(again.get_state(Room::LowerCorridor).actions.len(), graph.get_state(Room::LowerCorridor).actions.len());
+        }
+    }
+
+    // Each variation happens sometimes, but not most of the time
+    assert!((1..200).contains(&jammed), "{jammed}");
+    assert!((1..150).contains(&enemy_away), "{enemy_away}");
+}
+
+/// Tests forcing the jammed door to the bunks open
+#[test]
+fn test_force_jammed_door() {
+    let mut player = Player::init(Difficulty::Normal);
+    player.room = Room::LowerCorridor;
+    let lower_corridor = player.room_graph.get_state_mut(Room::LowerCorridor);
+    let door = lower_corridor.connections.iter_mut().find(|t| t.to == Room::Bunks).unwrap();
+    *door = LOWER_CORRIDOR_TO_BUNKS_JAMMED;
+
+    // The jammed door still has the same option, but leads nowhere
+    let door = player.get_room_state().connections.iter().find(|t| t.prompt_text == Some("Bunks")).unwrap();
+    assert_eq!(door.to, Room::LowerCorridor);
+    assert_eq!(door.option_text(), "Go to the Bunks");
+
+    RoomAction::LowerCorridorForceBunksDoor.execute(&mut player, &mut MockMenu::default()).unwrap();
+    assert!(player.get_room_state().connections.iter().any(|t| t.to == Room::Bunks));
+    assert!(!player.get_room_state().connections.iter().any(|t| t.to == Room::LowerCorridor));
+}
+
+/// Tests that searching a room can be repeated until it turns something up,
+/// and that what's found only depends on the room and the turn
+#[test]
+fn test_search() {
+    let mut player = Player::init_with(Difficulty::Normal, Room::Kitchen, Vec::new());
+    let mut menu = MockMenu::default();
+    let search = RoomAction::Search;
+
+    // Keep searching until something is found, which should happen long before the loop ends
+    let found_turn = loop {
+        let turn = player.turns_taken();
+        let result = search.execute(&mut player, &mut menu).unwrap();
+        if !result.show_again {
+            break turn;
+        }
+
+        assert_eq!(result.message.unwrap().title, "You search the room");
+        assert!(player.inventory.is_empty());
+        player.use_turn();
+    };
+
+    let expected = (actions::search_result(Room::Kitchen, found_turn).unwrap().item)();
+    assert!(matches!(&player.inventory[..], [item] if item.get_name() == expected.get_name()));
+
+    // Searching on the same turn of another loop finds the same thing
+    let mut player = Player::init_with(Difficulty::Normal, Room::Kitchen, Vec::new());
+    player.remaining_turns -= found_turn;
+    assert!(!search.execute(&mut player, &mut menu).unwrap().show_again);
+    assert!(matches!(&player.inventory[..], [item] if item.get_name() == expected.get_name()));
+
+    // A room without a loot table never has anything to find
+    assert!((0..100).all(|turn| actions::search_result(Room::Bridge, turn).is_none()));
+}
@@ -0,0 +1,32 @@
+//! Contains the [`RoomTrigger`] type, for things which happen when the player enters a room
+
+use crate::{menu::{Menu, MenuSignal, Screen}, player::Player, strings};
+
+/// Something which happens the first time the [`Player`] enters a room in a loop.
+/// Each [`RoomState`][crate::rooms::RoomState] has at most one, which is taken out of it when it fires, so it only fires once per loop.
+/// Undoing the move into the room puts the trigger back, so it fires again if the player comes back in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoomTrigger {
+    /// Notice the star charts in the [`StrategyRoom`][crate::rooms::Room::StrategyRoom]
+    StrategyRoomStarCharts,
+}
+
+impl RoomTrigger {
+    /// Runs the trigger
+    ///
+    /// ### Params:
+    /// * `player`: the [`Player`]'s state, for triggers which change it
+    /// * `menu`: the [`Menu`] to display to
+    pub fn fire(self, _player: &mut Player, menu: &mut impl Menu) -> Result<(), MenuSignal> {
+        match self {
+            Self::StrategyRoomStarCharts => {
+                menu.show_screen(Screen::new(
+                    strings::get("trigger.strategy_room_star_charts.title"),
+                    strings::get("trigger.strategy_room_star_charts.content"),
+                ))?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+//! functions to create [`Upgrade`] items
+
+use crate::{
+    combat::Damage,
+    items::{Item, Upgrade},
+    strings,
+};
+
+/// Creates a new 'stim injector' item
+pub(super) fn stim_injector() -> Item {
+    Item::Upgrade(Upgrade {
+        name: strings::get("item.stim_injector.name"),
+        description: strings::get("item.stim_injector.description"),
+        max_health_boost: Damage::new(5),
+    })
+}
@@ -1,104 +1,163 @@
 //! Functions to create [`Weapon`] items
 
 use crate::{
-    combat::Damage,
+    combat::{Damage, StatusEffect},
     items::{Item, Weapon},
+    strings,
 };
 
 /// Creates a new 'intruders blaster' item
-pub(super) const fn intruders_blaster() -> Item {
+pub(super) fn intruders_blaster() -> Item {
     Item::Weapon(Weapon {
-        name: "Intruders Blaster",
-        description: "An energy weapon kept on the wall in the bridge to use if an enemy boards the ship.",
+        name: strings::get("item.intruders_blaster.name"),
+        description: strings::get("item.intruders_blaster.description"),
 
         straight_damage: Damage::new(5),
         dodge_damage: Damage::new(3),
         speed: 3,
+        inflicts: None,
+        throwable: false,
+        heavy: false,
     })
 }
 
 /// Creates a new 'captain's blaster' item
-pub(super) const fn captains_blaster() -> Item {
+pub(super) fn captains_blaster() -> Item {
     Item::Weapon(Weapon {
-        name: "Captain's Blaster",
-        description: "An energy weapon which the captain keeps by their side through sunshine and rain, through of course they've seen neither in a long time.",
+        name: strings::get("item.captains_blaster.name"),
+        description: strings::get("item.captains_blaster.description"),
 
         straight_damage: Damage::new(7),
         dodge_damage: Damage::new(5),
         speed: 3,
+        inflicts: None,
+        throwable: false,
+        heavy: false,
     })
 }
 
 /// Creates a new 'standard blaster' item
-pub(super) const fn standard_blaster() -> Item {
+pub(super) fn standard_blaster() -> Item {
     Item::Weapon(Weapon {
-        name: "Standard Issue Blaster",
-        description: "The blaster issued to every serving troop. It's slow, but well made.",
+        name: strings::get("item.standard_blaster.name"),
+        description: strings::get("item.standard_blaster.description"),
 
         straight_damage: Damage::new(5),
         dodge_damage: Damage::new(2),
         speed: 4,
+        inflicts: None,
+        throwable: false,
+        heavy: false,
     })
 }
 
 /// Creates a new 'ISPD taser' item
-pub(super) const fn ispd_taser() -> Item {
+pub(super) fn ispd_taser() -> Item {
     Item::Weapon(Weapon {
-        name: "ISPD Taser",
-        description: "A high-powered taser given to every officer in the Interstellar Police Department. It's fast and lethal if you're not careful (or if you are).",
+        name: strings::get("item.ispd_taser.name"),
+        description: strings::get("item.ispd_taser.description"),
 
         straight_damage: Damage::new(10),
         dodge_damage: Damage::new(5),
-        speed: 2
+        speed: 2,
+        inflicts: None,
+        throwable: false,
+        heavy: false,
     })
 }
 
 /// Creates a new 'throwing dart set' item
-pub(super) const fn throwing_dart_set() -> Item {
+pub(super) fn throwing_dart_set() -> Item {
     Item::Weapon(Weapon {
-        name: "Set of Throwing Darts",
-        description: "A set of sharp darts from the darts set in the bunks. They're not too sharp, but you can throw them fast as anything.",
+        name: strings::get("item.throwing_dart_set.name"),
+        description: strings::get("item.throwing_dart_set.description"),
 
         straight_damage: Damage::new(2),
         dodge_damage: Damage::new(2),
-        speed: 1
+        speed: 1,
+        inflicts: None,
+        throwable: true,
+        heavy: false,
     })
 }
 
 /// Creates a new 'shaving razor' item
-pub(super) const fn shaving_razor() -> Item {
+pub(super) fn shaving_razor() -> Item {
     Item::Weapon(Weapon {
-        name: "Shaving Razor",
-        description: "A razor you found in the wash room. It's sharp, but it's not really a weapon.",
+        name: strings::get("item.shaving_razor.name"),
+        description: strings::get("item.shaving_razor.description"),
 
         straight_damage: Damage::new(3),
         dodge_damage: Damage::new(2),
-        speed: 5
+        speed: 5,
+        inflicts: None,
+        throwable: false,
+        heavy: false,
     })
 }
 
 
 /// Creates a new 'wrench' item
-pub(super) const fn wrench() -> Item {
+pub(super) fn wrench() -> Item {
     Item::Weapon(Weapon {
-        name: "Wrench",
-        description: "A wrench from the engine room. It's weighty and you could do some good damage with it.",
+        name: strings::get("item.wrench.name"),
+        description: strings::get("item.wrench.description"),
 
         straight_damage: Damage::new(6),
         dodge_damage: Damage::new(4),
-        speed: 3
+        speed: 3,
+        inflicts: None,
+        throwable: false,
+        heavy: true,
     })
 }
 
 
 /// Creates a new 'eating knife' item
-pub(super) const fn eating_knife() -> Item {
+pub(super) fn eating_knife() -> Item {
     Item::Weapon(Weapon {
-        name: "Eating Knife",
-        description: "A sharp steel knife. Synthetic protein is tough, so it's sharp and sturdy",
+        name: strings::get("item.eating_knife.name"),
+        description: strings::get("item.eating_knife.description"),
 
         straight_damage: Damage::new(5),
         dodge_damage: Damage::new(5),
-        speed: 2    
+        speed: 2,
+        inflicts: None,
+        throwable: false,
+        heavy: false,
+    })
+}
+
+/// Creates a new 'serrated shiv' item
+pub(super) fn serrated_shiv() -> Item {
+    Item::Weapon(Weapon {
+        name: strings::get("item.serrated_shiv.name"),
+        description: strings::get("item.serrated_shiv.description"),
+
+        straight_damage: Damage::new(2),
+        dodge_damage: Damage::new(1),
+        speed: 2,
+        inflicts: Some(StatusEffect {
+            name: strings::get("status.bleeding.name"),
+            damage_per_turn: Damage::new(1),
+            remaining_turns: 3,
+        }),
+        throwable: false,
+        heavy: false,
     })
-}
\ No newline at end of file
+}
+
+/// Creates a new 'hydraulic clamp' item
+pub(super) fn hydraulic_clamp() -> Item {
+    Item::Weapon(Weapon {
+        name: strings::get("item.hydraulic_clamp.name"),
+        description: strings::get("item.hydraulic_clamp.description"),
+
+        straight_damage: Damage::new(8),
+        dodge_damage: Damage::new(3),
+        speed: 4,
+        inflicts: None,
+        throwable: false,
+        heavy: true,
+    })
+}
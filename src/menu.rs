@@ -12,24 +12,32 @@
 //! ];
 //!
 //! let option_list = OptionList::new(&options, "Select an option");
-//! let user_choice = menu.show_option_list(option_list);    
+//! let user_choice = menu.show_option_list(option_list)?;
 //!
-//! let screen = Screen {
-//!     title: "The result",
-//!     content: &format!("You picked '{}'", options[user_choice]),
-//! };
+//! let screen = Screen::new("The result", &format!("You picked '{}'", options[user_choice]));
 //!
-//! menu.show_screen(screen);
+//! menu.show_screen(screen)?;
 //! ```
+//!
+//! If the user asks to quit and confirms it, the methods without a `try_` prefix will return a [`MenuSignal::Quit`],
+//! which should be propagated up to `main` so that the game can exit cleanly.
+//! Any other error is returned as a [`MenuSignal::Error`], which is propagated the same way so that the terminal is restored before it is reported.
 
 pub mod tests;
 
+mod markup;
+
 /// The list of options for a user to choose from
+#[derive(Clone, Copy)]
 pub struct OptionList<'a> {
     /// A list of options for the player to choose from
     pub options: &'a [String],
     /// A command to show the user
     pub prompt: &'a str,
+    /// The index of each option which is shown but can't be chosen, and why
+    pub disabled: &'a [(usize, &'a str)],
+    /// The index of each option which has extra text to show aligned to the right, such as a weapon's damage, and the text
+    pub details: &'a [(usize, String)],
 }
 
 impl<'a> OptionList<'a> {
@@ -40,19 +48,154 @@ impl<'a> OptionList<'a> {
     pub fn new(options: &'a [String], prompt: &'a str) -> Self {
         assert!(!options.is_empty(), "Options should not be empty");
 
-        Self { options, prompt }
+        Self { options, prompt, disabled: &[], details: &[] }
+    }
+
+    /// Takes an [`OptionList`] by value and returns a new one where some options are disabled.
+    /// Disabled options are still shown along with the reason they can't be chosen, but a [`Menu`] never returns their index.
+    ///
+    /// ### Params:
+    /// * `disabled`: the index of each option to disable, and why it can't be chosen
+    ///
+    /// ### Panics
+    /// If every option is disabled, as there would be nothing to choose
+    pub fn with_disabled(mut self, disabled: &'a [(usize, &'a str)]) -> Self {
+        self.disabled = disabled;
+        assert!(
+            (0..self.options.len()).any(|i| self.disabled_reason(i).is_none()),
+            "At least one option should be enabled"
+        );
+        self
+    }
+
+    /// Takes an [`OptionList`] by value and returns a new one where some options have extra text shown alongside them.
+    /// Menus which can line the text up show it against the right hand side, and others show it after the option.
+    ///
+    /// ### Params:
+    /// * `details`: the index of each option with extra text, and the text
+    pub fn with_details(mut self, details: &'a [(usize, String)]) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Gets the extra text to show alongside the option at the given index, or [`None`] if there isn't any
+    pub fn detail(&self, index: usize) -> Option<&'a str> {
+        self.details.iter().find(|(i, _)| *i == index).map(|(_, detail)| detail.as_str())
+    }
+
+    /// Gets the text to show for each option like [`option_texts`][OptionList::option_texts],
+    /// with any [detail][OptionList::with_details] added after it for menus which can't line it up
+    pub fn option_texts_with_details(&self) -> Vec<String> {
+        self.option_texts()
+            .into_iter()
+            .enumerate()
+            .map(|(i, text)| match self.detail(i) {
+                Some(detail) => format!("{text} - {detail}"),
+                None => text,
+            })
+            .collect()
+    }
+
+    /// Gets why the option at the given index can't be chosen, or [`None`] if it can
+    pub fn disabled_reason(&self, index: usize) -> Option<&'a str> {
+        self.disabled.iter().find(|&&(i, _)| i == index).map(|&(_, reason)| reason)
+    }
+
+    /// Gets the text to show for each option, with the reason added after any which are disabled
+    pub fn option_texts(&self) -> Vec<String> {
+        self.options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| match self.disabled_reason(i) {
+                Some(reason) => format!("{option} ({reason})"),
+                None => option.clone(),
+            })
+            .collect()
     }
 }
 
+/// A short line of text which a [`Menu`] keeps visible, set with [`Menu::set_status`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Status {
+    /// The text to show
+    pub text: String,
+    /// A warning to draw the user's attention to the status, if something needs it
+    pub warning: Option<String>,
+}
+
 /// A screen of text that can be shown to the user
 #[derive(Debug, Clone)]
 pub struct Screen<'a> {
+    /// Some ASCII art to show above the title, if any. This is [`None`] unless set with [`Screen::with_banner`].
+    /// This is shown exactly as written rather than being wrapped, so it may not be shown if the terminal is too narrow.
+    pub banner: Option<&'a str>,
     /// The title of the screen
     pub title: &'a str,
-    /// The text to display
+    /// The text to display. This can contain [colour markup][markup] such as `{red}Cook{/}`.
     pub content: &'a str,
 }
 
+impl<'a> Screen<'a> {
+    /// Constructs a new [`Screen`] with no banner
+    ///
+    /// ### Params:
+    /// * `title`: The title of the screen
+    /// * `content`: The text to display, which can contain colour markup
+    pub const fn new(title: &'a str, content: &'a str) -> Self {
+        Self { banner: None, title, content }
+    }
+
+    /// Sets the ASCII art to show above the title
+    pub const fn with_banner(mut self, banner: &'a str) -> Self {
+        self.banner = Some(banner);
+        self
+    }
+}
+
+/// The environment variable which can be used to set the [`ScrollSpeed`] at startup
+const SCROLL_SPEED_VAR: &str = "TEXT_SCROLL_SPEED";
+
+/// How quickly the text of a [`Screen`] is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollSpeed {
+    /// Text is shown a given number of characters per second
+    CharsPerSecond(u64),
+    /// All text is shown immediately
+    Instant,
+}
+
+impl ScrollSpeed {
+    /// Reads the [`ScrollSpeed`] from the `TEXT_SCROLL_SPEED` environment variable.
+    /// This can be either a number of characters per second or `instant`.
+    /// Returns [`None`] if the variable is not set or is not valid.
+    pub fn from_env() -> Option<Self> {
+        let value = std::env::var(SCROLL_SPEED_VAR).ok()?;
+
+        if value.eq_ignore_ascii_case("instant") {
+            return Some(Self::Instant);
+        }
+
+        match value.parse() {
+            Ok(0) | Err(_) => None,
+            Ok(chars_per_second) => Some(Self::CharsPerSecond(chars_per_second)),
+        }
+    }
+}
+
+/// A key which the user pressed, for instance to dismiss a [`Screen`]. Some variants will only occur on specific platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum KeyPress {
+    /// The enter key
+    Enter,
+    /// The escape key
+    Escape,
+    /// A key which produces a character
+    Char(char),
+    /// A key which is not otherwise recognised, such as an unknown escape sequence
+    Other,
+}
+
 /// An error which can occur while displaying a menu. Some variants will only occur on specific platforms.
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -83,14 +226,66 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// A signal from the [`Menu`] which the game loop needs to respond to
+#[derive(Debug)]
+pub enum MenuSignal {
+    /// The user asked to quit and confirmed it, so the game should exit
+    Quit,
+    /// The menu couldn't be shown, so the game can't carry on
+    Error(Error),
+}
+
+/// The screen which explains the controls of the TUI menu. This can be opened with '?' or from the settings menu.
+pub const CONTROLS_SCREEN: Screen = Screen::new(
+    "Controls",
+    "• Up/Down or j/k - move the selection
+• Page Up/Page Down - move the selection by a page
+• Home/End or g/G - jump to the first or last option
+• 0-9 - jump to an option by its number, if numbers are turned on in the settings
+• / - search the options, then Escape to stop searching
+• Enter - choose the selected option
+• Any key - skip the text animation or continue to the next screen
+• s - turn the text animation off or back on for every screen
+• ? - show this help
+• q or Escape twice - quit the game",
+);
+
+/// The screen to show when the user asks to quit
+const QUIT_SCREEN: Screen = Screen::new(
+    "Abandon the loop? y/N",
+    "Press 'y' to quit the game, or any other key to carry on.",
+);
+
+/// Runs a fallible menu operation, asking the user to confirm if they ask to quit.
+/// If they don't confirm, the operation will be run again.
+/// Any other error is returned as a [`MenuSignal::Error`].
+fn confirm_quit<M: Menu, T>(
+    menu: &mut M,
+    mut operation: impl FnMut(&mut M) -> Result<T, Error>,
+) -> Result<T, MenuSignal> {
+    loop {
+        match operation(menu) {
+            Ok(value) => return Ok(value),
+            Err(Error::Quit) => match menu.try_show_screen(QUIT_SCREEN) {
+                // Pressing the quit key again also counts as confirming
+                Ok(KeyPress::Char('y' | 'Y')) | Err(Error::Quit) => return Err(MenuSignal::Quit),
+                Ok(_) => (),
+                Err(e) => return Err(MenuSignal::Error(e)),
+            },
+            Err(e) => return Err(MenuSignal::Error(e)),
+        }
+    }
+}
+
 /// A trait for displaying menus to the user
 pub trait Menu: Sized {
     /// Creates a new instance of the object
     fn new() -> Result<Self, std::io::Error>;
 
-    /// Show a list of options. Will return the index of the option the user selected
-    fn show_option_list(&mut self, list: OptionList) -> usize {
-        self.try_show_option_list(list).unwrap()
+    /// Show a list of options. Will return the index of the option the user selected,
+    /// or a [`MenuSignal`] if the user quit the game or the list couldn't be shown
+    fn show_option_list(&mut self, list: OptionList) -> Result<usize, MenuSignal> {
+        confirm_quit(self, |menu| menu.try_show_option_list(list))
     }
     /// Fallible version of [`show_option_list`][Menu::show_option_list]
     fn try_show_option_list(&mut self, list: OptionList) -> Result<usize, Error>;
@@ -98,8 +293,8 @@ pub trait Menu: Sized {
     /// Show a list of options, with a cancel option. Returns [`None`] if the user selects cancel,
     /// or a [`Some`] value containing the 0-based index of the option the user selected
     /// (for instance if the user selects the first option in the list the return value will be 0)
-    fn show_option_list_cancellable(&mut self, list: OptionList) -> Option<usize> {
-        self.try_show_option_list_cancellable(list).unwrap()
+    fn show_option_list_cancellable(&mut self, list: OptionList) -> Result<Option<usize>, MenuSignal> {
+        confirm_quit(self, |menu| menu.try_show_option_list_cancellable(list))
     }
     /// Fallible version of [`show_option_list_cancellable`][Menu::show_option_list_cancellable]
     fn try_show_option_list_cancellable(
@@ -107,26 +302,218 @@ pub trait Menu: Sized {
         list: OptionList,
     ) -> Result<Option<usize>, Error>;
 
-    /// Show a screen
-    fn show_screen(&mut self, screen: Screen) {
-        self.try_show_screen(screen).unwrap();
+    /// Show a screen. Returns the [`KeyPress`] which the user used to dismiss it,
+    /// or a [`MenuSignal`] if the user quit the game or the screen couldn't be shown
+    fn show_screen(&mut self, screen: Screen) -> Result<KeyPress, MenuSignal> {
+        confirm_quit(self, |menu| menu.try_show_screen(screen.clone()))
     }
     /// Fallible version of [`try_show_screen`][Menu::show_screen]
-    fn try_show_screen(&mut self, screen: Screen) -> Result<(), Error>;
+    fn try_show_screen(&mut self, screen: Screen) -> Result<KeyPress, Error>;
+
+    /// Gets how quickly the text of screens is shown.
+    /// Menus which don't animate text show it [instantly][ScrollSpeed::Instant].
+    fn scroll_speed(&self) -> ScrollSpeed {
+        ScrollSpeed::Instant
+    }
+    /// Sets how quickly the text of screens is shown. Menus which don't animate text will ignore this.
+    fn set_scroll_speed(&mut self, _speed: ScrollSpeed) {}
+
+    /// Gets how many characters of a [`Screen`]'s content fit on one line before it is wrapped,
+    /// or [`None`] if the menu doesn't wrap text.
+    fn content_width(&self) -> Option<usize> {
+        None
+    }
+
+    /// Sets a short line of text which is always visible, such as the time left, or removes it if [`None`].
+    /// Menus without anywhere to keep it visible may only show its [warning][Status::warning], or ignore it.
+    fn set_status(&mut self, _status: Option<Status>) {}
+
+    /// Marks a point which the run can be restored to, such as the end of a turn.
+    /// Menus which [save the run][crate::autosave::AutosaveMenu] save it here, and other menus ignore this.
+    fn checkpoint(&mut self) {}
+
+    /// Gets whether the menu is skipping through choices which were already made, such as when [catching up to a save][crate::autosave::AutosaveMenu].
+    /// [`Effects`][crate::effects::Effects] shouldn't be played while this is true, as what caused them has already happened.
+    fn catching_up(&self) -> bool {
+        false
+    }
+
+    /// Lets the player know they have just been hurt, e.g. by flashing the border on the next frame.
+    /// Menus without any way to do this ignore it.
+    fn flash_damage(&mut self) {}
+
+    /// Gets whether the terminal bell rings when the player is [hurt][Menu::flash_damage].
+    /// This is off by default, and menus without a bell never ring it.
+    fn damage_bell(&self) -> bool {
+        false
+    }
+    /// Sets whether the terminal bell rings when the player is hurt. Menus without a bell will ignore this.
+    fn set_damage_bell(&mut self, _bell: bool) {}
+
+    /// Gets whether the player's weapons are listed in battle from most to least [effective][crate::items::Weapon::effective_damage],
+    /// rather than in the order they were picked up. This is off by default.
+    fn best_weapon_first(&self) -> bool {
+        false
+    }
+    /// Sets whether the player's weapons are listed in battle from most to least effective. Menus which can't store settings will ignore this.
+    fn set_best_weapon_first(&mut self, _best_first: bool) {}
+
+    /// Gets whether options in lists are shown with their numbers, which can be typed to pick them.
+    /// This is off by default.
+    fn numbered_options(&self) -> bool {
+        false
+    }
+    /// Sets whether options in lists are shown with their numbers. Menus which always or never number them will ignore this.
+    fn set_numbered_options(&mut self, _numbered: bool) {}
 }
 
 /// Implementation of the [`Menu`] trait for unix platforms using the [`termion`] library
 //#[cfg(all(unix, not(debug_assertions)))]
 mod unix;
 #[cfg(all(unix, not(debug_assertions)))]
-use unix::Tui;
+use self::UnixMenu as Tui;
 
-/// Fallback implementation of the [`Menu`] trait for platforms which don't support ANSI escape codes
-#[cfg(any(not(unix), debug_assertions))]
+/// Fallback implementation of the [`Menu`] trait for platforms which don't support ANSI escape codes.
+/// On unix, this is also used in [accessibility mode][accessible_from_env].
 mod fallback;
 #[cfg(any(not(unix), debug_assertions))]
 use fallback::Tui;
 
+/// The environment variable which turns on accessibility mode
+#[cfg(all(unix, not(debug_assertions)))]
+const ACCESSIBLE_VAR: &str = "TUI_ACCESSIBLE";
+
+/// Reads whether accessibility mode is turned on by the `TUI_ACCESSIBLE` environment variable being set to `1` or `true`.
+///
+/// In accessibility mode, the plain [fallback menu][fallback::Tui] is used even though the full-terminal menu is supported,
+/// which is easier to use with a screen reader. This means that:
+/// * Text is printed all at once, without the typewriter animation
+/// * There is no decorative border, and no colours
+/// * The screen isn't cleared and redrawn, so earlier text stays in the terminal's history
+/// * Lists are printed as numbered options, and picked by typing a number rather than moving a selection
+#[cfg(all(unix, not(debug_assertions)))]
+fn accessible_from_env() -> bool {
+    std::env::var(ACCESSIBLE_VAR).is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+}
+
+/// The [`Menu`] used on unix, which is the full-terminal [`unix::Tui`] unless [accessibility mode][accessible_from_env] is on
+#[cfg(all(unix, not(debug_assertions)))]
+enum UnixMenu {
+    /// The full-terminal menu
+    FullTerminal(unix::Tui),
+    /// The plain menu used in accessibility mode
+    Plain(fallback::Tui),
+}
+
+#[cfg(all(unix, not(debug_assertions)))]
+impl Menu for UnixMenu {
+    fn new() -> Result<Self, std::io::Error> {
+        if accessible_from_env() {
+            Ok(Self::Plain(fallback::Tui::new()?))
+        } else {
+            Ok(Self::FullTerminal(unix::Tui::new()?))
+        }
+    }
+
+    fn try_show_option_list(&mut self, list: OptionList) -> Result<usize, Error> {
+        match self {
+            Self::FullTerminal(menu) => menu.try_show_option_list(list),
+            Self::Plain(menu) => menu.try_show_option_list(list),
+        }
+    }
+
+    fn try_show_option_list_cancellable(&mut self, list: OptionList) -> Result<Option<usize>, Error> {
+        match self {
+            Self::FullTerminal(menu) => menu.try_show_option_list_cancellable(list),
+            Self::Plain(menu) => menu.try_show_option_list_cancellable(list),
+        }
+    }
+
+    fn try_show_screen(&mut self, screen: Screen) -> Result<KeyPress, Error> {
+        match self {
+            Self::FullTerminal(menu) => menu.try_show_screen(screen),
+            Self::Plain(menu) => menu.try_show_screen(screen),
+        }
+    }
+
+    fn scroll_speed(&self) -> ScrollSpeed {
+        match self {
+            Self::FullTerminal(menu) => menu.scroll_speed(),
+            Self::Plain(menu) => menu.scroll_speed(),
+        }
+    }
+
+    fn set_scroll_speed(&mut self, speed: ScrollSpeed) {
+        match self {
+            Self::FullTerminal(menu) => menu.set_scroll_speed(speed),
+            Self::Plain(menu) => menu.set_scroll_speed(speed),
+        }
+    }
+
+    fn content_width(&self) -> Option<usize> {
+        match self {
+            Self::FullTerminal(menu) => menu.content_width(),
+            Self::Plain(menu) => menu.content_width(),
+        }
+    }
+
+    fn set_status(&mut self, status: Option<Status>) {
+        match self {
+            Self::FullTerminal(menu) => menu.set_status(status),
+            Self::Plain(menu) => menu.set_status(status),
+        }
+    }
+
+    fn flash_damage(&mut self) {
+        match self {
+            Self::FullTerminal(menu) => menu.flash_damage(),
+            Self::Plain(menu) => menu.flash_damage(),
+        }
+    }
+
+    fn damage_bell(&self) -> bool {
+        match self {
+            Self::FullTerminal(menu) => menu.damage_bell(),
+            Self::Plain(menu) => menu.damage_bell(),
+        }
+    }
+
+    fn set_damage_bell(&mut self, bell: bool) {
+        match self {
+            Self::FullTerminal(menu) => menu.set_damage_bell(bell),
+            Self::Plain(menu) => menu.set_damage_bell(bell),
+        }
+    }
+
+    fn best_weapon_first(&self) -> bool {
+        match self {
+            Self::FullTerminal(menu) => menu.best_weapon_first(),
+            Self::Plain(menu) => menu.best_weapon_first(),
+        }
+    }
+
+    fn set_best_weapon_first(&mut self, best_first: bool) {
+        match self {
+            Self::FullTerminal(menu) => menu.set_best_weapon_first(best_first),
+            Self::Plain(menu) => menu.set_best_weapon_first(best_first),
+        }
+    }
+
+    fn numbered_options(&self) -> bool {
+        match self {
+            Self::FullTerminal(menu) => menu.numbered_options(),
+            Self::Plain(menu) => menu.numbered_options(),
+        }
+    }
+
+    fn set_numbered_options(&mut self, numbered: bool) {
+        match self {
+            Self::FullTerminal(menu) => menu.set_numbered_options(numbered),
+            Self::Plain(menu) => menu.set_numbered_options(numbered),
+        }
+    }
+}
+
 /// Initialises and returns a type which implements [`Menu`] for the current platform
 pub fn init() -> Result<impl Menu, std::io::Error> {
     Tui::new()
@@ -1,14 +1,34 @@
-use std::io::Write;
+use std::io::{BufRead, Write};
 use std::{io::StdoutLock, iter};
 
-use super::{Error, Menu, OptionList};
+use super::markup::parse_markup;
+use super::{Error, KeyPress, Menu, OptionList, Status};
+
+mod tests;
+
+/// The help text which is printed when the user enters '?'
+const HELP_TEXT: &str = "Controls:
+• Type the number of an option and press Enter to choose it
+• Press Enter to continue to the next screen
+• ? - show this help
+• q - quit the game
+";
 
 /// A struct which implements [`Menu`] for any platform
-pub struct Tui;
+pub struct Tui {
+    /// The [warning][Status::warning] of the current status, which is shown before every prompt.
+    /// The rest of the status isn't shown, as there's nowhere to keep it visible.
+    warning: Option<String>,
+    /// Whether to list the player's weapons from [most to least effective][Menu::best_weapon_first] in battle
+    best_weapon_first: bool,
+}
 
 impl Menu for Tui {
     fn new() -> Result<Self, std::io::Error> {
-        Ok(Self)
+        Ok(Self {
+            warning: None,
+            best_weapon_first: false,
+        })
     }
 
     fn try_show_option_list_cancellable(
@@ -21,17 +41,17 @@ impl Menu for Tui {
         let max_width = num_options.to_string().len();
 
         let options_text: String = list
-            .options
+            .option_texts_with_details()
             .iter() // Get the strings as an iterator
             .chain(iter::once(&"Cancel".to_string())) // Add the quit message
             .enumerate() // Get the indices of the items
             .map(|(i, s)| format!("{: >max_width$}) {}\n", i + 1, s)) // Convert each item to a string with numbers right aligned
             .collect();
 
-        writeln!(stdout, "{}", list.prompt)?;
+        writeln!(stdout, "{}", prompt_text(list.prompt, self.warning.as_deref()))?;
         writeln!(stdout, "{options_text}")?;
 
-        let choice = number_input(num_options, &mut stdout)?;
+        let choice = enabled_number_input(&list, num_options, &mut stdout)?;
 
         writeln!(stdout)?;
 
@@ -48,16 +68,16 @@ impl Menu for Tui {
         let max_width = num_options.to_string().len();
 
         let options_text: String = list
-            .options
+            .option_texts_with_details()
             .iter() // Get the strings as an iterator
             .enumerate() // Get the indices of the items
             .map(|(i, s)| format!("{: >max_width$}) {}\n", i + 1, s)) // Convert each item to a string with numbers right aligned
             .collect();
 
-        writeln!(stdout, "{}", list.prompt)?;
+        writeln!(stdout, "{}", prompt_text(list.prompt, self.warning.as_deref()))?;
         writeln!(stdout, "{options_text}")?;
 
-        let choice = number_input(num_options, &mut stdout)?;
+        let choice = enabled_number_input(&list, num_options, &mut stdout)?;
 
         writeln!(stdout)?;
 
@@ -65,27 +85,95 @@ impl Menu for Tui {
         Ok(choice - 1)
     }
 
-    fn try_show_screen(&mut self, screen: super::Screen) -> Result<(), Error> {
+    fn try_show_screen(&mut self, screen: super::Screen) -> Result<KeyPress, Error> {
         let mut stdout = std::io::stdout().lock();
 
+        if let Some(banner) = screen.banner {
+            writeln!(stdout, "{banner}")?;
+        }
         writeln!(stdout, "{}", screen.title)?;
-        writeln!(stdout, "{}", screen.content)?;
-        writeln!(stdout)?;
+        // Colours aren't supported, so just show the text without the markup
+        let (content, _) = parse_markup(screen.content);
+        writeln!(stdout, "{content}")?;
+
+        loop {
+            write!(stdout, "Press enter to continue ")?;
+            stdout.flush()?;
+
+            let line = read_input_line(&mut std::io::stdin().lock())?;
+
+            writeln!(stdout)?;
+
+            match key_press_from_line(&line) {
+                KeyPress::Char('q') => return Err(Error::Quit),
+                KeyPress::Char('?') => writeln!(stdout, "{HELP_TEXT}")?,
+                key => return Ok(key),
+            }
+        }
+    }
+
+    fn set_status(&mut self, status: Option<Status>) {
+        self.warning = status.and_then(|status| status.warning);
+    }
+
+    fn flash_damage(&mut self) {
+        // There's nothing to flash, so the hit is printed on its own line before the turn's result.
+        // If it can't be printed, the result still says how much damage was taken.
+        let _ = writeln!(std::io::stdout().lock(), "*thud*");
+    }
+
+    fn best_weapon_first(&self) -> bool {
+        self.best_weapon_first
+    }
+
+    fn set_best_weapon_first(&mut self, best_first: bool) {
+        self.best_weapon_first = best_first;
+    }
 
-        Ok(())
+    // Options are picked by typing their number, so they're always numbered
+    fn numbered_options(&self) -> bool {
+        true
+    }
+}
+
+/// Gets the text to show for a prompt, with the status's warning in brackets before it if there is one
+fn prompt_text(prompt: &str, warning: Option<&str>) -> String {
+    match warning {
+        Some(warning) => format!("({warning}) {prompt}"),
+        None => prompt.to_string(),
+    }
+}
+
+/// Reads a line of input, including the trailing newline if there is one.
+/// Returns [`Error::Quit`] if the input has ended (e.g. if the user pressed Ctrl-D or piped-in input ran out),
+/// as otherwise the game would keep asking for input which will never come.
+fn read_input_line(input: &mut impl BufRead) -> Result<String, Error> {
+    let mut buf = String::new();
+
+    match input.read_line(&mut buf)? {
+        0 => Err(Error::Quit),
+        _ => Ok(buf),
     }
 }
 
 /// Gets an integer input from the user from 1 to a maximum value (inclusive). Will get the user to retype their input until a valid value is entered.
+/// Returns [`Error::Quit`] if the user enters 'q' or the input ends. Entering '?' prints the [`HELP_TEXT`].
 fn number_input(max: usize, stdout: &mut StdoutLock) -> Result<usize, Error> {
     loop {
         write!(stdout, "Enter your selection from 1 to {max}: ")?;
         stdout.flush()?;
 
-        let mut buf = String::new();
-        std::io::stdin().read_line(&mut buf)?;
+        let line = read_input_line(&mut std::io::stdin().lock())?;
+
+        let selection = line.trim_end();
+        if selection == "q" {
+            return Err(Error::Quit);
+        }
+        if selection == "?" {
+            writeln!(stdout, "{HELP_TEXT}")?;
+            continue;
+        }
 
-        let selection = buf.trim_end();
         match selection.parse() {
             Ok(u) => match u {
                 0 => writeln!(stdout, "Value can't be 0")?,
@@ -96,3 +184,25 @@ fn number_input(max: usize, stdout: &mut StdoutLock) -> Result<usize, Error> {
         }
     }
 }
+
+/// Gets a number from the user like [`number_input`], but asks again if the number is for one of the list's [disabled][OptionList::with_disabled] options.
+/// Like [`number_input`], the number is 1-based.
+fn enabled_number_input(list: &OptionList, max: usize, stdout: &mut StdoutLock) -> Result<usize, Error> {
+    loop {
+        let choice = number_input(max, stdout)?;
+
+        match list.disabled_reason(choice - 1) {
+            Some(reason) => writeln!(stdout, "You can't choose that - {reason}")?,
+            None => return Ok(choice),
+        }
+    }
+}
+
+/// Gets the [`KeyPress`] represented by a line of input. Only the first character of the line is used.
+fn key_press_from_line(line: &str) -> KeyPress {
+    match line.trim_end_matches(['\r', '\n']).chars().next() {
+        None => KeyPress::Enter,
+        Some('\x1b') => KeyPress::Escape,
+        Some(c) => KeyPress::Char(c),
+    }
+}
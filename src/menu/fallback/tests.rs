@@ -0,0 +1,33 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that [`read_input_line`] returns lines until the input ends, and then returns [`Error::Quit`]
+#[test]
+fn test_read_input_line_eof() {
+    let mut input = "1\nq\n".as_bytes();
+
+    assert_eq!(read_input_line(&mut input).unwrap(), "1\n");
+    assert_eq!(read_input_line(&mut input).unwrap(), "q\n");
+    assert!(matches!(read_input_line(&mut input), Err(Error::Quit)));
+    assert!(matches!(read_input_line(&mut input), Err(Error::Quit)));
+}
+
+/// Tests that [`prompt_text`] only adds the warning when there is one
+#[test]
+fn test_prompt_text() {
+    assert_eq!(prompt_text("0:40 - What do you do?", None), "0:40 - What do you do?");
+    assert_eq!(
+        prompt_text("0:40 - What do you do?", Some("Time is running out!")),
+        "(Time is running out!) 0:40 - What do you do?"
+    );
+}
+
+/// Tests that [`key_press_from_line`] only looks at the first character of a line
+#[test]
+fn test_key_press_from_line() {
+    assert_eq!(key_press_from_line("\n"), KeyPress::Enter);
+    assert_eq!(key_press_from_line("\r\n"), KeyPress::Enter);
+    assert_eq!(key_press_from_line("q\n"), KeyPress::Char('q'));
+    assert_eq!(key_press_from_line("\x1b[A\n"), KeyPress::Escape);
+}
@@ -0,0 +1,128 @@
+//! Parsing of the colour markup which can be used in the content of a [`Screen`][super::Screen].
+//!
+//! A colour tag such as `{red}` colours all the text after it, until the next colour tag or a `{/}` tag which resets the colour.
+//! For instance, `"The {red}Cook{/} attacks"` shows the word 'Cook' in red.
+//! Anything in braces which isn't a recognised tag is shown as written.
+
+mod tests;
+
+/// A colour which text can be shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Colour {
+    /// Red, written as `{red}`
+    Red,
+    /// Green, written as `{green}`
+    Green,
+    /// Yellow, written as `{yellow}`
+    Yellow,
+    /// Blue, written as `{blue}`
+    Blue,
+    /// Magenta, written as `{magenta}`
+    Magenta,
+    /// Cyan, written as `{cyan}`
+    Cyan,
+}
+
+/// A tag which can be written in braces in a [`Screen`][super::Screen]'s content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tag {
+    /// Start colouring text in the given [`Colour`]
+    Colour(Colour),
+    /// Go back to the default colour, written as `{/}`
+    Reset,
+}
+
+impl Tag {
+    /// Gets the [`Tag`] with the given name, which is the text between the braces.
+    /// Returns [`None`] if there is no tag with that name.
+    fn from_name(name: &str) -> Option<Self> {
+        let tag = match name {
+            "red" => Self::Colour(Colour::Red),
+            "green" => Self::Colour(Colour::Green),
+            "yellow" => Self::Colour(Colour::Yellow),
+            "blue" => Self::Colour(Colour::Blue),
+            "magenta" => Self::Colour(Colour::Magenta),
+            "cyan" => Self::Colour(Colour::Cyan),
+            "/" => Self::Reset,
+            _ => return None,
+        };
+
+        Some(tag)
+    }
+}
+
+/// A section of text which should be shown in a [`Colour`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ColourSpan {
+    /// The byte index into the plain text where the span starts
+    pub(super) start: usize,
+    /// The byte index into the plain text just past the end of the span
+    pub(super) end: usize,
+    /// The colour to show the text in
+    pub(super) colour: Colour,
+}
+
+/// Removes the markup from some text.
+///
+/// ### Returns:
+/// The text with all tags removed, and the [`ColourSpan`]s of the text.
+/// The spans are in order, don't overlap, and index into the returned text rather than the original.
+pub(super) fn parse_markup(text: &str) -> (String, Vec<ColourSpan>) {
+    let mut plain = String::with_capacity(text.len());
+    let mut spans = Vec::new();
+
+    // The colour of the current span and where in the plain text it started, if there is one
+    let mut current_span: Option<(Colour, usize)> = None;
+    // The text which hasn't been parsed yet
+    let mut rest = text;
+
+    while let Some(brace_index) = rest.find('{') {
+        plain.push_str(&rest[..brace_index]);
+        rest = &rest[brace_index..];
+
+        // Get the tag starting at the brace, if there is a valid one
+        let tag = rest
+            .find('}')
+            .and_then(|end| Some((Tag::from_name(&rest[1..end])?, end)));
+
+        let Some((tag, tag_end)) = tag else {
+            // Not a tag, so show the brace as written
+            plain.push('{');
+            rest = &rest[1..];
+            continue;
+        };
+
+        // Any tag ends the current span
+        if let Some((colour, start)) = current_span.take() {
+            if start != plain.len() {
+                spans.push(ColourSpan {
+                    start,
+                    end: plain.len(),
+                    colour,
+                });
+            }
+        }
+
+        if let Tag::Colour(colour) = tag {
+            current_span = Some((colour, plain.len()));
+        }
+
+        rest = &rest[tag_end + 1..];
+    }
+
+    plain.push_str(rest);
+
+    // If the text ends without resetting the colour, the span goes until the end
+    if let Some((colour, start)) = current_span {
+        if start != plain.len() {
+            spans.push(ColourSpan {
+                start,
+                end: plain.len(),
+                colour,
+            });
+        }
+    }
+
+    (plain, spans)
+}
+
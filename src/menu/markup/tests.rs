@@ -0,0 +1,42 @@
+#![cfg(test)]
+
+use super::*;
+
+/// Tests that tags are removed from the text and turned into spans
+#[test]
+fn test_parse_markup() {
+    let (plain, spans) = parse_markup("The {red}Cook{/} hit you for {yellow}3{/} damage");
+
+    assert_eq!(plain, "The Cook hit you for 3 damage");
+    assert_eq!(
+        spans,
+        [
+            ColourSpan { start: 4, end: 8, colour: Colour::Red },
+            ColourSpan { start: 21, end: 22, colour: Colour::Yellow },
+        ]
+    );
+}
+
+/// Tests that a colour lasts until the next colour tag or the end of the text if it isn't reset
+#[test]
+fn test_unclosed_tags() {
+    let (plain, spans) = parse_markup("{red}a{green}b");
+
+    assert_eq!(plain, "ab");
+    assert_eq!(
+        spans,
+        [
+            ColourSpan { start: 0, end: 1, colour: Colour::Red },
+            ColourSpan { start: 1, end: 2, colour: Colour::Green },
+        ]
+    );
+}
+
+/// Tests that braces which aren't tags are left as they are
+#[test]
+fn test_unrecognised_tags() {
+    let (plain, spans) = parse_markup("{not a tag} {red {/");
+
+    assert_eq!(plain, "{not a tag} {red {/");
+    assert!(spans.is_empty());
+}
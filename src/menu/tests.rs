@@ -4,11 +4,144 @@ use std::collections::VecDeque;
 
 use super::*;
 
+/// A choice which a [`MockMenu`] will make when an option list is shown
+#[derive(Debug, Clone)]
+enum ScriptedChoice {
+    /// Pick the option at an index, or cancel if [`None`]
+    Index(Option<usize>),
+    /// Pick the first option which starts with the given text
+    Text(String),
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct MockMenu {
-    last_screen: Option<(String, String)>,
+    screens: Vec<(String, String)>,
     last_list: Option<(String, Vec<String>)>,
-    numbers_to_produce: VecDeque<Option<usize>>,
+    choices_to_make: VecDeque<ScriptedChoice>,
+    keys_to_produce: VecDeque<KeyPress>,
+    status: Option<Status>,
+    flashes: usize,
+    damage_bell: bool,
+    best_weapon_first: bool,
+    numbered_options: bool,
+}
+
+impl MockMenu {
+    /// Gets a [`MockMenuBuilder`] to script the choices a [`MockMenu`] will make
+    pub fn builder() -> MockMenuBuilder {
+        MockMenuBuilder::default()
+    }
+
+    /// Adds a choice to be returned when an option list is shown.
+    /// [`None`] represents the user cancelling, and should only be used for cancellable lists.
+    ///
+    /// ### Panics
+    /// If an option list is shown when no choices have been added
+    pub fn push_choice(&mut self, choice: Option<usize>) {
+        self.choices_to_make.push_back(ScriptedChoice::Index(choice));
+    }
+
+    /// Adds a [`KeyPress`] to be returned when a screen is shown.
+    /// If no keys have been added, screens will be dismissed with [`KeyPress::Enter`]
+    pub fn push_key(&mut self, key: KeyPress) {
+        self.keys_to_produce.push_back(key);
+    }
+
+    /// Gets the prompt and options of the last option list which was shown, if any
+    pub fn last_list(&self) -> Option<&(String, Vec<String>)> {
+        self.last_list.as_ref()
+    }
+
+    /// Gets the title and content of the last screen which was shown, if any
+    pub fn last_screen(&self) -> Option<&(String, String)> {
+        self.screens.last()
+    }
+
+    /// Gets the title and content of every screen which has been shown, in order
+    pub fn screens(&self) -> &[(String, String)] {
+        &self.screens
+    }
+
+    /// Gets the last [status][Menu::set_status] which was set, if any
+    pub fn status(&self) -> Option<&Status> {
+        self.status.as_ref()
+    }
+
+    /// Gets how many times the player has been [hurt][Menu::flash_damage]
+    pub fn flashes(&self) -> usize {
+        self.flashes
+    }
+
+    /// Gets how many scripted choices haven't been made yet
+    pub fn remaining_choices(&self) -> usize {
+        self.choices_to_make.len()
+    }
+
+    /// Asserts that the title of the last screen which was shown contains the given text
+    ///
+    /// ### Panics
+    /// If no screen has been shown, or the last screen's title doesn't contain the text
+    pub fn assert_last_screen_title_contains(&self, text: &str) {
+        let (title, _) = self.last_screen().expect("No screen has been shown");
+        assert!(title.contains(text), "Last screen title {title:?} doesn't contain {text:?}");
+    }
+
+    /// Makes the next scripted choice for an option list.
+    /// The [last list][MockMenu::last_list] records the options as they are shown, including why any disabled options can't be chosen.
+    ///
+    /// ### Panics
+    /// * If there are no scripted choices left
+    /// * If the choice is an option's text, but no enabled option starts with it
+    /// * If the choice is the index of a disabled option
+    fn make_choice(&mut self, list: OptionList) -> Option<usize> {
+        self.last_list = Some((list.prompt.to_string(), list.option_texts()));
+
+        let choice = self.choices_to_make.pop_front().unwrap_or_else(|| {
+            panic!("Ran out of scripted choices at prompt {:?} - options were {:?}", list.prompt, list.options)
+        });
+
+        let choice = match choice {
+            ScriptedChoice::Index(choice) => choice,
+            ScriptedChoice::Text(text) => {
+                let choice = (0..list.options.len())
+                    .find(|&i| list.disabled_reason(i).is_none() && list.options[i].starts_with(&text));
+                Some(choice.unwrap_or_else(|| {
+                    panic!("No enabled option {text:?} for prompt {:?} - options were {:?}", list.prompt, list.option_texts())
+                }))
+            }
+        };
+
+        if let Some(reason) = choice.and_then(|choice| list.disabled_reason(choice)) {
+            panic!("Scripted a disabled option at prompt {:?}, which can't be chosen - {reason}", list.prompt);
+        }
+        choice
+    }
+}
+
+/// Builds a [`MockMenu`] with a script of choices to make, for tests which drive the game through many menus.
+/// ```
+/// let mut menu = MockMenu::builder()
+///     .choose_option("Normal")
+///     .choose_option("Go to the Upper Corridor")
+///     .build();
+/// ```
+#[derive(Debug, Default)]
+pub struct MockMenuBuilder {
+    menu: MockMenu,
+}
+
+impl MockMenuBuilder {
+    /// Picks the first option in the next option list which starts with the given text.
+    /// Choosing by text keeps a script working when options are added to or removed from a list.
+    pub fn choose_option(mut self, text: &str) -> Self {
+        self.menu.choices_to_make.push_back(ScriptedChoice::Text(text.to_string()));
+        self
+    }
+
+    /// Finishes the script and returns the [`MockMenu`]
+    pub fn build(self) -> MockMenu {
+        self.menu
+    }
 }
 
 impl Menu for MockMenu {
@@ -17,18 +150,146 @@ impl Menu for MockMenu {
     }
 
     fn try_show_option_list(&mut self, list: OptionList) -> Result<usize, Error> {
-        self.last_list = Some((list.prompt.to_string(), list.options.to_vec()));
-        Ok(self.numbers_to_produce.pop_front().unwrap().unwrap())
+        let prompt = list.prompt.to_string();
+        let choice = self.make_choice(list);
+        Ok(choice.unwrap_or_else(|| panic!("Scripted a cancel at prompt {prompt:?}, which can't be cancelled")))
     }
 
     fn try_show_option_list_cancellable(&mut self, list: OptionList) -> Result<Option<usize>, Error> {
-        self.last_list = Some((list.prompt.to_string(), list.options.to_vec()));
-        Ok(self.numbers_to_produce.pop_front().unwrap())
+        Ok(self.make_choice(list))
+    }
+
+    fn try_show_screen(&mut self, screen: Screen) -> Result<KeyPress, Error> {
+        self.screens.push((screen.title.to_string(), screen.content.to_string()));
+        Ok(self.keys_to_produce.pop_front().unwrap_or(KeyPress::Enter))
+    }
+
+    fn set_status(&mut self, status: Option<Status>) {
+        self.status = status;
     }
 
-    fn try_show_screen(&mut self, screen: Screen) -> Result<(), Error> {
-        self.last_screen = Some((screen.title.to_string(), screen.content.to_string()));
-        Ok(())
+    fn flash_damage(&mut self) {
+        self.flashes += 1;
     }
+
+    fn damage_bell(&self) -> bool {
+        self.damage_bell
+    }
+
+    fn set_damage_bell(&mut self, bell: bool) {
+        self.damage_bell = bell;
+    }
+
+    fn best_weapon_first(&self) -> bool {
+        self.best_weapon_first
+    }
+
+    fn set_best_weapon_first(&mut self, best_first: bool) {
+        self.best_weapon_first = best_first;
+    }
+
+    fn numbered_options(&self) -> bool {
+        self.numbered_options
+    }
+
+    fn set_numbered_options(&mut self, numbered: bool) {
+        self.numbered_options = numbered;
+    }
+}
+
+/// Tests that disabled options are shown with their reasons, and that scripted choices skip them
+#[test]
+fn test_disabled_options() {
+    let options = ["Take off".to_string(), "Take a look around".to_string()];
+    let disabled = [(0, "you need the maps")];
+    let list = OptionList::new(&options, "What do you do?").with_disabled(&disabled);
+
+    assert_eq!(list.disabled_reason(0), Some("you need the maps"));
+    assert_eq!(list.disabled_reason(1), None);
+    assert_eq!(list.option_texts(), ["Take off (you need the maps)", "Take a look around"]);
+
+    // Choosing by text picks the first enabled option which matches
+    let mut menu = MockMenu::builder().choose_option("Take").build();
+    assert_eq!(menu.show_option_list(list).unwrap(), 1);
+    assert_eq!(menu.last_list().unwrap().1[0], "Take off (you need the maps)");
+}
+
+/// Tests that details are only added to the options they're for, after any reason the option is disabled
+#[test]
+fn test_option_details() {
+    let options = ["Attack with your Knife".to_string(), "Attack with your Wrench".to_string(), "Dodge".to_string()];
+    let disabled = [(1, "it's too heavy")];
+    let details = [(0, "4 dmg".to_string()), (1, "6 dmg".to_string())];
+    let list = OptionList::new(&options, "What do you do?").with_disabled(&disabled).with_details(&details);
+
+    assert_eq!(list.detail(0), Some("4 dmg"));
+    assert_eq!(list.detail(2), None);
+    assert_eq!(list.option_texts(), ["Attack with your Knife", "Attack with your Wrench (it's too heavy)", "Dodge"]);
+    assert_eq!(
+        list.option_texts_with_details(),
+        ["Attack with your Knife - 4 dmg", "Attack with your Wrench (it's too heavy) - 6 dmg", "Dodge"]
+    );
 }
 
+/// Tests that an option list can't have every option disabled
+#[test]
+#[should_panic = "At least one option should be enabled"]
+fn test_all_options_disabled() {
+    let options = ["Take off".to_string()];
+    let _ = OptionList::new(&options, "What do you do?").with_disabled(&[(0, "you need the maps")]);
+}
+
+/// A [`Menu`] which can't show anything, as if the terminal had gone away
+#[derive(Debug)]
+struct BrokenMenu;
+
+impl Menu for BrokenMenu {
+    fn new() -> Result<Self, std::io::Error> {
+        Ok(Self)
+    }
+
+    fn try_show_option_list(&mut self, _list: OptionList) -> Result<usize, Error> {
+        Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe).into())
+    }
+
+    fn try_show_option_list_cancellable(&mut self, _list: OptionList) -> Result<Option<usize>, Error> {
+        Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe).into())
+    }
+
+    fn try_show_screen(&mut self, _screen: Screen) -> Result<KeyPress, Error> {
+        Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe).into())
+    }
+}
+
+/// Tests that an error showing a menu is passed back as a [`MenuSignal::Error`] rather than panicking
+#[test]
+fn test_menu_error_signal() {
+    let mut menu = BrokenMenu;
+    let options = ["Take off".to_string()];
+
+    assert!(matches!(menu.show_screen(CONTROLS_SCREEN), Err(MenuSignal::Error(Error::Io(_)))));
+    assert!(matches!(
+        menu.show_option_list(OptionList::new(&options, "What do you do?")),
+        Err(MenuSignal::Error(Error::Io(_)))
+    ));
+}
+
+/// Tests that asking to quit only quits if the player presses 'y', and otherwise carries on with what they were doing
+#[test]
+fn test_confirm_quit() {
+    let mut menu = MockMenu::default();
+    menu.push_key(KeyPress::Char('n'));
+
+    // The first attempt asks to quit, and the second succeeds once the player decides not to
+    let mut attempts = 0;
+    let result = confirm_quit(&mut menu, |_| {
+        attempts += 1;
+        if attempts == 1 { Err(Error::Quit) } else { Ok(attempts) }
+    });
+    assert!(matches!(result, Ok(2)));
+    menu.assert_last_screen_title_contains("Abandon the loop?");
+
+    menu.push_key(KeyPress::Char('y'));
+    let result: Result<(), _> = confirm_quit(&mut menu, |_| Err(Error::Quit));
+    assert!(matches!(result, Err(MenuSignal::Quit)));
+}
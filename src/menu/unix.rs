@@ -1,9 +1,13 @@
+use std::collections::VecDeque;
 use std::io::{BufWriter, Read, StdinLock, Stdout, Write};
 use std::os::fd::AsRawFd;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
-use nix::libc::timeval;
+use nix::errno::Errno;
+use nix::libc::{c_int, timeval};
 use nix::sys::select::{select, FdSet};
+use nix::sys::signal::{sigaction, SaFlags, SigAction, SigHandler, SigSet, Signal};
 use nix::sys::time::TimeVal;
 
 use termion::raw::{IntoRawMode, RawTerminal};
@@ -15,29 +19,60 @@ use termion::clear;
 
 use unicode_segmentation::UnicodeSegmentation;
 
-use super::{Error, Menu, OptionList};
+use super::markup::{parse_markup, ColourSpan};
+use super::{Error, KeyPress, Menu, OptionList, Screen, ScrollSpeed, Status, CONTROLS_SCREEN};
 
 mod consts;
+mod demo;
+mod frame_rate;
+mod input;
 mod rendering;
 mod text_layout;
+mod theme;
 
 use consts::*;
+use demo::Demo;
+use frame_rate::FrameRate;
+use input::{decode_input, InputBuffer, Key, OptionNumberInput};
 use text_layout::*;
+use theme::Theme;
 
 
 mod tests;
 
-/// The ANSI escape to move the cursor 1 line up
-const ANSI_UP: &str = "\x1b[A";
-/// The ANSI escape to move the cursor 1 line down
-const ANSI_DOWN: &str = "\x1b[B";
+/// Set by [`handle_resize`] when the terminal is resized, and cleared when the next frame is drawn
+static RESIZED: AtomicBool = AtomicBool::new(false);
 
 /// The struct which implements [`Menu`] for unix platforms.\
 /// Holds a lock to stdout, so nothing else should be able to write to the console while this struct exists.
+#[allow(clippy::struct_excessive_bools)] // The settings are independent of each other, so they aren't a state machine
 pub struct Tui {
     /// A lock to stdout.
     /// A [`BufWriter`] is used to prevent flickering, as the output will only be written once per frame.
     stdout: BufWriter<AlternateScreen<RawTerminal<Stdout>>>,
+    /// How quickly to show the text of screens
+    scroll_speed: ScrollSpeed,
+    /// The colours to show the border and selected options in
+    theme: Theme,
+    /// If demo mode is on, screens and lists move on by themselves after a while
+    demo: Option<Demo>,
+    /// The [status][Menu::set_status] shown on the bottom border of every frame
+    status: Option<Status>,
+    /// Whether the text animation has been turned off with 's', so that every screen is shown instantly.
+    /// This lasts until 's' is pressed again, whatever the [`ScrollSpeed`] is.
+    no_animation: bool,
+    /// Whether the player has [just been hurt][Menu::flash_damage], so the next frame should flash
+    flash: bool,
+    /// Whether to ring the terminal bell when the player is hurt
+    damage_bell: bool,
+    /// Whether to list the player's weapons from [most to least effective][Menu::best_weapon_first] in battle
+    best_weapon_first: bool,
+    /// Whether to show the [numbers of options][Menu::numbered_options] in lists, so that they can be typed to pick them
+    numbered_options: bool,
+    /// How often to redraw, which is lower while nothing is moving
+    frame_rate: FrameRate,
+    /// Any escape sequence which was cut off at the end of the last read from stdin
+    input: InputBuffer,
 }
 
 /// A unix specific error which can occur while showing a menu
@@ -75,25 +110,119 @@ fn poll_stdin(stdin: &mut StdinLock) -> Result<Option<String>, std::io::Error> {
     }
     .into();
 
-    // Call the select syscall
-    let num_files = select(None, &mut fd_set, None, None, &mut zero_time)?;
+    // Call the select syscall. If a signal (e.g. a resize) interrupted it, treat it as there being no input yet.
+    let num_files = match select(None, &mut fd_set, None, None, &mut zero_time) {
+        Err(Errno::EINTR) => return Ok(None),
+        result => result?,
+    };
 
     // If stdout was ready to read, get the data from it
     if num_files > 0 {
         let mut buf = [0_u8; 256];
         let num_bytes = stdin.read(&mut buf)?;
-        let buf = std::str::from_utf8(&buf[..num_bytes]).unwrap();
-        Ok(Some(buf.to_string()))
+        Ok(Some(decode_input(&buf[..num_bytes])))
     } else {
         Ok(None)
     }
 }
 
+/// The handler for the `SIGWINCH` signal, which is sent when the terminal is resized.
+/// Signal handlers can interrupt the program at any point, so this only sets [`RESIZED`].
+extern "C" fn handle_resize(_signal: c_int) {
+    RESIZED.store(true, Ordering::Relaxed);
+}
+
+/// Sets [`handle_resize`] as the handler for the `SIGWINCH` signal
+fn install_resize_handler() -> Result<(), std::io::Error> {
+    let action = SigAction::new(
+        SigHandler::Handler(handle_resize),
+        SaFlags::SA_RESTART,
+        SigSet::empty(),
+    );
+
+    // SAFETY: `handle_resize` only stores to an atomic, which is safe to do in a signal handler
+    unsafe { sigaction(Signal::SIGWINCH, &action) }?;
+    Ok(())
+}
+
+/// Waits until it is time to draw the next frame, or until something happens which needs drawing straight away.
+/// Rather than sleeping for the whole frame, this blocks on stdin with the select syscall,
+/// so that a key press or a resize (which interrupts the syscall) wakes it up early.
+/// While nothing is moving on the screen, the frame can be long enough that the process sleeps until the next key press.
+///
+/// ### Params:
+/// * `stdin` - stdin, to stop waiting when there is input to read, or [`None`] to only stop early for a resize
+/// * `frame_ms` - the longest time to wait for, in milliseconds
+///
+/// ### Returns:
+/// Whether the terminal was resized, and how many milliseconds were actually waited for
+fn wait_for_frame(stdin: Option<&StdinLock>, frame_ms: u64) -> Result<(bool, u64), std::io::Error> {
+    let start = Instant::now();
+    let frame_end = start + Duration::from_millis(frame_ms);
+
+    loop {
+        let now = Instant::now();
+        if now >= frame_end || RESIZED.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let mut fd_set = FdSet::new();
+        if let Some(stdin) = stdin {
+            fd_set.insert(stdin.as_raw_fd());
+        }
+
+        let remaining = frame_end - now;
+        let mut remaining: TimeVal = timeval {
+            tv_sec: remaining.as_secs().try_into().unwrap_or(0),
+            tv_usec: remaining.subsec_micros().into(),
+        }
+        .into();
+
+        // A signal interrupting the syscall is checked for at the top of the loop
+        match select(None, &mut fd_set, None, None, &mut remaining) {
+            Err(Errno::EINTR) => continue,
+            Ok(num_files) if num_files > 0 => break,
+            result => result?,
+        };
+    }
+
+    #[allow(clippy::cast_possible_truncation)] // A frame never lasts anywhere near u64::MAX milliseconds
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+    Ok((RESIZED.swap(false, Ordering::Relaxed), elapsed_ms))
+}
+
+/// Gets how quickly a screen's text should scroll, given how long it is.
+/// Screens up to [`SCROLL_SPEEDUP_THRESHOLD`] characters long scroll at `scroll_speed`,
+/// and longer ones scroll faster in proportion to their length, up to [`MAX_SCROLL_SPEEDUP`] times faster.
+fn scroll_speed_for_length(scroll_speed: ScrollSpeed, num_graphemes: usize) -> ScrollSpeed {
+    match scroll_speed {
+        ScrollSpeed::CharsPerSecond(chars_per_second) => {
+            // The speed can be set from the environment, so it may be big enough to overflow
+            let scaled = chars_per_second.saturating_mul(num_graphemes as u64) / SCROLL_SPEEDUP_THRESHOLD as u64;
+            ScrollSpeed::CharsPerSecond(scaled.clamp(chars_per_second, chars_per_second.saturating_mul(MAX_SCROLL_SPEEDUP)))
+        }
+        ScrollSpeed::Instant => ScrollSpeed::Instant,
+    }
+}
+
+/// Gets the index of the option nearest to `i` which can be chosen, so that the selection never rests on a disabled option.
+/// If two options are equally near, the later one is picked. If no option can be chosen, `i` is kept as it is.
+fn nearest_enabled(enabled: &[bool], i: usize) -> usize {
+    let i = i.min(enabled.len().saturating_sub(1));
+    (0..enabled.len())
+        .filter(|&j| enabled[j])
+        .min_by_key(|&j| (j.abs_diff(i), j < i))
+        .unwrap_or(i)
+}
+
 /// Gets the size of the terminal, or an error if the terminal is too small
 fn get_size_checked() -> Result<(u16, u16), TuiError> {
     let (w, h) = terminal_size()?;
 
-    if w < LEFT_OFFSET + RIGHT_OFFSET + 50 || h < TOP_OFFSET + BOTTOM_OFFSET + 10 {
+    // The extra column is the space left for the dash when a word is hyphenated
+    if w < LEFT_OFFSET + RIGHT_OFFSET + 1 + MIN_CONTENT_WIDTH
+        || h < TOP_OFFSET + BOTTOM_OFFSET + MIN_CONTENT_HEIGHT
+    {
         Err(TuiError::TerminalTooSmall)
     } else {
         Ok((w, h))
@@ -102,34 +231,60 @@ fn get_size_checked() -> Result<(u16, u16), TuiError> {
 
 impl Drop for Tui {
     fn drop(&mut self) {
-        // Can't return a Result from drop, so unwrap Result values
+        // Can't return a Result from drop, and panicking here could hide the error which ended the game,
+        // so errors are ignored. The terminal is taken out of raw mode and the alternate screen whatever happens.
 
         // Show the cursor
-        write!(self.stdout, "{}", cursor::Show).unwrap();
-        self.stdout.flush().unwrap();
+        let _ = write!(self.stdout, "{}", cursor::Show);
+        let _ = self.stdout.flush();
     }
 }
 
 impl Menu for Tui {
     fn new() -> Result<Self, std::io::Error> {
-        let mut stdout = std::io::stdout().into_raw_mode()?.into_alternate_screen()?;
-
-        // Hide the cursor
-        write!(stdout, "{}", cursor::Hide)?;
-
-        let stdout = BufWriter::new(stdout);
-
-        Ok(Self { stdout })
+        // Redraw as soon as the terminal is resized rather than waiting for the next frame.
+        // This is done before the terminal is changed, so that failing to do it leaves the terminal as it was.
+        install_resize_handler()?;
+
+        // If entering the alternate screen fails, dropping the raw terminal takes it back out of raw mode
+        let stdout = BufWriter::new(std::io::stdout().into_raw_mode()?.into_alternate_screen()?);
+        let scroll_speed =
+            ScrollSpeed::from_env().unwrap_or(ScrollSpeed::CharsPerSecond(CHARS_PER_SECOND));
+        let theme = Theme::from_env().unwrap_or_default();
+        let demo = Demo::from_env();
+        let frame_rate = FrameRate::from_env().unwrap_or_default();
+
+        let mut tui = Self {
+            stdout,
+            scroll_speed,
+            theme,
+            demo,
+            frame_rate,
+            status: None,
+            no_animation: false,
+            flash: false,
+            damage_bell: false,
+            best_weapon_first: false,
+            numbered_options: false,
+            input: InputBuffer::default(),
+        };
+
+        // Hide the cursor. This is only done once the Tui exists, so that it is shown again when the Tui is dropped even if this fails.
+        write!(tui.stdout, "{}", cursor::Hide)?;
+        Ok(tui)
     }
 
     fn try_show_option_list(&mut self, list: OptionList<'_>) -> Result<usize, Error> {
         #[cfg(feature = "no-flicker")]
         write!(self.stdout, "{}", clear::All)?;
 
-        // Get options from list with numbers
-        let items: Vec<_> = list.options.iter().map(String::as_str).collect();
+        // Get options from list, with the reasons any disabled ones can't be chosen
+        let texts = list.option_texts();
+        let items: Vec<_> = texts.iter().map(String::as_str).collect();
+        let details: Vec<_> = (0..items.len()).map(|i| list.detail(i)).collect();
+        let enabled: Vec<_> = (0..items.len()).map(|i| list.disabled_reason(i).is_none()).collect();
 
-        let choice = self.choose_from_list(&items, list.prompt)?;
+        let choice = self.choose_from_list(&items, &details, &enabled, list.prompt)?;
         Ok(choice)
     }
 
@@ -140,16 +295,18 @@ impl Menu for Tui {
         #[cfg(feature = "no-flicker")]
         write!(self.stdout, "{}", clear::All)?;
 
-        // Get options from list, including cancel option
-        let items: Vec<_> = list
-            .options
+        // Get options from list, including cancel option, which can always be chosen
+        let texts = list.option_texts();
+        let items: Vec<_> = texts
             .iter()
             .map(String::as_str)
             .chain(std::iter::once("Cancel"))
             .collect();
+        let details: Vec<_> = (0..items.len()).map(|i| list.detail(i)).collect();
+        let enabled: Vec<_> = (0..items.len()).map(|i| list.disabled_reason(i).is_none()).collect();
 
         // Show list UI
-        let selection = self.choose_from_list(&items, list.prompt)?;
+        let selection = self.choose_from_list(&items, &details, &enabled, list.prompt)?;
 
         // Check whether the user pressed 'cancel'
         if selection == list.options.len() {
@@ -159,70 +316,206 @@ impl Menu for Tui {
         }
     }
 
-    fn try_show_screen(&mut self, screen: super::Screen) -> Result<(), Error> {
+    fn scroll_speed(&self) -> ScrollSpeed {
+        self.scroll_speed
+    }
+
+    fn set_scroll_speed(&mut self, speed: ScrollSpeed) {
+        self.scroll_speed = speed;
+    }
+
+    fn content_width(&self) -> Option<usize> {
+        // The extra column is the space left for the dash when a word is hyphenated
+        let (w, _) = get_size_checked().ok()?;
+        Some((w - LEFT_OFFSET - RIGHT_OFFSET - 1) as usize)
+    }
+
+    fn try_show_screen(&mut self, screen: Screen) -> Result<KeyPress, Error> {
+        self.show_screen_with_help(&screen, true)
+    }
+
+    fn set_status(&mut self, status: Option<Status>) {
+        self.status = status;
+    }
+
+    fn flash_damage(&mut self) {
+        self.flash = true;
+    }
+
+    fn damage_bell(&self) -> bool {
+        self.damage_bell
+    }
+
+    fn set_damage_bell(&mut self, bell: bool) {
+        self.damage_bell = bell;
+    }
+
+    fn best_weapon_first(&self) -> bool {
+        self.best_weapon_first
+    }
+
+    fn set_best_weapon_first(&mut self, best_first: bool) {
+        self.best_weapon_first = best_first;
+    }
+
+    fn numbered_options(&self) -> bool {
+        self.numbered_options
+    }
+
+    fn set_numbered_options(&mut self, numbered: bool) {
+        self.numbered_options = numbered;
+    }
+}
+
+impl Tui {
+    /// Shows the [`CONTROLS_SCREEN`] over whatever is currently being shown.
+    /// The caller should carry on where it left off once this returns.
+    fn show_help(&mut self) -> Result<(), Error> {
+        self.show_screen_with_help(&CONTROLS_SCREEN, false)?;
+        Ok(())
+    }
+
+    /// Shows a [`Screen`], as in [`try_show_screen`][Menu::try_show_screen].
+    /// If `help_available` is true, pressing '?' shows the [help][Tui::show_help] and then returns to the screen.
+    /// This is false for the help itself, so that it can't be opened on top of itself.
+    fn show_screen_with_help(&mut self, screen: &Screen, help_available: bool) -> Result<KeyPress, Error> {
         #[cfg(feature = "no-flicker")]
         write!(self.stdout, "{}", clear::All)?;
 
         // Lock stdin
         let mut stdin = std::io::stdin().lock();
+        // Remove the colour markup, so that only the visible text is laid out and scrolled
+        let (content, colour_spans) = parse_markup(screen.content);
         // A cache for the layout so that it doesn't need to be regenerated every frame
-        let mut layout = TextLayout::new(screen.content, 100);
+        let mut layout = TextLayout::new(&content, 100);
         // The number of graphemes in the string
-        let num_graphemes = screen.content.graphemes(true).count();
+        let num_graphemes = content.graphemes(true).count();
+        // Long screens scroll faster, so that they don't take too long to read
+        let scroll_speed = scroll_speed_for_length(self.scroll_speed, num_graphemes);
 
-        // The number of milliseconds that have passed, used to compute how many graphemes to render
+        // Whether the last key the user pressed was escape, so that pressing it twice can quit
+        let mut last_key_was_escape = false;
+        // Keys which have been read but not handled yet
+        let mut pending_keys = VecDeque::new();
+
+        // The number of milliseconds of scrolling that have been shown, used to compute how many graphemes to render
         let mut ms = 0;
-        // Whether to render all graphemes in the string
-        let mut render_all_graphemes = false;
+        // Whether the last frame showed the 'terminal too small' screen
+        let mut was_too_small = false;
+        // Whether to render all graphemes in the string. If the text should be shown instantly, there is no scroll.
+        let mut render_all_graphemes = self.scroll_speed == ScrollSpeed::Instant || self.no_animation;
+        // The value of `ms` when the whole string was first shown, used to dismiss the screen in demo mode
+        let mut finished_ms = None;
 
         // Loop until the user quits
         loop {
-            // Show the frame and wait
+            // Show the frame and wait. The first frame is drawn straight away, and once the text has finished
+            // scrolling there's nothing to animate, so this sleeps until a key is pressed.
+            // Demo mode needs to keep time to dismiss the screen, so it never sleeps.
+            // Keys can't be handled while the terminal is too small, so only a resize ends the wait then.
             self.stdout.flush()?;
-            std::thread::sleep(Duration::from_millis(MS_PER_FRAME));
-            ms += MS_PER_FRAME;
-
-            // Calculate how many graphemes to render this frame
-            let graphemes = if render_all_graphemes {
-                num_graphemes
-            } else {
-                let graphemes = (ms / MS_PER_CHAR) as usize;
-                // If the scroll has reached the end of the string, set render_all_graphemes to true
-                // This means that the next character press will quit instead of trying to skip the scroll
-                if graphemes > num_graphemes {
-                    render_all_graphemes = true;
-                }
-                graphemes
-            };
+            let animating = ms == 0 || !render_all_graphemes || !pending_keys.is_empty() || self.demo.is_some();
+            let frame_ms = self.frame_rate.frame_ms(animating && !was_too_small);
+            let (resized, elapsed_ms) = wait_for_frame((!was_too_small).then_some(&stdin), frame_ms)?;
+
+            // The layout is regenerated if the width changed, but anything left over from the old size
+            // or from the 'terminal too small' screen needs clearing
+            if resized || was_too_small {
+                self.clear_screen()?;
+            }
 
             match self.new_frame() {
                 Err(TuiError::TerminalTooSmall) => {
                     self.render_too_small_error_screen()?;
+                    // Don't advance the scroll, so no text is skipped while the user can't see it
+                    was_too_small = true;
                     continue;
                 }
                 Err(TuiError::MenuError(m)) => return Err(m),
                 Ok(()) => (),
             };
+            was_too_small = false;
+
+            ms += elapsed_ms;
 
-            self.render_graphemes_from_str(screen.content, graphemes, &mut layout)?;
+            // Calculate how many graphemes to render this frame
+            let graphemes = match scroll_speed {
+                ScrollSpeed::CharsPerSecond(chars_per_second) if !render_all_graphemes => {
+                    let graphemes = (ms * chars_per_second / 1000) as usize;
+                    // If the scroll has reached the end of the string, set render_all_graphemes to true
+                    // This means that the next character press will quit instead of trying to skip the scroll
+                    if graphemes > num_graphemes {
+                        render_all_graphemes = true;
+                    }
+                    graphemes
+                }
+                _ => num_graphemes,
+            };
 
-            self.render_text_centred(screen.title, TOP_OFFSET)?;
+            // Render the banner if there is one, and move the title and content down to make room for it
+            let banner_height = match screen.banner {
+                Some(banner) => self.render_banner(banner)?,
+                None => 0,
+            };
 
-            if let Some(char) = poll_stdin(&mut stdin)? {
-                // If the scroll has finished, break
-                if render_all_graphemes {
-                    break;
+            // Render the title below the banner. Long titles are wrapped, but must leave room for some content.
+            let (_, h) = get_size_checked().unwrap();
+            let max_title_lines = h - TOP_OFFSET - BOTTOM_OFFSET - banner_height - MIN_CONTENT_HEIGHT + 1;
+            let title_height = self.render_title(screen.title, TOP_OFFSET + banner_height, max_title_lines)?;
+
+            // The first line of the title is above the content area, so only the rest push the content down
+            let content_top_line = banner_height + title_height - 1;
+            self.render_graphemes_from_str(&content, &colour_spans, graphemes, &mut layout, content_top_line.into())?;
+
+            // In demo mode, dismiss the screen once it has been shown in full for the dwell time
+            if render_all_graphemes {
+                let finished_ms = *finished_ms.get_or_insert(ms);
+
+                if self.demo.as_ref().is_some_and(|demo| ms - finished_ms >= demo.dwell_ms()) {
+                    return Ok(KeyPress::Enter);
                 }
+            }
+
+            // Read user input, handling one key per frame
+            let input = poll_stdin(&mut stdin)?;
+            pending_keys.extend(self.input.parse(input.as_deref()));
 
-                if char == "q" {
+            if let Some(key) = pending_keys.pop_front() {
+
+                // Pressing 'q' or escape twice quits
+                if key == Key::Char('q') || (key == Key::Escape && last_key_was_escape) {
                     return Err(Error::Quit);
                 }
+                last_key_was_escape = key == Key::Escape;
+
+                // Escape is only used for quitting, so it shouldn't dismiss the screen
+                if key == Key::Escape {
+                    continue;
+                }
+
+                // Turning the animation off also skips the rest of this screen's scroll.
+                // The key doesn't dismiss the screen, so it can be pressed at any time.
+                if key == Key::Char('s') {
+                    self.no_animation = !self.no_animation;
+                    render_all_graphemes |= self.no_animation;
+                    continue;
+                }
+
+                // Show the help, then carry on with the screen where it was
+                if help_available && key == Key::Char('?') {
+                    self.show_help()?;
+                    self.clear_screen()?;
+                    continue;
+                }
+
+                // If the scroll has finished, return the key which dismissed the screen
+                if render_all_graphemes {
+                    return Ok(key.to_key_press());
+                }
 
                 // Otherwise, skip the rest of the scroll
                 render_all_graphemes = true;
             }
         }
-
-        Ok(())
     }
 }
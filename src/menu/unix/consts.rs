@@ -14,6 +14,9 @@ pub(super) const BOTTOM_LEFT_CORNER: char = '\\';
 /// The character to be printed in the bottom-right of the screen
 pub(super) const BOTTOM_RIGHT_CORNER: char = '/';
 
+/// The number of border characters kept between the [status][crate::menu::Menu::set_status] and the corners of the bottom line
+pub(super) const STATUS_BORDER_GAP: usize = 2;
+
 /// The offset of content from the left hand side of the screen
 pub(super) const LEFT_OFFSET: u16 = 3;
 /// The offset of content from the top of the screen
@@ -25,13 +28,34 @@ pub(super) const RIGHT_OFFSET: u16 = 2;
 
 /// The smallest size a segment will be when wrapping text
 pub(super) const TEXT_WRAPPING_MIN_SEGMENT_SIZE: usize = 5;
+/// The number of spaces a tab character is rendered as
+pub(super) const TAB_WIDTH: usize = 4;
+
+/// The smallest number of columns kept between an option in a list and its [detail][crate::menu::OptionList::with_details]
+pub(super) const LIST_DETAIL_GAP: u16 = 2;
+
+/// The hint shown in place of the list when a search doesn't match any options
+pub(super) const NO_SEARCH_MATCHES_TEXT: &str = "No options match your search";
+/// The minimum width of the content area, not including the offsets.
+/// This is the longest text which has to be shown in full, which is [`NO_SEARCH_MATCHES_TEXT`].
+/// Everything else is either wrapped or cut off with an ellipsis.
+#[allow(clippy::cast_possible_truncation)]
+pub(super) const MIN_CONTENT_WIDTH: u16 = NO_SEARCH_MATCHES_TEXT.len() as u16;
+/// The minimum height of the content area, not including the offsets.
+/// Lists which don't fit need one line for the selected option and one for the ellipsis showing there are more.
+pub(super) const MIN_CONTENT_HEIGHT: u16 = 2;
 
-/// The target framerate
-pub(super) const FPS: u64 = 30;
-/// The number of milliseconds per frame based on [`FPS`]
-pub(super) const MS_PER_FRAME: u64 = 1000 / FPS;
+/// The default framerate while text is scrolling or keys are being handled, if no [`FrameRate`][super::frame_rate::FrameRate] is set
+pub(super) const MAX_FPS: u64 = 30;
+/// The longest time to block waiting for a key press while nothing on the screen is moving.
+/// A resize interrupts the wait, but one which happens just before it starts is only noticed once this runs out.
+pub(super) const IDLE_TIMEOUT_MS: u64 = 500;
 
-/// The target number of characters to print per second when scrolling text
+/// The default number of characters to print per second when scrolling text, if no [`ScrollSpeed`][crate::menu::ScrollSpeed] is set
 pub(super) const CHARS_PER_SECOND: u64 = 50;
-/// The number of milliseconds between characters based on [`CHARS_PER_SECOND`]
-pub(super) const MS_PER_CHAR: u64 = 1000 / CHARS_PER_SECOND;
+/// The number of characters a screen can have before its text starts scrolling faster than the [`ScrollSpeed`][crate::menu::ScrollSpeed].
+/// Longer screens scroll proportionally faster, so they take as long as a screen of this length would.
+pub(super) const SCROLL_SPEEDUP_THRESHOLD: usize = 250;
+/// The most times faster than the [`ScrollSpeed`][crate::menu::ScrollSpeed] that a long screen's text scrolls,
+/// so that even the longest screens are still animated. Screens longer than this many times [`SCROLL_SPEEDUP_THRESHOLD`] take longer to scroll.
+pub(super) const MAX_SCROLL_SPEEDUP: u64 = 4;
@@ -0,0 +1,88 @@
+//! Demo mode, where screens and lists move on by themselves so that the game can be recorded hands-off
+
+use std::collections::VecDeque;
+
+use crate::map::SeededRng;
+
+/// The name of the environment variable which turns on [`Demo`] mode, set to the dwell time in milliseconds
+const DEMO_VAR: &str = "TUI_DEMO";
+/// The name of the environment variable holding the path of a [`Demo`] script, with the text of one option to pick on each line
+const DEMO_SCRIPT_VAR: &str = "TUI_DEMO_SCRIPT";
+/// The seed for picking options once the script has run out, so that the same demo is recorded every time
+const DEMO_SEED: u64 = 0;
+
+/// The state of demo mode, which is off unless the `TUI_DEMO` environment variable is set
+#[derive(Debug)]
+pub(super) struct Demo {
+    /// How long a screen stays up once its text has finished scrolling, and how long a list is shown before an option is picked
+    dwell_ms: u64,
+    /// The text of the options to pick, in order. Each one picks the first option which starts with it.
+    script: VecDeque<String>,
+    /// Picks options once the script has run out
+    rng: SeededRng,
+}
+
+impl Demo {
+    /// Creates a new [`Demo`]
+    ///
+    /// ### Params:
+    /// * `dwell_ms` - how long to wait before moving on, in milliseconds
+    /// * `script` - the text of the options to pick, one per line. Blank lines are ignored.
+    pub(super) fn new(dwell_ms: u64, script: &str) -> Self {
+        Self {
+            dwell_ms,
+            script: script
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect(),
+            rng: SeededRng::new(DEMO_SEED),
+        }
+    }
+
+    /// Reads the [`Demo`] settings from the `TUI_DEMO` and `TUI_DEMO_SCRIPT` environment variables.
+    /// Returns [`None`] if `TUI_DEMO` is not set or is not a number, so demo mode is off.
+    /// If the script can't be read, options are picked at random from the start.
+    pub(super) fn from_env() -> Option<Self> {
+        let dwell_ms = std::env::var(DEMO_VAR).ok()?.trim().parse().ok()?;
+        let script = std::env::var(DEMO_SCRIPT_VAR)
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .unwrap_or_default();
+
+        Some(Self::new(dwell_ms, &script))
+    }
+
+    /// Gets how long to wait before moving on, in milliseconds
+    pub(super) const fn dwell_ms(&self) -> u64 {
+        self.dwell_ms
+    }
+
+    /// Picks an option from a list, out of the options which `enabled` says can be chosen.
+    /// This is the next option from the script if it's in the list, otherwise a random option.
+    /// Random picks never quit the game, so that the demo carries on until the player escapes.
+    ///
+    /// ### Panics
+    /// * If none of `items` can be chosen
+    pub(super) fn choose(&mut self, items: &[&str], enabled: &[bool]) -> usize {
+        if let Some(text) = self.script.pop_front() {
+            if let Some(choice) = (0..items.len()).find(|&i| enabled[i] && items[i].starts_with(&text)) {
+                return choice;
+            }
+        }
+
+        let choosable: Vec<usize> = (0..items.len()).filter(|&i| enabled[i]).collect();
+        let candidates: Vec<usize> = choosable
+            .iter()
+            .copied()
+            .filter(|&i| !items[i].starts_with("Quit"))
+            .collect();
+
+        if candidates.is_empty() {
+            choosable[self.rng.below(choosable.len())]
+        } else {
+            candidates[self.rng.below(candidates.len())]
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! How often the TUI redraws, which stops while nothing on the screen is moving
+
+use super::consts::{IDLE_TIMEOUT_MS, MAX_FPS};
+
+/// The name of the environment variable which sets the [`FrameRate`]'s maximum frames per second
+const FPS_VAR: &str = "TUI_FPS";
+
+/// How often frames are drawn.
+/// Text scrolling onto the screen and queued key presses are drawn at the full rate,
+/// but while the menu is only waiting for a key press it sleeps until one arrives, for up to [`IDLE_TIMEOUT_MS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct FrameRate {
+    /// The number of frames per second while something is moving on the screen
+    max_fps: u64,
+}
+
+impl Default for FrameRate {
+    fn default() -> Self {
+        Self { max_fps: MAX_FPS }
+    }
+}
+
+impl FrameRate {
+    /// Creates a new [`FrameRate`]. Returns [`None`] if `max_fps` is 0.
+    pub(super) const fn new(max_fps: u64) -> Option<Self> {
+        if max_fps == 0 {
+            None
+        } else {
+            Some(Self { max_fps })
+        }
+    }
+
+    /// Reads the maximum frames per second from the `TUI_FPS` environment variable.
+    /// Returns [`None`] if the variable is not set or is not a positive number.
+    pub(super) fn from_env() -> Option<Self> {
+        Self::new(std::env::var(FPS_VAR).ok()?.trim().parse().ok()?)
+    }
+
+    /// Gets how long the next frame should last, in milliseconds
+    ///
+    /// ### Params:
+    /// * `animating` - whether anything will change on the next frame without the user pressing a key,
+    ///   such as text still scrolling or keys still queued up to be handled
+    pub(super) fn frame_ms(self, animating: bool) -> u64 {
+        // Very high rates are capped at one frame per millisecond
+        let animating_ms = (1000 / self.max_fps).max(1);
+
+        // A low maximum rate is never sped up by going idle
+        if animating {
+            animating_ms
+        } else {
+            animating_ms.max(IDLE_TIMEOUT_MS)
+        }
+    }
+}
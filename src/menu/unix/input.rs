@@ -0,0 +1,200 @@
+//! Parsing of the raw input read from the terminal into the keys which were pressed
+
+use crate::menu::KeyPress;
+
+/// The ANSI escape produced by the up arrow key
+const ANSI_UP: &str = "\x1b[A";
+/// The ANSI escape produced by the down arrow key
+const ANSI_DOWN: &str = "\x1b[B";
+/// The ANSI escape produced by the page up key
+const ANSI_PAGE_UP: &str = "\x1b[5~";
+/// The ANSI escape produced by the page down key
+const ANSI_PAGE_DOWN: &str = "\x1b[6~";
+/// The ANSI escapes which different terminals produce for the home key
+const ANSI_HOME: [&str; 3] = ["\x1b[H", "\x1b[1~", "\x1bOH"];
+/// The ANSI escapes which different terminals produce for the end key
+const ANSI_END: [&str; 3] = ["\x1b[F", "\x1b[4~", "\x1bOF"];
+/// The input produced by pressing the escape key on its own
+const ANSI_ESCAPE: &str = "\x1b";
+
+/// A key which the user pressed in the TUI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Key {
+    /// The up arrow key
+    Up,
+    /// The down arrow key
+    Down,
+    /// The page up key
+    PageUp,
+    /// The page down key
+    PageDown,
+    /// The home key
+    Home,
+    /// The end key
+    End,
+    /// The enter key, which terminals send as either a carriage return or a newline
+    Enter,
+    /// The escape key on its own
+    Escape,
+    /// The backspace key, which terminals send as either DEL or BS
+    Backspace,
+    /// A key which produces a printable character
+    Char(char),
+    /// A key which is not otherwise recognised, such as an unknown escape sequence or control character
+    Other,
+}
+
+impl Key {
+    /// Gets the [`Key`] represented by the input for a single key, as split by [`split_input`]
+    fn from_input(input: &str) -> Self {
+        match input {
+            ANSI_UP => Self::Up,
+            ANSI_DOWN => Self::Down,
+            ANSI_PAGE_UP => Self::PageUp,
+            ANSI_PAGE_DOWN => Self::PageDown,
+            ANSI_ESCAPE => Self::Escape,
+            "\r" | "\n" => Self::Enter,
+            "\x7f" | "\x08" => Self::Backspace,
+            _ if ANSI_HOME.contains(&input) => Self::Home,
+            _ if ANSI_END.contains(&input) => Self::End,
+            _ => {
+                let mut chars = input.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if !c.is_control() => Self::Char(c),
+                    _ => Self::Other,
+                }
+            }
+        }
+    }
+
+    /// Gets the [`KeyPress`] to return to the caller of a [`Menu`][crate::menu::Menu] method
+    pub(super) const fn to_key_press(self) -> KeyPress {
+        match self {
+            Self::Enter => KeyPress::Enter,
+            Self::Escape => KeyPress::Escape,
+            Self::Char(c) => KeyPress::Char(c),
+            _ => KeyPress::Other,
+        }
+    }
+}
+
+/// Decodes some raw input read by [`poll_stdin`][super::poll_stdin] as text, so that it can be [parsed into keys][parse_keys].
+/// Any bytes which aren't valid UTF-8 are ignored rather than crashing the game.
+/// This includes pasted binary data, and characters which were split between two reads because they came at the end of the buffer.
+pub(super) fn decode_input(bytes: &[u8]) -> String {
+    bytes.utf8_chunks().map(|chunk| chunk.valid()).collect()
+}
+
+/// Parses some input read by [`poll_stdin`][super::poll_stdin] into the keys which were pressed, in order.
+/// When a key is held, the terminal may send several repeats before the next frame, which all arrive in one read.
+pub(super) fn parse_keys(buf: &str) -> Vec<Key> {
+    split_input(buf).into_iter().map(Key::from_input).collect()
+}
+
+/// Holds on to the start of an escape sequence which was cut off at the end of a read, so that it can be joined to the rest of it from the next read.
+/// Over slow connections such as ssh, an arrow key's escape can arrive in two halves, which would otherwise be read as two separate keys.
+#[derive(Debug, Default)]
+pub(super) struct InputBuffer {
+    /// The incomplete escape sequence from the end of the last read, if there was one
+    leftover: String,
+}
+
+impl InputBuffer {
+    /// Parses the input read by [`poll_stdin`][super::poll_stdin] this frame into the keys which were pressed, in order.
+    /// If the input ends partway through an escape sequence, that part is kept until the next frame.
+    ///
+    /// ### Params:
+    /// * `input`: what was read this frame, or [`None`] if nothing was.
+    ///   If nothing was read, the rest of the sequence isn't coming, so anything left over is parsed as it is - e.g. a lone escape is the escape key.
+    pub(super) fn parse(&mut self, input: Option<&str>) -> Vec<Key> {
+        let mut input_to_parse = std::mem::take(&mut self.leftover);
+
+        if let Some(input) = input {
+            input_to_parse.push_str(input);
+
+            let incomplete_len = split_input(&input_to_parse)
+                .last()
+                .filter(|key| is_incomplete_escape(key))
+                .map_or(0, |key| key.len());
+            self.leftover = input_to_parse.split_off(input_to_parse.len() - incomplete_len);
+        }
+
+        parse_keys(&input_to_parse)
+    }
+}
+
+/// The digits typed so far to jump to an option in a list by its number, when [options are numbered][crate::menu::Menu::numbered_options]
+#[derive(Debug, Default)]
+pub(super) struct OptionNumberInput {
+    /// The digits typed since the last key which wasn't a digit
+    typed: String,
+}
+
+impl OptionNumberInput {
+    /// Adds a typed digit to the number. If that makes a number with no option, the digit starts a new number instead.
+    ///
+    /// ### Params:
+    /// * `digit`: the digit which was typed
+    /// * `num_options`: how many options there are in the list
+    ///
+    /// ### Returns:
+    /// The 0-based index of the option which the typed number refers to, or [`None`] if there isn't one
+    pub(super) fn push(&mut self, digit: char, num_options: usize) -> Option<usize> {
+        let refers_to_option = |typed: &str| typed.parse().is_ok_and(|n: usize| (1..=num_options).contains(&n));
+
+        self.typed.push(digit);
+        if !refers_to_option(&self.typed) {
+            self.typed = digit.to_string();
+        }
+        if !refers_to_option(&self.typed) {
+            self.typed.clear();
+            return None;
+        }
+
+        self.typed.parse::<usize>().ok().map(|n| n - 1)
+    }
+
+    /// Forgets the digits typed so far, so that the next digit starts a new number
+    pub(super) fn clear(&mut self) {
+        self.typed.clear();
+    }
+}
+
+/// Gets whether the input for a key is the start of an escape sequence which might continue in the next read
+fn is_incomplete_escape(key: &str) -> bool {
+    match key.strip_prefix('\x1b') {
+        Some("" | "O") => true,
+        Some(rest) => rest.strip_prefix('[').is_some_and(|rest| !rest.ends_with(|c| ('@'..='~').contains(&c))),
+        None => false,
+    }
+}
+
+/// Splits some input into the input for each key press.
+///
+/// ### Returns:
+/// The input for each key, which is either an escape sequence or a single character
+fn split_input(input: &str) -> Vec<&str> {
+    let mut keys = Vec::new();
+    let mut rest = input;
+
+    while let Some(c) = rest.chars().next() {
+        let len = match (c, rest[c.len_utf8()..].chars().next()) {
+            // A control sequence such as an arrow key goes until its final byte, which is in the range '@'..='~'
+            ('\x1b', Some('[')) => rest[2..]
+                .find(|c| ('@'..='~').contains(&c))
+                .map_or(rest.len(), |end| end + 3),
+            // Some terminals send the home and end keys as 'ESC O' followed by one character
+            ('\x1b', Some('O')) => rest[2..]
+                .chars()
+                .next()
+                .map_or(rest.len(), |c| 2 + c.len_utf8()),
+            _ => c.len_utf8(),
+        };
+
+        keys.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+
+    keys
+}
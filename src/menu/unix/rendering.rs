@@ -1,13 +1,14 @@
 //! Methods responsible for writing output to the tty
 
+use std::collections::VecDeque;
 use std::io::{stdin, Write};
-use std::time::Duration;
 
 use termion::{clear, color, cursor, style};
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 use super::*;
+use crate::menu::markup::Colour;
 use crate::menu::Error;
 
 impl Tui {
@@ -50,6 +51,9 @@ impl Tui {
     ///
     /// ### Params
     /// * items: the strings to render
+    /// * details: extra text for each item, shown against the right hand side.
+    ///   If space is tight the item is cut off first, and the detail is only left out if it doesn't fit at all.
+    /// * enabled: whether each item can be chosen. Items which can't are dimmed.
     /// * scroll: the offset to render the list at if it is cut off. Should persist between calls for best UX.
     /// * selected: which item in the list is selected
     ///
@@ -58,6 +62,8 @@ impl Tui {
     fn render_list(
         &mut self,
         items: &[&str],
+        details: &[Option<&str>],
+        enabled: &[bool],
         scroll: &mut usize,
         selected: usize,
     ) -> Result<(), Error> {
@@ -116,14 +122,33 @@ impl Tui {
 
             // If this is the currently selected line, highlight the option
             if option_number == selected {
-                write!(self.stdout, "{}", style::Invert)?;
+                write!(self.stdout, "{}", self.theme.selected_style())?;
+            }
+            // If the option can't be chosen, dim it
+            if !enabled[option_number] {
+                write!(self.stdout, "{}", Theme::disabled_style())?;
             }
 
-            // Write the line text
-            self.render_text_with_max_width(line, max_width)?;
+            // Write the line text, leaving room for the detail if there is one and it fits
+            let detail = details[option_number]
+                .map(|detail| (detail, display_width(detail)))
+                .and_then(|(detail, width)| Some((detail, u16::try_from(width).ok()?)))
+                .filter(|&(_, width)| width + LIST_DETAIL_GAP < max_width);
+
+            if let Some((detail, detail_width)) = detail {
+                // Fill the line first, so that the highlight covers the gap between the text and the detail
+                write!(self.stdout, "{}", " ".repeat(max_width.into()))?;
+                self.move_cursor(0, screen_line_number)?;
+                // The ellipsis can go one column past the maximum width, so the text is given one less column
+                self.render_text_with_max_width(line, max_width - detail_width - LIST_DETAIL_GAP - 1)?;
+                self.move_cursor((max_width - detail_width).into(), screen_line_number)?;
+                write!(self.stdout, "{detail}")?;
+            } else {
+                self.render_text_with_max_width(line, max_width)?;
+            }
 
             // Undo any highlighting
-            write!(self.stdout, "{}", style::NoInvert)?;
+            write!(self.stdout, "{}", Theme::reset_style())?;
         }
 
         // If the
@@ -143,6 +168,20 @@ impl Tui {
         #[cfg(not(feature = "no-flicker"))]
         write!(self.stdout, "{}", clear::All)?;
 
+        // If the player was just hurt, the border is inverted for this frame only, and the bell rings if it's turned on
+        let flash = std::mem::take(&mut self.flash);
+        if flash && self.damage_bell {
+            write!(self.stdout, "\x07")?;
+        }
+        let border_style = if flash {
+            format!("{}{}", self.theme.border_style(), style::Invert)
+        } else {
+            self.theme.border_style()
+        };
+
+        // Start drawing in the border style
+        write!(self.stdout, "{border_style}")?;
+
         // Go to top left corner
         write!(self.stdout, "{}", cursor::Goto(1, 1))?;
         // Print top left corner
@@ -205,6 +244,36 @@ impl Tui {
         // Print bottom right corner
         write!(self.stdout, "{BOTTOM_RIGHT_CORNER}")?;
 
+        // Print the status over the right of the bottom line, leaving some of the line next to each corner.
+        // If it doesn't fit, it isn't shown at all rather than being cut off.
+        if let Some(status) = &self.status {
+            // The status is padded with a space on each side to separate it from the border
+            let width = status.text.width() + 2;
+
+            if width + 2 * STATUS_BORDER_GAP <= (w - 2) as usize {
+                // The status is narrower than the terminal, so this fits in a u16
+                #[allow(clippy::cast_possible_truncation)]
+                let x = w - 1 - STATUS_BORDER_GAP as u16 - width as u16;
+                write!(self.stdout, "{} ", cursor::Goto(x + 1, h))?;
+
+                // A status with a warning is shown in red, then the border style is restored for the padding after it
+                match self.theme.text_colour(Colour::Red).filter(|_| status.warning.is_some()) {
+                    Some(colour) => write!(
+                        self.stdout,
+                        "{}{}{}{} ",
+                        color::Fg(colour),
+                        status.text,
+                        color::Fg(color::Reset),
+                        border_style
+                    )?,
+                    None => write!(self.stdout, "{} ", status.text)?,
+                }
+            }
+        }
+
+        // Go back to the normal style for the content
+        write!(self.stdout, "{}", Theme::reset_style())?;
+
         Ok(())
     }
 
@@ -231,93 +300,405 @@ impl Tui {
         Ok(())
     }
 
+    /// Clears the whole screen, e.g. after the terminal is resized or recovers from being too small.
+    /// With the `no-flicker` feature frames aren't usually cleared, so text from the old frame would otherwise be left behind.
+    pub(super) fn clear_screen(&mut self) -> Result<(), std::io::Error> {
+        write!(self.stdout, "{}", clear::All)?;
+        Ok(())
+    }
+
+    /// Renders a title centred at the given line. If the title is too long to fit on one line, it is wrapped onto the lines below.
+    ///
+    /// ### Params:
+    /// * title: the text to render
+    /// * line: the line to render the first line of the title on
+    /// * `max_lines`: the most lines the title can take up. If it needs more than this, the last line is cut off with an ellipsis.
+    ///
+    /// ### Returns:
+    /// The number of lines the title takes up
+    ///
+    /// ### Panics
+    /// * If the terminal is too small, based on if [`get_size_checked`] fails
+    pub(super) fn render_title(&mut self, title: &str, line: u16, max_lines: u16) -> Result<u16, Error> {
+        let (w, _) = get_size_checked().unwrap();
+        // Leave a column for the dash or ellipsis at the end of a line, so that it doesn't overwrite the border
+        let max_width = (w - LEFT_OFFSET - RIGHT_OFFSET - 1) as usize;
+
+        let layout = TextLayout::new(title, max_width);
+        let is_cut_off = layout.lines.len() > max_lines as usize;
+
+        let mut num_lines = 0;
+        for title_line in layout.lines.iter().take(max_lines as usize) {
+            num_lines += 1;
+
+            let text = if is_cut_off && num_lines == max_lines {
+                format!("{}⋯", title_line.content)
+            } else if title_line.dash_at_end {
+                format!("{}-", title_line.content)
+            } else {
+                title_line.content.to_string()
+            };
+
+            self.render_text_centred(&text, line + num_lines - 1)?;
+        }
+
+        Ok(num_lines)
+    }
+
     /// Renders an empty screen with text saying 'terminal too small'.
     pub(super) fn render_too_small_error_screen(&mut self) -> Result<(), std::io::Error> {
-        write!(self.stdout, "{}", clear::All)?;
-        write!(
-            self.stdout,
-            "{}{}Terminal too small{}",
-            cursor::Goto(1, 1),
-            color::Fg(color::Red),
-            color::Fg(color::Reset)
-        )?;
+        write!(self.stdout, "{}{}", clear::All, cursor::Goto(1, 1))?;
+
+        if self.theme.uses_colour() {
+            write!(
+                self.stdout,
+                "{}Terminal too small{}",
+                color::Fg(color::Red),
+                color::Fg(color::Reset)
+            )?;
+        } else {
+            write!(self.stdout, "Terminal too small")?;
+        }
 
         Ok(())
     }
 
-    /// Shows a TUI interface allowing the user to select an item from a list of options
-    pub(super) fn choose_from_list(&mut self, items: &[&str], title: &str) -> Result<usize, Error> {
-        let num_items = items.len();
+    /// Renders the current search query over the bottom border
+    ///
+    /// ### Panics
+    /// * If the terminal is too small, based on if [`get_size_checked`] fails
+    fn render_search_query(&mut self, query: &str) -> Result<(), Error> {
+        let (w, h) = get_size_checked().unwrap();
+        let max_width = w - LEFT_OFFSET - RIGHT_OFFSET;
 
-        // Init the UI state
-        let mut selected = 0;
+        write!(self.stdout, "{}", cursor::Goto(LEFT_OFFSET + 1, h))?;
+        self.render_text_with_max_width(&format!(" Search: {query}_ "), max_width)?;
+
+        Ok(())
+    }
+
+    /// Shows a TUI interface allowing the user to select an item from a list of options.
+    /// Pressing '/' starts a search, which filters the list to options containing the query.
+    /// The returned index is always into the unfiltered `items`, and is never one which `enabled` says can't be chosen.
+    #[allow(clippy::too_many_lines)]
+    pub(super) fn choose_from_list(
+        &mut self,
+        items: &[&str],
+        details: &[Option<&str>],
+        enabled: &[bool],
+        title: &str,
+    ) -> Result<usize, Error> {
+        // Init the UI state, starting on the first option which can be chosen
+        let mut selected = enabled.iter().position(|&e| e).unwrap_or(0);
         let mut scroll_offset = 0;
+        // Whether the last key the user pressed was escape, so that pressing it twice can quit
+        let mut last_key_was_escape = false;
+        // The search query, if the user is searching
+        let mut search: Option<String> = None;
+        // Whether the last frame showed the 'terminal too small' screen
+        let mut was_too_small = false;
+        // Keys which have been read but not handled yet
+        let mut pending_keys = VecDeque::new();
+        // The number of milliseconds the list has been shown for, used to pick an option in demo mode
+        let mut ms = 0;
+        // The option picked in demo mode, which is highlighted for the dwell time before it is returned
+        let mut demo_choice = None;
+        // The digits typed so far to jump to an option by its number
+        let mut number_input = OptionNumberInput::default();
+        // The items with their numbers in front, if options are numbered. The numbers are right aligned so that the options line up.
+        let number_width = items.len().to_string().len();
+        let numbered_items: Vec<String> = items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| format!("{: >number_width$}) {item}", i + 1))
+            .collect();
 
         // Lock stdin
         let mut stdin = stdin().lock();
 
         // Loop until the user chooses an option
         loop {
-            // Show the frame and wait
+            // Show the frame and wait. The list only changes when a key is pressed, so apart from the first frame,
+            // any queued keys, and demo mode's timing, this sleeps until the next key press.
+            // Keys can't be handled while the terminal is too small, so only a resize ends the wait then.
             self.stdout.flush()?;
-            std::thread::sleep(Duration::from_millis(MS_PER_FRAME));
+            let animating = ms == 0 || !pending_keys.is_empty() || self.demo.is_some();
+            let frame_ms = self.frame_rate.frame_ms(animating && !was_too_small);
+            let (resized, elapsed_ms) = wait_for_frame((!was_too_small).then_some(&stdin), frame_ms)?;
+            // Clear anything left over from the old size or from the 'terminal too small' screen
+            if resized || was_too_small {
+                self.clear_screen()?;
+            }
 
             // Render the border, propagating errors
             if let Err(e) = self.new_frame() {
                 match e {
                     TuiError::TerminalTooSmall => {
                         self.render_too_small_error_screen()?;
+                        was_too_small = true;
                         continue;
                     }
                     TuiError::MenuError(m) => return Err(m),
                 }
             };
+            was_too_small = false;
+            ms += elapsed_ms;
+
+            // In demo mode, pick an option after the dwell time, then return it after the dwell time again.
+            // Nothing is picked while the user is searching, so that they can take over.
+            if let Some(demo) = &mut self.demo {
+                if search.is_none() && ms >= demo.dwell_ms() {
+                    match demo_choice {
+                        None => {
+                            let choice = demo.choose(items, enabled);
+                            selected = choice;
+                            demo_choice = Some(choice);
+                        }
+                        Some(choice) if ms >= 2 * demo.dwell_ms() => return Ok(choice),
+                        Some(_) => (),
+                    }
+                }
+            }
+
+            // Get the indices of the items which match the search query, case-insensitively
+            let matching: Vec<usize> = match &search {
+                Some(query) => {
+                    let query = query.to_lowercase();
+                    (0..items.len())
+                        .filter(|&i| items[i].to_lowercase().contains(&query))
+                        .collect()
+                }
+                None => (0..items.len()).collect(),
+            };
+            // Each option keeps the number of its position in the whole list, even when the search hides some before it
+            let matching_items: Vec<&str> = if self.numbered_options {
+                matching.iter().map(|&i| numbered_items[i].as_str()).collect()
+            } else {
+                matching.iter().map(|&i| items[i]).collect()
+            };
+            let matching_details: Vec<Option<&str>> = matching.iter().map(|&i| details[i]).collect();
+            let matching_enabled: Vec<bool> = matching.iter().map(|&i| enabled[i]).collect();
+            let num_items = matching.len();
+            // Editing the search resets the selection to the top, which may not be an option that can be chosen
+            selected = nearest_enabled(&matching_enabled, selected);
+
+            // The number of lines visible at once, used for paging
+            let (_, h) = get_size_checked().unwrap();
+            let page_size = (h - TOP_OFFSET - BOTTOM_OFFSET) as usize;
 
             // Render the title
             self.render_text_centred(title, TOP_OFFSET)?;
 
-            // Render the list items
-            self.render_list(items, &mut scroll_offset, selected)?;
+            // Render the list items, or a hint if nothing matches the search
+            if matching_items.is_empty() {
+                self.move_cursor(0, 0)?;
+                self.render_text_with_max_width(NO_SEARCH_MATCHES_TEXT, u16::MAX)?;
+            } else {
+                self.render_list(&matching_items, &matching_details, &matching_enabled, &mut scroll_offset, selected)?;
+            }
+
+            // Render the search query
+            if let Some(query) = &search {
+                self.render_search_query(query)?;
+            }
 
-            // Handle user input
-            if let Some(input) = poll_stdin(&mut stdin)? {
-                // Up arrow
-                if input == ANSI_UP && selected != 0 {
-                    selected -= 1;
+            // Read user input. Held keys may send several repeats in one read, so each key is queued and handled on its own frame.
+            let input = poll_stdin(&mut stdin)?;
+            pending_keys.extend(self.input.parse(input.as_deref()));
+
+            // Handle the next key
+            if let Some(key) = pending_keys.pop_front() {
+                // While searching, typed characters edit the query rather than being used as keys
+                if let Some(query) = &mut search {
+                    match key {
+                        // Escape stops searching
+                        Key::Escape => {
+                            search = None;
+                            selected = 0;
+                            continue;
+                        }
+                        // Backspace deletes a character, or stops searching if the query is empty
+                        Key::Backspace => {
+                            if query.pop().is_none() {
+                                search = None;
+                            }
+                            selected = 0;
+                            continue;
+                        }
+                        // Printable characters are added to the query
+                        Key::Char(c) => {
+                            query.push(c);
+                            selected = 0;
+                            continue;
+                        }
+                        _ => (),
+                    }
                 }
-                // Down arrow
-                else if input == ANSI_DOWN && selected != num_items - 1 {
-                    selected += 1;
+
+                // Pressing 'q' or escape twice quits
+                if key == Key::Char('q') || (key == Key::Escape && last_key_was_escape) {
+                    return Err(Error::Quit);
                 }
-                // Enter
-                else if input == "\r" || input == "\n" {
-                    return Ok(selected);
+                last_key_was_escape = key == Key::Escape;
+
+                // Typing an option's number jumps to it. Numbers are only typed outside searches, so they are positions in the whole list.
+                // If every option has a single digit number, there's nothing more to type, so the option is picked straight away.
+                if let Key::Char(digit @ '0'..='9') = key {
+                    if self.numbered_options {
+                        if let Some(i) = number_input.push(digit, num_items) {
+                            if num_items <= 9 && matching_enabled[i] {
+                                return Ok(matching[i]);
+                            }
+                            selected = nearest_enabled(&matching_enabled, i);
+                        }
+                        continue;
+                    }
+                }
+                number_input.clear();
+
+                match key {
+                    // Show the help, then carry on with the same selection
+                    Key::Char('?') => {
+                        self.show_help()?;
+                        self.clear_screen()?;
+                    }
+                    // Start searching
+                    Key::Char('/') => {
+                        search = Some(String::new());
+                        selected = 0;
+                    }
+                    // Moving up or down skips over options which can't be chosen
+                    Key::Up | Key::Char('k') => {
+                        if let Some(i) = (0..selected).rev().find(|&i| matching_enabled[i]) {
+                            selected = i;
+                        }
+                    }
+                    Key::Down | Key::Char('j') => {
+                        if let Some(i) = (selected + 1..num_items).find(|&i| matching_enabled[i]) {
+                            selected = i;
+                        }
+                    }
+                    Key::PageUp => selected = nearest_enabled(&matching_enabled, selected.saturating_sub(page_size)),
+                    Key::PageDown => selected = nearest_enabled(&matching_enabled, selected + page_size),
+                    // Jump to the first option
+                    Key::Home | Key::Char('g') => selected = nearest_enabled(&matching_enabled, 0),
+                    // Jump to the last option
+                    Key::End | Key::Char('G') => selected = nearest_enabled(&matching_enabled, num_items.saturating_sub(1)),
+                    // Map the selection back to its position in the unfiltered list
+                    Key::Enter if selected < num_items && matching_enabled[selected] => return Ok(matching[selected]),
+                    _ => (),
                 }
             }
         }
     }
 
+    /// Renders a banner at the top of the content area, centred as a block so that its lines stay lined up.
+    /// The banner is not rendered if it would go past the border or leave less than [`MIN_CONTENT_HEIGHT`] lines for the content.
+    ///
+    /// ### Returns:
+    /// The number of lines the banner takes up, which is 0 if it was not rendered
+    ///
+    /// ### Panics
+    /// * If the terminal is too small, based on if [`get_size_checked`] fails
+    pub(super) fn render_banner(&mut self, banner: &str) -> Result<u16, Error> {
+        let (w, h) = get_size_checked().unwrap();
+        let max_width = (w - LEFT_OFFSET - RIGHT_OFFSET) as usize;
+        let max_lines = h - TOP_OFFSET - BOTTOM_OFFSET;
+
+        let banner_width = banner.lines().map(display_width).max().unwrap_or(0);
+        let Ok(banner_height) = u16::try_from(banner.lines().count()) else {
+            return Ok(0);
+        };
+
+        if banner_width > max_width || banner_height + MIN_CONTENT_HEIGHT > max_lines {
+            return Ok(0);
+        }
+
+        // The banner is checked to fit in the width, so this fits in a u16
+        #[allow(clippy::cast_possible_truncation)]
+        let left_offset = ((max_width - banner_width) / 2) as u16;
+
+        for (y, line) in (0..).zip(banner.lines()) {
+            write!(
+                self.stdout,
+                "{}{}",
+                cursor::Goto(left_offset + LEFT_OFFSET + 1, y + TOP_OFFSET),
+                expand_tabs(line)
+            )?;
+        }
+
+        Ok(banner_height)
+    }
+
+    /// Renders part of some text at the current cursor position, in the colours given by the text's [`ColourSpan`]s.
+    ///
+    /// ### Params:
+    /// * text: the whole text, with any markup removed
+    /// * part: the part of `text` to render. This must be a slice of `text`, not just equal to part of it.
+    /// * `colour_spans`: the [`ColourSpan`]s of `text`
+    fn render_coloured(&mut self, text: &str, part: &str, colour_spans: &[ColourSpan]) -> Result<(), Error> {
+        // Get where the part is in the text, so that it can be compared to the spans
+        let part_start = part.as_ptr() as usize - text.as_ptr() as usize;
+        let part_end = part_start + part.len();
+
+        // Where in the text has been rendered up to
+        let mut position = part_start;
+
+        for span in colour_spans {
+            if span.end <= position || span.start >= part_end {
+                continue;
+            }
+
+            let span_start = span.start.max(position);
+            let span_end = span.end.min(part_end);
+
+            write!(self.stdout, "{}", expand_tabs(&text[position..span_start]))?;
+
+            if let Some(colour) = self.theme.text_colour(span.colour) {
+                write!(
+                    self.stdout,
+                    "{}{}{}",
+                    color::Fg(colour),
+                    expand_tabs(&text[span_start..span_end]),
+                    color::Fg(color::Reset)
+                )?;
+            } else {
+                write!(self.stdout, "{}", expand_tabs(&text[span_start..span_end]))?;
+            }
+
+            position = span_end;
+        }
+
+        write!(self.stdout, "{}", expand_tabs(&text[position..part_end]))?;
+
+        Ok(())
+    }
+
     /// Renders a given number of graphemes from a string.
     ///
     /// ### Params:
-    /// * text: the text to render from
+    /// * text: the text to render from, with any markup removed
+    /// * `colour_spans`: the [`ColourSpan`]s of the text
     /// * graphemes: the number of characters to render
     /// * layout: a reference to cache the generated [`TextLayout`]
+    /// * `top_line`: the number of lines to leave empty above the text, e.g. for a banner
     ///
     /// ### Panics:
     /// * If the terminal is too small, based on if [`get_size_checked`] fails
     pub(super) fn render_graphemes_from_str<'a: 'b, 'b>(
         &mut self,
         text: &'a str,
+        colour_spans: &[ColourSpan],
         graphemes: usize,
         layout: &'b mut TextLayout<'a>,
+        top_line: usize,
     ) -> Result<(), Error> {
         // Get the size of the terminal
         let (w, h) = get_size_checked().unwrap();
 
         // Calculate the maximum width and height
         let max_width = (w - LEFT_OFFSET - RIGHT_OFFSET - 1) as usize;
-        let max_lines = (h - TOP_OFFSET - BOTTOM_OFFSET) as usize;
+        let max_lines = (h - TOP_OFFSET - BOTTOM_OFFSET) as usize - top_line;
 
         // Regenerate layout if it was generated for a different width
         if layout.max_width != max_width {
@@ -356,8 +737,8 @@ impl Tui {
         {
             // If the whole line must be printed
             if render_all_lines || layout_line != needed_lines - 1 {
-                self.move_cursor(0, screen_line)?;
-                write!(self.stdout, "{}", line.content)?;
+                self.move_cursor(0, screen_line + top_line)?;
+                self.render_coloured(text, line.content, colour_spans)?;
 
                 // Print dash for words split over multiple lines
                 if line.dash_at_end {
@@ -371,8 +752,8 @@ impl Tui {
                     .grapheme_indices(true)
                     .nth(graphemes - graphemes_so_far)
                     .unwrap();
-                self.move_cursor(0, screen_line)?;
-                write!(self.stdout, "{}", &line.content[..end_index])?;
+                self.move_cursor(0, screen_line + top_line)?;
+                self.render_coloured(text, &line.content[..end_index], colour_spans)?;
             }
         }
 
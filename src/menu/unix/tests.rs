@@ -1,6 +1,14 @@
 #![cfg(test)]
 
-use super::text_layout::TextLayout;
+use super::consts::TAB_WIDTH;
+use super::demo::Demo;
+use super::frame_rate::FrameRate;
+use super::{nearest_enabled, scroll_speed_for_length, ScrollSpeed};
+use super::input::{decode_input, parse_keys, InputBuffer, Key, OptionNumberInput};
+use super::parse_markup;
+use super::text_layout::{display_width, TextLayout};
+use super::theme::Theme;
+use crate::menu::markup::Colour;
 
 /// Test normal text formatting
 #[test]
@@ -85,4 +93,323 @@ fn test_line_wrapping_with_special_chars() {
     assert_eq!(layout.lines[3].content, "AB̈😀".repeat(25));
     assert!(!layout.lines[3].dash_at_end);
 
-}
\ No newline at end of file
+}
+
+/// Test that blank lines are kept for paragraph breaks
+#[test]
+fn test_blank_lines() {
+    let layout = TextLayout::new("a\n\nb", 50);
+
+    assert_eq!(layout.lines.len(), 3);
+    assert_eq!(layout.lines[0].content, "a");
+    assert_eq!(layout.lines[1].content, "");
+    assert_eq!(layout.lines[1].length, 0);
+    assert!(!layout.lines[1].dash_at_end);
+    assert_eq!(layout.lines[2].content, "b");
+}
+
+/// Test that tabs are counted as multiple columns, so lines containing them don't go past the maximum width
+#[test]
+fn test_tabs() {
+    assert_eq!(display_width("a\tb"), 6);
+
+    // A tab can't be split, so widths too narrow to fit one next to a dash aren't tested
+    for max_width in TAB_WIDTH + 1..10 {
+        let layout = TextLayout::new("a\tb a\tb", max_width);
+
+        for line in &layout.lines {
+            let dash_width = usize::from(line.dash_at_end);
+            assert!(display_width(line.content) + dash_width <= max_width);
+        }
+    }
+}
+
+
+/// Test a word which exactly fills the line, at the start of a line and after some other text
+#[test]
+fn test_word_filling_line() {
+    let word = "A".repeat(10);
+
+    let layout = TextLayout::new(&word, 10);
+    assert_eq!(layout.lines.len(), 1);
+    assert_eq!(layout.lines[0].content, word);
+    assert!(!layout.lines[0].dash_at_end);
+
+    let text = format!("ab {word}");
+    let layout = TextLayout::new(&text, 10);
+    assert_eq!(layout.lines.len(), 2);
+    assert_eq!(layout.lines[0].content, "ab");
+    assert!(!layout.lines[0].dash_at_end);
+    assert_eq!(layout.lines[1].content, word);
+    assert!(!layout.lines[1].dash_at_end);
+}
+
+/// Test a word which is one column too long for the line, at the start of a line and after some other text
+#[test]
+fn test_word_overfilling_line() {
+    let word = "A".repeat(11);
+
+    let layout = TextLayout::new(&word, 10);
+    assert_eq!(layout.lines.len(), 2);
+    assert_eq!(layout.lines[0].content, "A".repeat(9));
+    assert!(layout.lines[0].dash_at_end);
+    assert_eq!(layout.lines[1].content, "AA");
+    assert!(!layout.lines[1].dash_at_end);
+
+    let text = format!("ab {word}");
+    let layout = TextLayout::new(&text, 10);
+    assert_eq!(layout.lines.len(), 3);
+    assert_eq!(layout.lines[0].content, "ab");
+    assert!(!layout.lines[0].dash_at_end);
+    assert_eq!(layout.lines[1].content, "A".repeat(9));
+    assert!(layout.lines[1].dash_at_end);
+    assert_eq!(layout.lines[2].content, "AA");
+    assert!(!layout.lines[2].dash_at_end);
+}
+
+/// Test that words after a wrapped or hyphenated word still fit within the line
+#[test]
+fn test_words_after_wrapping() {
+    let layout = TextLayout::new("abcdefgh ijklm nopqr", 10);
+    assert_eq!(layout.lines[0].content, "abcdefgh");
+    assert_eq!(layout.lines[1].content, "ijklm");
+    assert_eq!(layout.lines[2].content, "nopqr");
+
+    let layout = TextLayout::new("AAAAAAAAAAAA bc de", 10);
+    assert_eq!(layout.lines[0].content, "A".repeat(9));
+    assert!(layout.lines[0].dash_at_end);
+    assert_eq!(layout.lines[1].content, "AAA bc de");
+    assert!(!layout.lines[1].dash_at_end);
+}
+
+/// Test that words which already contain a hyphen are broken after it rather than having another dash added
+#[test]
+fn test_wrapping_at_existing_hyphen() {
+    let layout = TextLayout::new("A time-bending jet", 10);
+
+    assert_eq!(layout.lines.len(), 3);
+    assert_eq!(layout.lines[0].content, "A time-");
+    assert!(!layout.lines[0].dash_at_end);
+    assert_eq!(layout.lines[1].content, "bending");
+    assert!(!layout.lines[1].dash_at_end);
+    assert_eq!(layout.lines[2].content, "jet");
+    assert!(!layout.lines[2].dash_at_end);
+}
+
+/// Test that colour markup isn't counted when wrapping text
+#[test]
+fn test_markup_wrapping() {
+    let plain_text = "The Cook hit you with their Standard Blaster and dealt 3 damage. ".repeat(5);
+    let marked_up_text = "The {red}Cook{/} hit you with their {cyan}Standard Blaster{/} and dealt {yellow}3{/} damage. ".repeat(5);
+
+    let (parsed_text, _) = parse_markup(&marked_up_text);
+    assert_eq!(parsed_text, plain_text);
+
+    let plain_layout = TextLayout::new(&plain_text, 30);
+    let marked_up_layout = TextLayout::new(&parsed_text, 30);
+
+    assert_eq!(plain_layout.lines.len(), marked_up_layout.lines.len());
+    for (plain_line, marked_up_line) in plain_layout.lines.iter().zip(&marked_up_layout.lines) {
+        assert_eq!(plain_line.content, marked_up_line.content);
+        assert_eq!(plain_line.length, marked_up_line.length);
+        assert_eq!(plain_line.dash_at_end, marked_up_line.dash_at_end);
+    }
+}
+
+/// Tests that theme names are parsed ignoring case
+#[test]
+fn test_theme_from_name() {
+    assert_eq!(Theme::from_name("default"), Some(Theme::Default));
+    assert_eq!(Theme::from_name("Cyan"), Some(Theme::Cyan));
+    assert_eq!(Theme::from_name("High-Contrast"), Some(Theme::HighContrast));
+    assert_eq!(Theme::from_name("MONOCHROME"), Some(Theme::Monochrome));
+    assert_eq!(Theme::from_name("rainbow"), None);
+}
+
+/// Tests that [`Theme::Monochrome`] doesn't write any colour escapes
+#[test]
+fn test_monochrome_theme_has_no_colour() {
+    let theme = Theme::Monochrome;
+
+    for escape in [theme.border_style(), theme.selected_style()] {
+        assert!(!escape.contains("38;"), "{escape:?} sets a foreground colour");
+        assert!(!escape.contains("48;"), "{escape:?} sets a background colour");
+    }
+
+    assert!(!theme.uses_colour());
+    assert!(theme.text_colour(Colour::Red).is_none());
+    assert!(Theme::Default.text_colour(Colour::Red).is_some());
+}
+
+/// Tests that the default theme leaves the border uncoloured, and the cyan theme colours it
+#[test]
+fn test_default_theme_border_is_uncoloured() {
+    assert_eq!(Theme::default().border_style(), "");
+    assert!(Theme::Cyan.border_style().contains("38;"));
+}
+
+/// Tests that input containing several keys is split into one key per key press
+#[test]
+fn test_parse_keys_splits_input() {
+    assert_eq!(parse_keys("\x1b[B\x1b[B\x1b[B"), [Key::Down; 3]);
+    assert_eq!(parse_keys("jk\r"), [Key::Char('j'), Key::Char('k'), Key::Enter]);
+    // A lone escape is its own key, even if followed by something else
+    assert_eq!(parse_keys("\x1b\x1bq"), [Key::Escape, Key::Escape, Key::Char('q')]);
+    assert!(parse_keys("").is_empty());
+}
+
+/// Tests that input which isn't valid UTF-8 has the invalid bytes dropped, rather than panicking, and the rest is still parsed
+#[test]
+fn test_decode_invalid_input() {
+    assert_eq!(decode_input(b"jk\r"), "jk\r");
+    assert_eq!(decode_input("é".as_bytes()), "é");
+    assert_eq!(decode_input(b""), "");
+
+    // Bytes which can never be valid
+    assert_eq!(decode_input(&[0xff, b'q', 0xfe]), "q");
+    // A character cut off at the end of the buffer, or the rest of it at the start of the next read
+    assert_eq!(decode_input(&[b'j', 0xc3]), "j");
+    assert_eq!(decode_input(&[0xa9, b'k']), "k");
+    // The keys around an invalid byte are still parsed
+    assert_eq!(parse_keys(&decode_input(&[0x1b, b'[', b'B', 0x80, b'\r'])), [Key::Down, Key::Enter]);
+}
+
+/// Tests that an escape sequence split between two reads is joined back together into one key,
+/// and that a lone escape is still the escape key once nothing more arrives
+#[test]
+fn test_split_escape_sequences() {
+    let mut buffer = InputBuffer::default();
+    assert!(buffer.parse(Some("\x1b")).is_empty());
+    assert_eq!(buffer.parse(Some("[A")), [Key::Up]);
+
+    // The keys before the cut-off sequence aren't held back
+    assert_eq!(buffer.parse(Some("j\x1b[5")), [Key::Char('j')]);
+    assert_eq!(buffer.parse(Some("~k")), [Key::PageUp, Key::Char('k')]);
+    assert_eq!(buffer.parse(Some("\x1bO")), []);
+    assert_eq!(buffer.parse(Some("H")), [Key::Home]);
+
+    assert!(buffer.parse(Some("\x1b")).is_empty());
+    assert_eq!(buffer.parse(None), [Key::Escape]);
+    assert!(buffer.parse(None).is_empty());
+}
+
+/// Tests parsing the escapes produced by arrow and navigation keys
+#[test]
+fn test_parse_keys_escapes() {
+    assert_eq!(parse_keys("\x1b[A"), [Key::Up]);
+    assert_eq!(parse_keys("\x1b[B"), [Key::Down]);
+    assert_eq!(parse_keys("\x1b[5~\x1b[6~"), [Key::PageUp, Key::PageDown]);
+    assert_eq!(parse_keys("\x1b[H\x1b[1~\x1bOH"), [Key::Home; 3]);
+    assert_eq!(parse_keys("\x1b[F\x1b[4~\x1bOF"), [Key::End; 3]);
+    // Unknown escapes are still split off as one key
+    assert_eq!(parse_keys("\x1b[1;5Cx"), [Key::Other, Key::Char('x')]);
+}
+
+/// Tests parsing enter, backspace and plain characters
+#[test]
+fn test_parse_keys_characters() {
+    assert_eq!(parse_keys("\r\n"), [Key::Enter, Key::Enter]);
+    assert_eq!(parse_keys("\x7f\x08"), [Key::Backspace, Key::Backspace]);
+    assert_eq!(parse_keys("aé?"), [Key::Char('a'), Key::Char('é'), Key::Char('?')]);
+    assert_eq!(parse_keys("\x01"), [Key::Other]);
+}
+
+/// Tests that demo mode picks options from its script, then at random without quitting or picking disabled options
+#[test]
+fn test_demo_choices() {
+    let mut demo = Demo::new(1000, "Go to the Bridge\n\nNot an option\nTake off\n");
+    let items = ["Look around the room again", "Go to the Bridge", "Quit the game"];
+    let enabled = [true; 3];
+
+    assert_eq!(demo.choose(&items, &enabled), 1);
+
+    // A scripted option which isn't in the list is skipped, and the rest are picked at random
+    assert_ne!(demo.choose(&items, &enabled), 2);
+
+    // So is a scripted option which is disabled, and random picks never pick disabled options either
+    let items = ["Take off (you need in-date maps)", "Look around the room again", "Quit the game"];
+    for _ in 0..50 {
+        assert_eq!(demo.choose(&items, &[false, true, true]), 1);
+    }
+
+    // If quitting is the only option, it is picked
+    assert_eq!(demo.choose(&["Quit the game"], &[true]), 0);
+}
+
+/// Tests that the frame rate drops while the screen is idle, and that frames are never shorter than a millisecond
+#[test]
+fn test_idle_frame_rate() {
+    let frame_rate = FrameRate::new(60).unwrap();
+    assert_eq!(frame_rate.frame_ms(true), 16);
+    assert_eq!(frame_rate.frame_ms(false), 500);
+
+    // Frames longer than the idle timeout are used all the time
+    let slow = FrameRate::new(1).unwrap();
+    assert_eq!(slow.frame_ms(true), 1000);
+    assert_eq!(slow.frame_ms(false), 1000);
+
+    // Frames are never shorter than a millisecond
+    assert_eq!(FrameRate::new(5000).unwrap().frame_ms(true), 1);
+    assert_eq!(FrameRate::new(0), None);
+}
+
+/// Tests that screens longer than [`SCROLL_SPEEDUP_THRESHOLD`] scroll faster, up to [`MAX_SCROLL_SPEEDUP`] times the normal speed
+#[test]
+fn test_long_screens_scroll_faster() {
+    let speed = ScrollSpeed::CharsPerSecond(50);
+
+    // Short screens, even a single word, scroll at the normal speed
+    assert_eq!(scroll_speed_for_length(speed, 5), speed);
+    assert_eq!(scroll_speed_for_length(speed, 250), speed);
+
+    // Longer screens take the same time as the threshold, until they reach the maximum speedup
+    assert_eq!(scroll_speed_for_length(speed, 500), ScrollSpeed::CharsPerSecond(100));
+    assert_eq!(scroll_speed_for_length(speed, 1000), ScrollSpeed::CharsPerSecond(200));
+    assert_eq!(scroll_speed_for_length(speed, 5000), ScrollSpeed::CharsPerSecond(200));
+
+    // A huge speed doesn't overflow, and is never slowed down
+    let fast = ScrollSpeed::CharsPerSecond(u64::MAX / 2);
+    assert_eq!(scroll_speed_for_length(fast, 5000), fast);
+
+    assert_eq!(scroll_speed_for_length(ScrollSpeed::Instant, 5000), ScrollSpeed::Instant);
+}
+
+#[test]
+fn test_option_number_input() {
+    let mut input = OptionNumberInput::default();
+
+    // Digits build up a number while it still refers to an option
+    assert_eq!(input.push('1', 15), Some(0));
+    assert_eq!(input.push('2', 15), Some(11));
+
+    // A digit which would make the number too big starts a new number
+    assert_eq!(input.push('3', 15), Some(2));
+    assert_eq!(input.push('0', 15), None);
+    assert_eq!(input.push('7', 15), Some(6));
+
+    // Clearing the input means the next digit starts a new number
+    input.clear();
+    assert_eq!(input.push('1', 15), Some(0));
+    input.clear();
+    assert_eq!(input.push('0', 15), None);
+    assert_eq!(input.push('9', 5), None);
+}
+
+/// Tests that jumping around a list lands on the nearest option which can be chosen
+#[test]
+fn test_nearest_enabled() {
+    let enabled = [false, true, false, false, true, false];
+
+    assert_eq!(nearest_enabled(&enabled, 0), 1);
+    assert_eq!(nearest_enabled(&enabled, 1), 1);
+    assert_eq!(nearest_enabled(&enabled, 2), 1);
+    // Ties go to the later option
+    assert_eq!(nearest_enabled(&enabled, 3), 4);
+    // Past the end of the list counts as the last option
+    assert_eq!(nearest_enabled(&enabled, 5), 4);
+    assert_eq!(nearest_enabled(&enabled, 10), 4);
+
+    // With nothing to choose, the selection stays where it is
+    assert_eq!(nearest_enabled(&[false, false], 1), 1);
+    assert_eq!(nearest_enabled(&[], 0), 0);
+}
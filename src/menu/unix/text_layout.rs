@@ -5,6 +5,19 @@ use unicode_width::UnicodeWidthStr;
 
 use super::consts::*;
 
+/// Gets the number of columns some text takes up when rendered.
+/// This is the same as [`UnicodeWidthStr::width`], except that tabs are counted as [`TAB_WIDTH`] columns.
+pub(super) fn display_width(text: &str) -> usize {
+    text.graphemes(true)
+        .map(|g| if g == "\t" { TAB_WIDTH } else { g.width() })
+        .sum()
+}
+
+/// Replaces tabs in some text with [`TAB_WIDTH`] spaces, so that it takes up the number of columns given by [`display_width`]
+pub(super) fn expand_tabs(text: &str) -> String {
+    text.replace('\t', &" ".repeat(TAB_WIDTH))
+}
+
 /// One line of text in the formatted output
 #[derive(Debug)]
 pub(super) struct TextLine<'a> {
@@ -19,15 +32,26 @@ pub(super) struct TextLine<'a> {
 /// The formatted layout some text
 #[derive(Debug)]
 pub(super) struct TextLayout<'a> {
-    /// The maximum length of a line. This is measured in columns using [`unicode_width::UnicodeWidthStr`], not in characters or graphemes.
+    /// The maximum length of a line. This is measured in columns using [`display_width`], not in characters or graphemes.
     pub(super) max_width: usize,
     /// The formatted lines
     pub(super) lines: Vec<TextLine<'a>>,
 }
 
 impl<'a> TextLayout<'a> {
-    /// Adds a source line to the layout. The line will be wrapped, so it may span multiple render lines
+    /// Adds a source line to the layout. The line will be wrapped, so it may span multiple render lines.
+    /// Blank lines are kept as empty render lines, so that paragraph breaks are preserved.
+    #[allow(clippy::too_many_lines)]
     fn add_source_line(&mut self, line: &'a str) {
+        if line.is_empty() {
+            self.lines.push(TextLine {
+                content: line,
+                dash_at_end: false,
+                length: 0,
+            });
+            return;
+        }
+
         // The x position of the end of the current render line
         let mut x = 0;
         // Points to the first char in the current render line
@@ -39,7 +63,7 @@ impl<'a> TextLayout<'a> {
 
         for word in line.split(' ') {
             // The display width of the word
-            let width = word.width();
+            let width = display_width(word);
 
             // If the word fits on the current line
             if x + width <= self.max_width {
@@ -72,8 +96,13 @@ impl<'a> TextLayout<'a> {
             let first_segment_long_enough = width_left >= TEXT_WRAPPING_MIN_SEGMENT_SIZE;
             // If the size of the lower segment would be big enough
             let last_segment_long_enough = (width - width_left) >= TEXT_WRAPPING_MIN_SEGMENT_SIZE;
+            // Whether the word already contains a hyphen which it could be broken after on the current line
+            let has_hyphen_break = word
+                .match_indices('-')
+                .any(|(i, _)| x + display_width(&word[..=i]) <= self.max_width);
             // Whether the word could be hyphenated
-            let could_hyphenate = first_segment_long_enough && last_segment_long_enough;
+            let could_hyphenate =
+                (first_segment_long_enough && last_segment_long_enough) || has_hyphen_break;
 
             // Whether to hyphenate the word
             let render_hyphen = needs_hyphen || could_hyphenate;
@@ -98,7 +127,6 @@ impl<'a> TextLayout<'a> {
                     current_render_line_end += 1;
                     current_render_line_start = current_render_line_end;
                     x = 0;
-                    is_line_start = true;
 
                     word_start_index = current_render_line_end;
                 } else if !is_line_start {
@@ -107,30 +135,58 @@ impl<'a> TextLayout<'a> {
                     word_start_index = current_render_line_end;
                 }
 
+                // The width of the previous grapheme in the word
+                let mut prev_g_width = 0;
+
                 // Loop through the graphemes
                 for (i, g) in word.grapheme_indices(true) {
-                    let g_width = g.width();
+                    let g_width = display_width(g);
 
                     x += g_width;
 
                     // If the grapheme would go over the end of the line, hyphenate and go to the next line
                     if x > self.max_width {
-                        let content = &line[current_render_line_start..current_render_line_end];
-                        self.lines.push(TextLine {
-                            content,
-                            dash_at_end: true,
-                            length: content.graphemes(true).count(),
-                        });
-
-                        current_render_line_start = current_render_line_end;
-                        x = g_width + 1;
+                        // The part of the word which is on the current line
+                        let word_on_line_start = current_render_line_start.max(word_start_index);
+                        let word_on_line = &line[word_on_line_start..word_start_index + i];
+
+                        // Prefer to break after a hyphen which is already in the word, so no dash needs to be added
+                        if let Some(hyphen_index) = word_on_line.rfind('-') {
+                            let break_index = word_on_line_start + hyphen_index + 1;
+
+                            let content = &line[current_render_line_start..break_index];
+                            self.lines.push(TextLine {
+                                content,
+                                dash_at_end: false,
+                                length: content.graphemes(true).count(),
+                            });
+
+                            current_render_line_start = break_index;
+                            x = display_width(&line[break_index..word_start_index + i]) + g_width;
+                        }
+                        // Otherwise, the previous grapheme is moved to the next line to leave space for the dash
+                        else {
+                            let content = &line[current_render_line_start..current_render_line_end];
+                            self.lines.push(TextLine {
+                                content,
+                                dash_at_end: true,
+                                length: content.graphemes(true).count(),
+                            });
+
+                            current_render_line_start = current_render_line_end;
+                            x = prev_g_width + g_width;
+                        }
                     }
 
                     current_render_line_end = word_start_index + i;
+                    prev_g_width = g_width;
                 }
 
                 // Update end pointer to point past the end of the string
                 current_render_line_end = word_start_index + word.len();
+                // + 1 to account for the space after the word, as when the word fits on the line
+                x += 1;
+                is_line_start = false;
             }
             // If the word does not need to be hyphenated
             else {
@@ -147,7 +203,8 @@ impl<'a> TextLayout<'a> {
                 current_render_line_start = current_render_line_end + 1;
                 current_render_line_end += word.len() + 1;
 
-                x = width;
+                // + 1 to account for the space after the word, as when the word fits on the line
+                x = width + 1;
             }
         }
 
@@ -0,0 +1,98 @@
+//! The colour themes which the TUI can be shown in
+
+use termion::{color, style};
+
+use crate::menu::markup::Colour;
+
+/// The name of the environment variable which sets the [`Theme`]
+const THEME_VAR: &str = "TUI_THEME";
+
+/// How the border and the selected option of a list are styled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum Theme {
+    /// An uncoloured border, with the selected option shown inverted
+    #[default]
+    Default,
+    /// A cyan border, with the selected option shown inverted
+    Cyan,
+    /// A bold bright white border, with the selected option shown in bold black on bright yellow
+    HighContrast,
+    /// No colours at all, for terminals which don't support them. The selected option is shown inverted.
+    Monochrome,
+}
+
+impl Theme {
+    /// Gets the [`Theme`] with the given name, ignoring case.
+    /// Returns [`None`] if there is no theme with that name.
+    pub(super) fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+
+        match name.as_str() {
+            "default" => Some(Self::Default),
+            "cyan" => Some(Self::Cyan),
+            "high-contrast" => Some(Self::HighContrast),
+            "monochrome" => Some(Self::Monochrome),
+            _ => None,
+        }
+    }
+
+    /// Reads the [`Theme`] from the `TUI_THEME` environment variable.
+    /// Returns [`None`] if the variable is not set or is not valid.
+    pub(super) fn from_env() -> Option<Self> {
+        Self::from_name(&std::env::var(THEME_VAR).ok()?)
+    }
+
+    /// Whether text in a [`Screen`][crate::menu::Screen]'s content should be shown in the [`Colour`]s given by its markup
+    pub(super) const fn uses_colour(self) -> bool {
+        !matches!(self, Self::Monochrome)
+    }
+
+    /// Gets the [`termion`] colour to use for a [`Colour`], or [`None`] if the theme doesn't use colour
+    pub(super) fn text_colour(self, colour: Colour) -> Option<&'static dyn color::Color> {
+        if !self.uses_colour() {
+            return None;
+        }
+
+        Some(match colour {
+            Colour::Red => &color::Red,
+            Colour::Green => &color::Green,
+            Colour::Yellow => &color::Yellow,
+            Colour::Blue => &color::Blue,
+            Colour::Magenta => &color::Magenta,
+            Colour::Cyan => &color::Cyan,
+        })
+    }
+
+    /// Gets the escapes to write before the border. The border should be followed by [`Theme::reset_style`].
+    pub(super) fn border_style(self) -> String {
+        match self {
+            Self::Cyan => color::Fg(color::Cyan).to_string(),
+            Self::HighContrast => format!("{}{}", style::Bold, color::Fg(color::LightWhite)),
+            Self::Default | Self::Monochrome => String::new(),
+        }
+    }
+
+    /// Gets the escapes to write before the selected option of a list. The option should be followed by [`Theme::reset_style`].
+    pub(super) fn selected_style(self) -> String {
+        match self {
+            Self::Default | Self::Cyan | Self::Monochrome => style::Invert.to_string(),
+            Self::HighContrast => format!(
+                "{}{}{}",
+                style::Bold,
+                color::Fg(color::Black),
+                color::Bg(color::LightYellow)
+            ),
+        }
+    }
+
+    /// Gets the escapes to write before a disabled option of a list, after any [`Theme::selected_style`].
+    /// The option should be followed by [`Theme::reset_style`].
+    pub(super) fn disabled_style() -> String {
+        style::Faint.to_string()
+    }
+
+    /// Gets the escape which undoes [`Theme::border_style`], [`Theme::selected_style`] and [`Theme::disabled_style`]
+    pub(super) fn reset_style() -> String {
+        style::Reset.to_string()
+    }
+}
@@ -2,59 +2,231 @@
 
 mod tests;
 
-use crate::combat::{self, Health};
+use crate::combat::{self, BattleResult, Damage, Enemy, Health, StatusEffect};
 use crate::config::{self, STARTING_ROOM};
-use crate::items::Item;
-use crate::map;
-use crate::menu::{Menu, OptionList, Screen};
+use crate::difficulty::Difficulty;
+use crate::effects::Effects;
+use crate::items::{take_one_food, Item, Weapon};
+use crate::map::{self, RoomTrigger};
+use crate::menu::{Menu, MenuSignal, OptionList, Screen, Status};
 use crate::rooms::{Room, RoomGraph, RoomState, RoomTransition};
+use crate::settings;
+use crate::strings;
 
 /// The state of the player
 #[derive(Debug)]
 pub struct Player {
     /// Which [`Room`] the [`Player`] is in
     pub room: Room,
+    /// Which [`Room`] the [`Player`] was in before the current one, if they have moved this loop
+    pub previous_room: Option<Room>,
     /// The [`Player`]'s inventory
     pub inventory: Vec<Item>,
     /// The [`Player`]'s current health
     pub health: Health,
     /// The maximum health the [`Player`] can reach
     pub max_health: Health,
+    /// How many spaces the [`Player`]'s inventory has, or [`None`] for no limit
+    pub max_inventory_size: Option<usize>,
+    /// The [`StatusEffect`]s currently affecting the [`Player`]. These are only applied in battle.
+    pub status_effects: Vec<StatusEffect>,
+    /// The weapons the [`Player`] has [thrown][combat::Action::Throw] in the current battle, which can be recovered once it ends
+    pub thrown_weapons: Vec<Item>,
+    /// The number of upcoming turns of the current battle on which the [`Player`] is [stunned][Player::stun] and can't act
+    pub stunned_turns: usize,
     /// The number of turns the user has left before the loop resets
     pub remaining_turns: usize,
+    /// The number of enemies the [`Player`] has defeated this run. Unlike most of the state, this is kept between loops.
+    pub enemies_defeated: usize,
+    /// The number of items the [`Player`] has picked up this run. Unlike most of the state, this is kept between loops.
+    pub items_collected: usize,
+
+    /// The [`Difficulty`] the game is being played on. This is kept between loops.
+    pub difficulty: Difficulty,
+
+    /// What would be needed to undo the [`Player`]'s last action, or [`None`] if there's nothing to undo
+    pub last_action: Option<LastAction>,
+    /// How many [turns had been taken][Player::turns_taken] when the [`Player`] last got a hint this loop, if they have
+    pub last_hint_turn: Option<usize>,
+    /// How many [turns had been taken][Player::turns_taken] when the [`Player`] last [recovered health][config::HEALTH_REGEN] this loop
+    pub last_regen_turn: usize,
 
     /// The current state of the rooms
     pub room_graph: RoomGraph,
 }
 
+/// The [`Player`]'s last action which took time, kept so that it can be undone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LastAction {
+    /// The [`Player`] moved between rooms. This holds where they were before, so that they can go back.
+    Movement {
+        /// The [`Room`] the [`Player`] was in before moving
+        room: Room,
+        /// The [`Player`]'s [`previous_room`][Player::previous_room] before moving
+        previous_room: Option<Room>,
+        /// The [trigger][RoomState::on_enter] of the room moved into, if moving fired it. It's put back if the move is undone.
+        fired_trigger: Option<RoomTrigger>,
+    },
+    /// The action changed something which can't be put back, such as taking an item or starting a fight.
+    /// This holds the [key][strings::get] of a message explaining why it can't be undone.
+    Irreversible(&'static str),
+}
+
 /// An action the [`Player`] can take outside of a battle
 #[derive(Debug)]
-enum PassiveAction<'a> {
+enum PassiveAction {
     /// Print the [`Player`]'s health
     CheckState,
-    /// Go to a [`Room`] which is connected to the current one
-    GoToRoom(&'a RoomTransition),
+    /// Show the description of the current [`Room`] again
+    LookAround,
+    /// Open the [settings menu][settings::open_settings_menu]
+    OpenMenu,
+    /// Go through the [`RoomTransition`] at the given index into the [current room's connections][RoomState::connections],
+    /// after fighting its [guard][RoomTransition::guard] if it has one
+    GoToRoom(usize),
     /// Use the [`Item`] at the given index into the [player's inventory][Player::inventory]
     UseItem(usize),
+    /// Read the [captain's diary][Item::CaptainsDiary] at the given index into the [player's inventory][Player::inventory]
+    ReadDiary(usize),
+    /// Pick one of the [player's weapons][Player::inventory] to see its description and stats
+    InspectWeapon,
+    /// Show a [table][Player::weapon_table] comparing the stats of all the [player's weapons][Player::inventory]
+    CompareWeapons,
+    /// Show a [hint][next_hint] about what the [`Player`] should do next
+    GetHint,
     /// Add the [`Item`] at the given index into the [current room's inventory][RoomState::items] to the [player's inventory][Player::inventory]
     PickUpItem(usize),
     /// Carry out the [`RoomAction`][crate::map::RoomAction] at the given index into the [current room's actions][RoomState::actions]
     RoomAction(usize),
+    /// Deliberately let a turn pass without doing anything, e.g. to [recover health][config::HEALTH_REGEN]
+    Wait,
+    /// Undo the [`Player`]'s [last action][Player::last_action] if it was a movement, refunding its turn
+    Undo,
+    /// Give up on the current loop and [start the next one][PassiveActionResult::ResetLoop] straight away, after confirming
+    ResetLoop,
 }
 
-/// Prints a screen with the details of a [`RoomTransition`] and the player's new [`Room`]
-fn print_room_transition(transition: &RoomTransition, menu: &mut impl Menu) {
-    let screen = Screen {
-        title: &format!("You go to the {}", transition.prompt_text.unwrap_or_else(||transition.to.get_name())),
-        content: &format!(
-            "{}\nYou are now in the {} - {}",
-            transition.message,
-            transition.to.get_name(),
-            transition.to.get_description()
-        ),
-    };
+/// What should happen after the [`Player`] takes a [`PassiveAction`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassiveActionResult {
+    /// The current loop carries on
+    Continue,
+    /// The [`Player`] gave up on the current loop, so the next one should start
+    ResetLoop,
+    /// The [`Player`] fought the [guard][RoomTransition::guard] of a door and didn't get through it.
+    /// The [`BattleResult`] should be handled the same as a battle with an enemy in a room.
+    GuardBattle(BattleResult),
+}
+
+impl PassiveAction {
+    /// Gets whether carrying out the action uses up a turn.
+    /// Actions which only show the player information are free, while actions involving movement or interaction take time.
+    const fn costs_turn(&self) -> bool {
+        !matches!(
+            self,
+            Self::CheckState
+                | Self::LookAround
+                | Self::OpenMenu
+                | Self::ReadDiary(_)
+                | Self::InspectWeapon
+                | Self::CompareWeapons
+                | Self::GetHint
+                | Self::Undo
+                | Self::ResetLoop
+        )
+    }
+
+    /// Gets the [key][strings::get] of the message explaining why the action can't be undone, or [`None`] if it can be
+    const fn irreversible_reason(&self) -> Option<&'static str> {
+        match self {
+            Self::CheckState | Self::LookAround | Self::OpenMenu | Self::GoToRoom(_) | Self::ReadDiary(_) | Self::InspectWeapon | Self::CompareWeapons | Self::GetHint | Self::Undo | Self::ResetLoop => None,
+            Self::UseItem(_) => Some("undo.reason.use_item"),
+            Self::PickUpItem(_) => Some("undo.reason.pick_up_item"),
+            Self::RoomAction(_) => Some("undo.reason.room_action"),
+            Self::Wait => Some("undo.reason.wait"),
+        }
+    }
+}
+
+/// The [keys][strings::get] of the lines shown when the [`Player`] [waits][PassiveAction::Wait].
+/// They are picked by [`wait_line`] using the turn number, so waiting is deterministic.
+const WAIT_LINE_KEYS: [&str; 6] = ["wait.0", "wait.1", "wait.2", "wait.3", "wait.4", "wait.5"];
+
+/// Gets the line to show when the [`Player`] [waits][PassiveAction::Wait] on the given turn
+fn wait_line(turn: usize) -> &'static str {
+    strings::get(WAIT_LINE_KEYS[turn % WAIT_LINE_KEYS.len()])
+}
+
+/// Gets a vague nudge towards what the [`Player`] should do next, based on what they still need to escape.
+/// The hint names the room which holds what they need, as the map may have been [randomised][map::init_randomized].
+pub fn next_hint(player: &Player) -> String {
+    let has_maps = player.inventory.iter().any(|item| matches!(item, Item::Maps));
+    let has_keys = player.inventory.iter().any(|item| matches!(item, Item::EscapePodKeys));
+
+    match (has_maps, has_keys) {
+        (false, _) => match room_with_action(&player.room_graph, |action| matches!(action, map::RoomAction::StrategyRoomTakeMaps)) {
+            Some(room) => strings::format("hint.maps.room", &[&room.get_name()]),
+            None => strings::get("hint.maps").to_string(),
+        },
+        (true, false) => match room_with_action(&player.room_graph, |action| matches!(action, map::RoomAction::EngineRoomTakeKeys)) {
+            Some(room) => strings::format("hint.keys.room", &[&room.get_name()]),
+            None => strings::get("hint.keys").to_string(),
+        },
+        (true, true) => strings::get("hint.escape_pod").to_string(),
+    }
+}
+
+/// Finds the [`Room`] whose [actions][RoomState::actions] include one matching `is_action`, if any room has one
+fn room_with_action(graph: &RoomGraph, is_action: impl Fn(&map::RoomAction) -> bool) -> Option<Room> {
+    graph.rooms.iter().find(|(_, state)| state.actions.iter().any(&is_action)).map(|(&room, _)| room)
+}
+
+/// The [key][strings::get] of the message shown when the [`Player`] tries to undo after a battle
+pub const BATTLE_IRREVERSIBLE_REASON: &str = "undo.reason.battle";
+
+/// The banner to show above the win screen - the escape pod leaving the ship
+const ESCAPE_POD_BANNER: &str = r"   _____
+  / o o \  ~ ~
+ |_______|~ ~ ~
+   \___/   ~ ~";
+
+/// The [keys][strings::get] of the title and content of each page of the [captain's diary][Item::CaptainsDiary], in the order the player reads them.
+/// The last page is read first, then the diary is read from the beginning.
+const DIARY_PAGE_KEYS: [(&str, &str); 8] = [
+    ("diary.0.title", "diary.0.content"),
+    ("diary.1.title", "diary.1.content"),
+    ("diary.2.title", "diary.2.content"),
+    ("diary.3.title", "diary.3.content"),
+    ("diary.4.title", "diary.4.content"),
+    ("diary.5.title", "diary.5.content"),
+    ("diary.6.title", "diary.6.content"),
+    ("diary.7.title", "diary.7.content"),
+];
+
+/// Gets the [`Screen`] showing the page of the [captain's diary][Item::CaptainsDiary] at the given index into [`DIARY_PAGE_KEYS`]
+fn diary_page(page: usize) -> Screen<'static> {
+    let (title, content) = DIARY_PAGE_KEYS[page];
+    Screen::new(strings::get(title), strings::get(content))
+}
+
+/// Gets a [`Room`]'s description, followed by its [ambient line][Room::ambient_line] for the given turn if it has any
+fn room_description(room: Room, turn: usize) -> String {
+    match room.ambient_line(turn) {
+        Some(line) => format!("{} {line}", room.get_description()),
+        None => room.get_description().to_string(),
+    }
+}
 
-    menu.show_screen(screen);
+/// Prints a screen with the details of a [`RoomTransition`] and the player's new [`Room`], as it is on the given turn
+fn print_room_transition(transition: &RoomTransition, turn: usize, menu: &mut impl Menu) -> Result<(), MenuSignal> {
+    menu.show_screen(Screen::new(
+        &transition.title(),
+        &strings::format(
+            "passive.transition.content",
+            &[&transition.message, &transition.to.get_name(), &room_description(transition.to, turn)],
+        ),
+    ))?;
+    Ok(())
 }
 
 impl Player {
@@ -69,95 +241,238 @@ impl Player {
     }
 
     /// Prints a screen describing the current [`RoomState`]
-    pub fn print_room(&self, menu: &mut impl Menu) {
-        let screen = Screen {
-            title: &format!("You are in the {}.", self.room.get_name()),
-            content: self.room.get_description(),
-        };
-
-        menu.show_screen(screen);
+    pub fn print_room(&self, menu: &mut impl Menu) -> Result<(), MenuSignal> {
+        menu.show_screen(Screen::new(
+            &strings::format("passive.room.title", &[&self.room.get_name()]),
+            &room_description(self.room, self.turns_taken()),
+        ))?;
+        Ok(())
     }
 
     /// Gets a [`String`] representing the number of turns left.
-    /// 1 turn = 20 sec
-    fn get_remaining_time(&self) -> String {
+    /// 1 turn = 20 sec. Turns spent in battle count towards this as well as passive turns.
+    pub fn get_remaining_time(&self) -> String {
         let mins = self.remaining_turns / 3;
         let secs = self.remaining_turns % 3 * 20;
         format!("{mins}:{secs:0<2}")
     }
 
+    /// Shows the time the [`Player`] has left as the [`Menu`]'s [status][Menu::set_status], so that it's always visible.
+    /// Once there are [few turns left][config::LOW_TIME_TURNS], the status warns that time is running out.
+    /// This should be called whenever the number of remaining turns changes.
+    pub fn show_remaining_time(&self, menu: &mut impl Menu) {
+        menu.set_status(Some(self.remaining_time_status()));
+    }
+
+    /// Gets the [`Status`] shown by [`Player::show_remaining_time`]
+    fn remaining_time_status(&self) -> Status {
+        Status {
+            text: strings::format("status.time_left", &[&self.get_remaining_time()]),
+            warning: (self.remaining_turns <= config::LOW_TIME_TURNS).then(|| strings::get("status.time_running_out").to_string()),
+        }
+    }
+
+    /// Heals the [`Player`] by [`config::HEALTH_REGEN`] if it has been [enough turns][config::HEALTH_REGEN_TURNS] since they last recovered health.
+    /// This should only be called after a passive turn, so that health never comes back in the middle of a fight.
+    /// Undoing a movement refunds its turn, so moving back and forth can't be used to recover health for free.
+    ///
+    /// ### Returns:
+    /// How much the [`Player`] was healed by, which is 0 if it wasn't time to recover yet or they were at full health
+    fn regenerate_health(&mut self) -> Damage {
+        if self.turns_taken() < self.last_regen_turn + config::HEALTH_REGEN_TURNS {
+            return Damage::new(0);
+        }
+
+        self.last_regen_turn = self.turns_taken();
+        self.health.heal_to_max(config::HEALTH_REGEN, self.max_health)
+    }
+
     /// Asks the user what [`PassiveAction`] to perform given the [`Player`]'s inventory and the current [`RoomState`]
-    fn choose_passive_action(&self, menu: &mut impl Menu) -> PassiveAction {
+    fn choose_passive_action(&self, menu: &mut impl Menu) -> Result<PassiveAction, MenuSignal> {
         // Init lists of options and their string representations
-        let mut options = vec![PassiveAction::CheckState];
-        let mut options_str = vec!["Check how you're doing".to_string()];
+        let mut options = vec![
+            PassiveAction::CheckState,
+            PassiveAction::LookAround,
+            PassiveAction::OpenMenu,
+        ];
+        let mut options_str = vec![
+            strings::get("passive.option.check_state").to_string(),
+            strings::get("passive.option.look_around").to_string(),
+            strings::get("passive.option.open_menu").to_string(),
+        ];
 
         let room_state = self.get_room_state();
 
-        for connection in &room_state.connections {
-            options.push(PassiveAction::GoToRoom(connection));
-            options_str.push(format!(
-                "Go to the {}",
-                connection.prompt_text.unwrap_or_else(||connection.to.get_name())
-            ));
+        for (i, connection) in room_state.connections.iter().enumerate() {
+            options.push(PassiveAction::GoToRoom(i));
+            options_str.push(connection.option_text());
         }
 
         for (i, item) in room_state.items.iter().enumerate() {
             options.push(PassiveAction::PickUpItem(i));
-            options_str.push(format!(
-                "Pick up the {} - {}",
-                item.get_name(),
-                item.get_description()
-            ));
+            options_str.push(strings::format("passive.option.pick_up", &[&item.get_name(), &item.get_description()]));
         }
 
+        // Actions which can't be taken yet are shown disabled, so the player knows they're there
+        let mut disabled = Vec::new();
         for (i, action) in room_state.actions.iter().enumerate() {
+            if let Some(reason) = action.unavailable_reason(self) {
+                disabled.push((options.len(), reason));
+            }
             options.push(PassiveAction::RoomAction(i));
             options_str.push(action.get_description().to_string());
         }
 
         for (i, item) in self.inventory.iter().enumerate() {
             match item {
-                Item::Food(f) => {
+                Item::Food(_) => {
                     options.push(PassiveAction::UseItem(i));
-                    options_str.push(format!("Eat your {}", f.name));
+                    options_str.push(strings::format("passive.option.eat", &[&item.get_display_name()]));
                 }
-                Item::CaptainsDiary(_) => {
+                Item::Upgrade(u) => {
                     options.push(PassiveAction::UseItem(i));
-                    options_str.push("Read the captain's diary".to_string());
+                    options_str.push(strings::format("passive.option.use_upgrade", &[&u.name]));
+                }
+                Item::CaptainsDiary(_) => {
+                    options.push(PassiveAction::ReadDiary(i));
+                    options_str.push(strings::get("passive.option.read_diary").to_string());
                 }
                 _ => ()
             }
         }
 
-        let prompt = format!("{} - What do you do?", self.get_remaining_time());
-        let option_list = OptionList::new(&options_str, &prompt);
+        options.push(PassiveAction::Wait);
+        options_str.push(strings::get("passive.option.wait").to_string());
+
+        if self.has_weapon() {
+            options.push(PassiveAction::InspectWeapon);
+            options_str.push(strings::get("passive.option.inspect_weapon").to_string());
+        }
+
+        // There's only something to compare with more than one weapon
+        if self.weapons().count() > 1 {
+            options.push(PassiveAction::CompareWeapons);
+            options_str.push(strings::get("passive.option.compare_weapons").to_string());
+        }
+
+        // Hints can only be had once every few turns
+        if self.can_get_hint() {
+            options.push(PassiveAction::GetHint);
+            options_str.push(strings::get("passive.option.get_hint").to_string());
+        }
+
+        options.push(PassiveAction::ResetLoop);
+        options_str.push(strings::get("passive.option.reset_loop").to_string());
 
-        let choice = menu.show_option_list(option_list);
+        // Only offer to undo if the last action took time
+        if self.last_action.is_some() {
+            options.push(PassiveAction::Undo);
+            options_str.push(strings::get("passive.option.undo").to_string());
+        }
 
-        options.swap_remove(choice)
+        let prompt = strings::format("passive.prompt", &[&self.get_remaining_time()]);
+        let option_list = OptionList::new(&options_str, &prompt).with_disabled(&disabled);
+
+        let choice = menu.show_option_list(option_list)?;
+
+        Ok(options.swap_remove(choice))
     }
 
-    /// Gets a [`PassiveAction`] from the user and carries it out
-    pub fn take_passive_action(&mut self, menu: &mut impl Menu) {
-        self.remaining_turns -= 1;
-        
-        let action = self.choose_passive_action(menu);
+    /// Gets a [`PassiveAction`] from the user and carries it out, playing any [`Effects`] it causes
+    ///
+    /// ### Returns:
+    /// Whether the loop should carry on, or the [`Player`] chose to [reset it][PassiveActionResult::ResetLoop],
+    /// or a [guard][PassiveActionResult::GuardBattle] stopped them going through a door
+    pub fn take_passive_action(&mut self, menu: &mut impl Menu, effects: &mut impl Effects) -> Result<PassiveActionResult, MenuSignal> {
+        let action = self.choose_passive_action(menu)?;
+        let mut costs_turn = action.costs_turn();
+
+        // Work out how to undo the action before it's carried out, as it may move the player
+        let mut undo = if let Some(reason) = action.irreversible_reason() {
+            Some(LastAction::Irreversible(reason))
+        } else if let PassiveAction::GoToRoom(i) = action {
+            // Going into the room fires its trigger, so the trigger has to be put back if the move is undone
+            let to = self.get_room_state().connections[i].to;
+            Some(LastAction::Movement {
+                room: self.room,
+                previous_room: self.previous_room,
+                fired_trigger: self.room_graph.get_state(to).on_enter,
+            })
+        } else {
+            None
+        };
 
         match action {
-            PassiveAction::CheckState => self.print_state(menu),
-            PassiveAction::GoToRoom(r) => {
-                print_room_transition(r, menu);
-                self.room = r.to;
+            PassiveAction::CheckState => self.print_state(menu)?,
+            PassiveAction::LookAround => self.print_room(menu)?,
+            PassiveAction::OpenMenu => settings::open_settings_menu(menu)?,
+            PassiveAction::GoToRoom(i) => {
+                // A guard has to be beaten before the player can go through the door they're guarding
+                if let Some(guard) = self.get_room_state_mut().connections[i].guard.take() {
+                    let result = self.fight_guard(guard, i, menu, effects)?;
+                    if result != BattleResult::PlayerWin {
+                        return Ok(PassiveActionResult::GuardBattle(result));
+                    }
+
+                    // The fight already took time, and it can't be undone, so neither can going through the door
+                    costs_turn = false;
+                    undo = None;
+                }
+
+                self.take_transition(i, menu, effects)?;
+            }
+            PassiveAction::Undo => self.undo_last_action(menu)?,
+            PassiveAction::ResetLoop => {
+                let options = &[strings::get("passive.reset_loop.confirm").to_string()];
+                let list = OptionList::new(options, strings::get("passive.reset_loop.prompt"));
+
+                // Changing their mind doesn't take any time
+                if menu.show_option_list_cancellable(list)?.is_some() {
+                    return Ok(PassiveActionResult::ResetLoop);
+                }
+            }
+            PassiveAction::UseItem(i) => {
+                // Changing their mind about eating doesn't take any time
+                if !self.use_item(menu, i)? {
+                    return Ok(PassiveActionResult::Continue);
+                }
+            }
+            PassiveAction::ReadDiary(i) => self.read_diary(menu, i)?,
+            PassiveAction::InspectWeapon => self.inspect_weapon(menu)?,
+            PassiveAction::CompareWeapons => {
+                menu.show_screen(Screen::new(
+                    strings::get("passive.compare_weapons.title"),
+                    &self.weapon_table(menu.content_width()),
+                ))?;
+            }
+            PassiveAction::GetHint => {
+                self.last_hint_turn = Some(self.turns_taken());
+
+                menu.show_screen(Screen::new(strings::get("passive.get_hint.title"), &next_hint(self)))?;
+            }
+            PassiveAction::PickUpItem(i) => {
+                // Finding out their hands are full doesn't take any time
+                if !self.pick_up_item_from_room(menu, i)? {
+                    return Ok(PassiveActionResult::Continue);
+                }
+            }
+            PassiveAction::Wait => {
+                let screen = Screen::new(strings::get("passive.wait.title"), wait_line(self.turns_taken()));
+
+                menu.show_screen(screen)?;
             }
-            PassiveAction::UseItem(i) => self.use_item(menu, i),
-            PassiveAction::PickUpItem(i) => self.pick_up_item_from_room(i),
             PassiveAction::RoomAction(i) => {
                 let action = self.get_room_state_mut().actions.remove(i); // Take action out of vec to avoid multiple mutable references
-                let result = action.execute(self);
+                let result = action.execute(self, menu)?;
+
+                // Changing their mind part way through doesn't take any time
+                if result.cancelled {
+                    self.get_room_state_mut().actions.insert(i, action);
+                    return Ok(PassiveActionResult::Continue);
+                }
 
                 if let Some(message) = result.message {
-                    menu.show_screen(message);
+                    menu.show_screen(message)?;
                 }
 
                 if result.show_again {
@@ -165,124 +480,469 @@ impl Player {
                 }
             }
         }
+
+        // Actions which only show the player information don't replace the last action
+        if undo.is_some() {
+            self.last_action = undo;
+        }
+
+        if costs_turn {
+            self.use_turn();
+
+            // Recovering health is only noted on the status line, so that it doesn't interrupt the player
+            let healed = self.regenerate_health();
+            let mut status = self.remaining_time_status();
+            if healed != Damage::new(0) {
+                status.text = strings::format("status.healed", &[&healed, &status.text]);
+            }
+            menu.set_status(Some(status));
+        }
+
+        menu.checkpoint();
+        Ok(PassiveActionResult::Continue)
+    }
+
+    /// Moves the [`Player`] through the [`RoomTransition`] at the given index into the [current room's connections][RoomState::connections],
+    /// firing the new room's [trigger][RoomState::on_enter] if it has one
+    fn take_transition(&mut self, i: usize, menu: &mut impl Menu, effects: &mut impl Effects) -> Result<(), MenuSignal> {
+        let transition = &self.get_room_state().connections[i];
+        // A locked or jammed door leaves the player where they are
+        if transition.to != self.room && !menu.catching_up() {
+            effects.on_room_change();
+        }
+        print_room_transition(transition, self.turns_taken(), menu)?;
+        let to = transition.to;
+        self.previous_room = Some(self.room);
+        self.room = to;
+
+        // The trigger is taken out of the room, so it won't fire again this loop
+        if let Some(trigger) = self.get_room_state_mut().on_enter.take() {
+            trigger.fire(self, menu)?;
+        }
+
+        Ok(())
+    }
+
+    /// Fights the [`Enemy`] guarding the [`RoomTransition`] at the given index into the [current room's connections][RoomState::connections].
+    /// The fight takes place in the doorway, so running away leaves the [`Player`] in the room they were already in.
+    /// If the guard isn't beaten, it goes back to guarding the door.
+    fn fight_guard(&mut self, guard: Enemy, i: usize, menu: &mut impl Menu, effects: &mut impl Effects) -> Result<BattleResult, MenuSignal> {
+        // Fleeing goes back to the previous room, which for a guard is the room the player is in
+        let previous_room = self.previous_room.replace(self.room);
+        let result = combat::battle(self, guard, menu, effects)?;
+        self.previous_room = previous_room;
+
+        // `battle` leaves an enemy who wasn't beaten in the room.
+        // A room's own enemy is always fought before the player can move, so the only enemy here is the guard.
+        if let Some(guard) = self.get_room_state_mut().enemy.take() {
+            self.get_room_state_mut().connections[i].guard = Some(guard);
+        }
+
+        Ok(result)
+    }
+
+    /// Undoes the [`Player`]'s [last action][Player::last_action] if it was a movement, refunding the turn it took.
+    /// Otherwise, shows a [`Screen`] explaining why it can't be undone.
+    fn undo_last_action(&mut self, menu: &mut impl Menu) -> Result<(), MenuSignal> {
+        match self.last_action {
+            Some(LastAction::Movement {
+                room,
+                previous_room,
+                fired_trigger,
+            }) => {
+                // A movement can only be undone once
+                self.last_action = None;
+                if let Some(trigger) = fired_trigger {
+                    self.get_room_state_mut().on_enter = Some(trigger);
+                }
+                self.room = room;
+                self.previous_room = previous_room;
+                self.remaining_turns += 1;
+                self.show_remaining_time(menu);
+
+                menu.show_screen(Screen::new(
+                    strings::get("passive.undo.title"),
+                    &strings::format("passive.undo.content", &[&room.get_name(), &room.get_description()]),
+                ))?;
+            }
+            Some(LastAction::Irreversible(reason)) => {
+                let screen = Screen::new(strings::get("passive.undo.irreversible.title"), strings::get(reason));
+
+                menu.show_screen(screen)?;
+            }
+            None => (),
+        }
+
+        Ok(())
     }
 
     /// Prints the [`Player`]'s room and health
-    fn print_state(&self, menu: &mut impl Menu) {
-        let screen = Screen {
-            title: "You take a moment to rest and check your body for injuries",
-            content: &format!(
-                "You are in the {} - {}\nYou are at {}/{} HP\nYou have:\n{}• {} to get off the ship\n",
-                self.room.get_name(),
-                self.room.get_description(),
-                self.health,
-                self.max_health,
-                self.inventory
-                    .iter()
-                    .map(|item| format!("• {} - {}\n", item.get_name(), item.get_description()))
-                    .collect::<String>(),
-                self.get_remaining_time()
+    fn print_state(&self, menu: &mut impl Menu) -> Result<(), MenuSignal> {
+        menu.show_screen(Screen::new(
+            strings::get("passive.state.title"),
+            &strings::format(
+                "passive.state.content",
+                &[
+                    &self.room.get_name(),
+                    &self.room.get_description(),
+                    &self.health,
+                    &self.max_health,
+                    &self.describe_inventory(),
+                    &self.get_remaining_time(),
+                    &self.difficulty.get_name(),
+                ],
             ),
+        ))?;
+        Ok(())
+    }
+
+    /// Shows the [`Player`]'s health and inventory in battle. This is the part of [`Player::print_state`] which matters in a fight.
+    fn print_gear(&self, menu: &mut impl Menu) -> Result<(), MenuSignal> {
+        let items = if self.inventory.is_empty() {
+            strings::get("combat.gear.empty").to_string()
+        } else {
+            self.describe_inventory()
         };
 
-        menu.show_screen(screen);
+        menu.show_screen(Screen::new(
+            strings::get("combat.gear.title"),
+            &strings::format("combat.gear.content", &[&self.health, &self.max_health, &items]),
+        ))?;
+        Ok(())
     }
 
-    /// Uses the [`Item`] at the given index into the [`Player`]'s inventory
-    fn use_item(&mut self, menu: &mut impl Menu, i: usize) {
+    /// Gets a list of the items in the [`Player`]'s inventory and their descriptions, with one item on each line
+    fn describe_inventory(&self) -> String {
+        self.inventory
+            .iter()
+            .map(|item| format!("• {} - {}\n", item.get_display_name(), item.get_description()))
+            .collect()
+    }
+
+    /// Uses the [`Item`] at the given index into the [`Player`]'s inventory.
+    /// If the item is food and the [`Player`] is already at full health, they are asked to confirm first.
+    /// If the item is an upgrade, the [`Player`]'s max health goes up and they are fully healed.
+    /// [`Player::init`] resets max health, so the boost only lasts until the end of the loop.
+    ///
+    /// ### Returns:
+    /// Whether the item was used, which is false if the [`Player`] decided not to
+    fn use_item(&mut self, menu: &mut impl Menu, i: usize) -> Result<bool, MenuSignal> {
         match &mut self.inventory[i] {
             Item::Food(f) => {
+                // Check the food would actually heal the player before it's used up
+                let mut simulated_health = self.health;
+                if simulated_health.heal_to_max(f.heals_for, self.max_health) == Damage::new(0) {
+                    let options = &[strings::get("passive.eat.confirm").to_string()];
+                    let prompt = strings::format("passive.eat.prompt", &[&f.name]);
+                    let list = OptionList::new(options, &prompt);
+
+                    if menu.show_option_list_cancellable(list)?.is_none() {
+                        return Ok(false);
+                    }
+                }
+
                 let prev_health = self.health;
                 self.health.heal_to_max(f.heals_for, self.max_health);
 
-                let screen = Screen {
-                    title: &format!("You ate your {}", f.name),
-                    content: &format!(
-                        "You are healed by {} HP.\nYou are now at {}/{} HP.",
-                        self.health - prev_health,
-                        self.health,
-                        self.max_health
-                    ),
-                };
+                menu.show_screen(Screen::new(
+                    &strings::format("passive.eat.title", &[&f.name]),
+                    &strings::format("passive.eat.content", &[&(self.health - prev_health), &self.health, &self.max_health]),
+                ))?;
+
+                take_one_food(&mut self.inventory, i);
+            }
+            Item::Upgrade(u) => {
+                self.max_health += u.max_health_boost;
+                self.health = self.max_health;
 
-                menu.show_screen(screen);
+                menu.show_screen(Screen::new(
+                    &strings::format("passive.upgrade.title", &[&u.name]),
+                    &strings::format("passive.upgrade.content", &[&u.max_health_boost, &self.health, &self.max_health]),
+                ))?;
 
                 self.inventory.remove(i);
             }
-            Item::CaptainsDiary(ref mut page) => {
-                let screen;
-                (screen, *page) = match *page {
-                    0 => (Screen {
-                        title: "You read the last page: 15/08/2168 - Found someone in the cold",
-                        content: "Found a body in the cold. Ship was all busted up so we thought we'd be holding a funeral but turns out they had a pulse. \
-They've been out for a few hours now, but still breathing. They look harmless but they're wearing an Arnithian military uniform so we're treating them like a prisoner."
-                    }, 1),
-                    
-                    1 => (Screen {
-                        title: "You start at the beginning: 01/01/2168 - Another revolution, another revelation",
-                        content: "It occurred to me during the festivities that I didn't know why a revolution was called that. Turns out it's based on roughly the time Earth took to complete one orbit of its star. \
-Early humans called it a 'year' and it was an important unit of time due to the seasonal nature of their planet."
-                    }, 2),
-
-                    2 => (Screen {
-                        title: "03/02/2168 - Big setback today, the Arnithians seem to have ultra-powerful fighter jet engines",
-                        content: "They can go from 0-60 in half a second - the engineers reckon that needs 200kN of thrust but the engines don't look much different. Can't imagine what that would do to a pilot's neck"
-                    }, 3),
-
-                    3 => (Screen {
-                        title: "17/02/2168 - More bad news about the new jets",
-                        content: "They seem to have some automated dodging capabilities. We can't hit them with anything. Their comms seems to be on all different frequencies and the patterns are all over the place - much faster than normal."
-                    }, 4),
-
-                    4 => (Screen {
-                        title: "05/04/2168 - Home is in sight",
-                        content: "The commander says I can have a few scores off after we pick up the new recruits. Sounds good to me."
-                    }, 5),
-
-                    5 => (Screen {
-                        title: "01/06/2168 - Something about the new jets seems off",
-                        content: "They're messing up all our instruments - according to my scanner, the pilot of one of them was 90 degrees! I'm really not sure what's going on - maybe they're all remote and it's to mess with our heads.\
-In any case, whatever they're doing is working. We need to step up our game before they start deploying these everywhere."
-                    }, 6),
-
-                    6 => (Screen {
-                        title: "20/06/2168 - Going home sooner than I thought",
-                        content: "The commander moved forward picking up the recruits because we need more officers on the front lines. I'll be leaving in a few cycles, but it'll only be three of us"
-                    }, 7),
-
-                    7 => (Screen {
-                        title: "10/07/2168 - Had to confiscate the darts",
-                        content: "Juuran keeps throwing them at the windows. I know they're double reinforced but from my point of view there's no point in testing that. Besides, it makes a damn annoying noise. \
-I've not hidden them but Juuran knows there'll be trouble if they take them."
-                    }, 8),
-
-                    p => (Screen {
-                        title: "There's no more pages",
-                        content: "How disappointing."
-                    }, p)
-                };
-
-                menu.show_screen(screen);
+            _ => panic!("Only food and upgrades can be used outside of combat")
+        }
+
+        Ok(true)
+    }
+
+    /// Gets an iterator over the weapons in the [`Player`]'s inventory
+    fn weapons(&self) -> impl Iterator<Item = &Weapon> {
+        self.inventory.iter().filter_map(|item| match item {
+            Item::Weapon(w) => Some(w),
+            _ => None,
+        })
+    }
+
+    /// Gets whether the [`Player`] can get a hint, which is only once every [few turns][config::HINT_COOLDOWN_TURNS]
+    fn can_get_hint(&self) -> bool {
+        match self.last_hint_turn {
+            Some(turn) => self.turns_taken() >= turn + config::HINT_COOLDOWN_TURNS,
+            None => true,
+        }
+    }
+
+    /// Gets whether the [`Player`] has any weapons in their inventory
+    fn has_weapon(&self) -> bool {
+        self.weapons().next().is_some()
+    }
+
+    /// Gets the order to list the [`Player`]'s items in during a battle, as indices into their [inventory][Player::inventory].
+    /// If `best_weapon_first` is set, the weapons are listed from most to least [effective][Weapon::effective_damage] and everything else stays where it is.
+    /// Otherwise, items are listed in the order they were picked up.
+    fn combat_item_order(&self, best_weapon_first: bool) -> Vec<usize> {
+        if !best_weapon_first {
+            return (0..self.inventory.len()).collect();
+        }
+
+        let mut weapons: Vec<_> = self
+            .inventory
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| match item {
+                Item::Weapon(w) => Some((i, w.effective_damage())),
+                _ => None,
+            })
+            .collect();
+        weapons.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        // Put the sorted weapons into the slots the weapons were in
+        let mut weapons = weapons.into_iter().map(|(i, _)| i);
+        self.inventory
+            .iter()
+            .enumerate()
+            .map(|(i, item)| match item {
+                Item::Weapon(_) => weapons.next().expect("There should be a sorted weapon for each weapon slot"),
+                _ => i,
+            })
+            .collect()
+    }
+
+    /// Builds a table of the stats of every weapon in the [`Player`]'s inventory, with one weapon on each row.
+    /// The numbers are right-aligned so they line up.
+    ///
+    /// ### Params:
+    /// * `width`: the most characters which fit on a line, if there's a limit.
+    ///   If the table is too wide, weapon names are cut off with an ellipsis so that the numbers are always shown in full.
+    fn weapon_table(&self, width: Option<usize>) -> String {
+        /// The [keys][strings::get] of the headings of the numeric columns
+        const HEADING_KEYS: [&str; 3] = ["weapon.table.straight_damage", "weapon.table.dodge_damage", "weapon.table.speed"];
+        /// The space between columns
+        const GAP: &str = "  ";
+
+        let rows: Vec<_> = self
+            .weapons()
+            .map(|w| {
+                let numbers = [w.straight_damage.to_string(), w.dodge_damage.to_string(), w.speed.to_string()];
+                (w.name, numbers)
+            })
+            .collect();
+
+        let name_heading = strings::get("weapon.table.name");
+        let headings = HEADING_KEYS.map(strings::get);
+
+        // Each numeric column is as wide as its widest entry, including the heading
+        let number_widths = headings.map(|heading| heading.chars().count());
+        let number_widths: Vec<_> = (0..headings.len())
+            .map(|i| rows.iter().map(|(_, numbers)| numbers[i].len()).fold(number_widths[i], usize::max))
+            .collect();
+        let numbers_width: usize = number_widths.iter().map(|w| w + GAP.len()).sum();
+
+        // The name column gives up space if the table is too wide, but always keeps at least one character
+        let longest_name = rows.iter().map(|(name, _)| name.chars().count()).fold(name_heading.chars().count(), usize::max);
+        let name_width = match width {
+            Some(width) => longest_name.min(width.saturating_sub(numbers_width)).max(1),
+            None => longest_name,
+        };
+
+        let format_row = |name: &str, numbers: &[&str]| {
+            let name = if name.chars().count() > name_width {
+                format!("{}⋯", name.chars().take(name_width - 1).collect::<String>())
+            } else {
+                name.to_string()
+            };
+
+            let mut cells = vec![format!("{name: <name_width$}")];
+            cells.extend(
+                numbers
+                    .iter()
+                    .zip(&number_widths)
+                    .map(|(number, width)| format!("{number: >width$}")),
+            );
+
+            cells.join(GAP)
+        };
+
+        let mut lines = vec![format_row(name_heading, &headings)];
+        for (name, numbers) in &rows {
+            let numbers = numbers.each_ref().map(String::as_str);
+            lines.push(format_row(name, &numbers));
+        }
+
+        lines.join("\n")
+    }
+
+    /// Lets the user pick one of their weapons, and shows them its description and [stats][Weapon::stats_summary].
+    /// The user can cancel without picking a weapon.
+    fn inspect_weapon(&self, menu: &mut impl Menu) -> Result<(), MenuSignal> {
+        let weapons: Vec<_> = self.weapons().collect();
+
+        let options: Vec<_> = weapons.iter().map(|w| w.name.to_string()).collect();
+        let list = OptionList::new(&options, strings::get("passive.inspect_weapon.prompt"));
+
+        let Some(choice) = menu.show_option_list_cancellable(list)? else {
+            return Ok(());
+        };
+        let weapon = weapons[choice];
+
+        menu.show_screen(Screen::new(
+            &strings::format("passive.inspect_weapon.title", &[&weapon.name]),
+            &format!("{}\n\n{}", weapon.description, weapon.stats_summary()),
+        ))?;
+        Ok(())
+    }
+
+    /// Lets the user pick a page of the [captain's diary][Item::CaptainsDiary] at the given index into the [`Player`]'s inventory to read.
+    /// Pages which have already been read can be read again, and the next page can be read if there is one.
+    ///
+    /// ### Panics
+    /// * If the item at the index is not the captain's diary
+    fn read_diary(&mut self, menu: &mut impl Menu, i: usize) -> Result<(), MenuSignal> {
+        let Item::CaptainsDiary(pages_read) = &mut self.inventory[i] else {
+            panic!("Item should have been the captain's diary");
+        };
+        let read = usize::from(*pages_read);
+
+        // The first time the diary is read, there's nothing to choose between
+        let choice = if read == 0 {
+            Some(0)
+        } else {
+            let mut options_str: Vec<_> = DIARY_PAGE_KEYS[..read]
+                .iter()
+                .map(|&(title, _)| strings::get(title).to_string())
+                .collect();
+
+            if read < DIARY_PAGE_KEYS.len() {
+                options_str.push(strings::get("diary.next_page").to_string());
             }
-            _ => panic!("Only food items can be used outside of combat")
+
+            let list = OptionList::new(&options_str, strings::get("diary.prompt"));
+            menu.show_option_list_cancellable(list)?
+        };
+
+        let Some(choice) = choice else {
+            return Ok(());
+        };
+
+        if choice < read {
+            menu.show_screen(diary_page(choice))?;
+            return Ok(());
         }
+
+        // Explain the order the first two pages are read in
+        let page = diary_page(read);
+        let title = match read {
+            0 => strings::format("diary.intro.last_page", &[&page.title]),
+            1 => strings::format("diary.intro.first_page", &[&page.title]),
+            _ => page.title.to_string(),
+        };
+        let screen = Screen { title: &title, ..page };
+
+        menu.show_screen(screen)?;
+        *pages_read += 1;
+
+        Ok(())
     }
 
     /// Removes an [`Item`] from the current [`RoomState`] at the specified index and adds it to the [player's inventory][Player::inventory]
-    fn pick_up_item_from_room(&mut self, i: usize) {
+    fn pick_up_item_from_room(&mut self, menu: &mut impl Menu, i: usize) -> Result<bool, MenuSignal> {
         let room_state = self.get_room_state_mut();
         let item = room_state.items.remove(i);
-        self.pick_up_item(item);
+
+        if let Err(item) = self.pick_up_item(item) {
+            let title = strings::format("passive.pick_up.hands_full.title", &[&item.get_name()]);
+
+            self.get_room_state_mut().items.insert(i, item);
+            menu.show_screen(Screen::new(&title, strings::get("passive.pick_up.hands_full.content")))?;
+            return Ok(false);
+        }
+
+        Ok(true)
     }
 
-    /// Add an item to the [player's inventory][Player::inventory]
-    pub fn pick_up_item(&mut self, item: Item) {
-        // TODO: max inventory size
+    /// Gets whether the [player's inventory][Player::inventory] has space for the given [`Item`].
+    /// [`Food`][crate::items::Food] which would be stacked always fits.
+    pub fn has_space_for(&self, item: &Item) -> bool {
+        if let Item::Food(new_food) = item {
+            if self.inventory.iter().any(|existing| matches!(existing, Item::Food(f) if f.name == new_food.name)) {
+                return true;
+            }
+        }
+
+        self.max_inventory_size.is_none_or(|max| self.inventory.len() < max)
+    }
+
+    /// Add an item to the [player's inventory][Player::inventory].
+    /// Identical [`Food`][crate::items::Food] is stacked with any of the same food already in the inventory, rather than taking up another space.
+    ///
+    /// ### Returns:
+    /// * `Ok(())` if the item was picked up
+    /// * `Err(item)` if the inventory is [full][Player::has_space_for], giving the item back
+    pub fn pick_up_item(&mut self, item: Item) -> Result<(), Item> {
+        if !self.has_space_for(&item) {
+            return Err(item);
+        }
+
+        self.items_collected += 1;
+
+        if let Item::Food(new_food) = &item {
+            let existing = self.inventory.iter_mut().find_map(|existing| match existing {
+                Item::Food(f) if f.name == new_food.name => Some(f),
+                _ => None,
+            });
+
+            if let Some(existing) = existing {
+                existing.count += new_food.count;
+                return Ok(());
+            }
+        }
+
         self.inventory.push(item);
+        Ok(())
+    }
+
+    /// Uses up one of the [`Player`]'s remaining turns, both in and out of battle.
+    ///
+    /// ### Panics
+    /// * If the [`Player`] has no turns remaining. The loop should always be reset before this happens.
+    pub fn use_turn(&mut self) {
+        self.remaining_turns = self
+            .remaining_turns
+            .checked_sub(1)
+            .expect("Player should have had turns remaining - the loop should have been reset");
+    }
+
+    /// Stuns the [`Player`] so that they do [nothing][combat::Action::Nothing] for the given number of turns, starting from the next turn of the battle.
+    /// If they are already stunned for longer, this doesn't shorten it.
+    #[allow(dead_code)] // Nothing can stun the player yet
+    pub fn stun(&mut self, turns: usize) {
+        self.stunned_turns = self.stunned_turns.max(turns);
     }
 
-    /// Get the user to choose a [combat action][combat::Action] to perform
-    pub fn choose_combat_action(&self, menu: &mut impl Menu) -> combat::Action {
+    /// Gets the number of turns the [`Player`] has taken so far this loop
+    pub const fn turns_taken(&self) -> usize {
+        config::MAX_TURNS - self.remaining_turns
+    }
+
+    /// Get the user to choose a [combat action][combat::Action] to perform.
+    /// `tell` is a description of the enemy's [tell][combat::Tell] to show alongside the options, if they gave one away.
+    pub fn choose_combat_action(
+        &self,
+        tell: Option<&str>,
+        menu: &mut impl Menu,
+    ) -> Result<combat::Action, MenuSignal> {
         // Init lists of options and their string representations
         let mut options = vec![
             combat::Action::Nothing,
@@ -290,50 +950,92 @@ I've not hidden them but Juuran knows there'll be trouble if they take them."
             combat::Action::DodgeRight,
         ];
         let mut options_str = vec![
-            "Do nothing".to_string(),
-            "Dodge to the left".to_string(),
-            "Dodge to the right".to_string(),
+            strings::get("combat.option.nothing").to_string(),
+            strings::get("combat.option.dodge_left").to_string(),
+            strings::get("combat.option.dodge_right").to_string(),
         ];
 
+        // The player can only run away if there's somewhere to run to
+        if self.previous_room.is_some() {
+            options.push(combat::Action::Flee);
+            options_str.push(strings::get("combat.option.flee").to_string());
+        }
+
+        // Attacks show the weapon's damage alongside them
+        let mut details = Vec::new();
+
         // Add actions for items
-        for (i, item) in self.inventory.iter().enumerate() {
+        for i in self.combat_item_order(menu.best_weapon_first()) {
+            let item = &self.inventory[i];
             match item {
                 Item::Food(f) => {
                     options.push(combat::Action::EatFood(i));
-                    options_str.push(format!("Eat your {}", f.name));
+                    options_str.push(strings::format("combat.option.eat", &[&item.get_display_name()]));
+                    options.push(combat::Action::ThrowFood(i));
+                    options_str.push(strings::format("combat.option.throw_food", &[&f.name]));
                 }
                 Item::Weapon(w) => {
                     options.push(combat::Action::AttackStraight(i));
-                    options_str.push(format!("Attack with your {}", w.name));
+                    details.push((options_str.len(), strings::format("combat.option.attack.details", &[&w.straight_damage])));
+                    options_str.push(strings::format("combat.option.attack", &[&w.name]));
+
+                    if w.throwable {
+                        options.push(combat::Action::Throw(i));
+                        options_str.push(strings::format("combat.option.throw", &[&w.name]));
+                    }
                 }
                 _ => (),
             }
         }
 
-        // Get the user to pick an option
-        let prompt = format!("{} - What do you do?", self.get_remaining_time());
-        let list = OptionList::new(&options_str, &prompt);
-        let choice = menu.show_option_list(list);
+        options.push(combat::Action::Disarm);
+        options_str.push(strings::get("combat.option.disarm").to_string());
+
+        // Inspecting a weapon or checking gear isn't an action, so they go after all the actions and don't take the turn
+        let inspect_choice = self.has_weapon().then(|| {
+            options_str.push(strings::get("combat.option.inspect_weapon").to_string());
+            options_str.len() - 1
+        });
+        options_str.push(strings::get("combat.option.check_gear").to_string());
+        let gear_choice = options_str.len() - 1;
+
+        // Get the user to pick an option, showing the enemy's tell if there is one
+        let prompt = match tell {
+            Some(tell) => strings::format("combat.prompt.tell", &[&self.get_remaining_time(), &tell]),
+            None => strings::format("combat.prompt", &[&self.get_remaining_time()]),
+        };
+        let choice = loop {
+            let list = OptionList::new(&options_str, &prompt).with_details(&details);
+            let choice = menu.show_option_list(list)?;
+
+            if Some(choice) == inspect_choice {
+                self.inspect_weapon(menu)?;
+            } else if choice == gear_choice {
+                self.print_gear(menu)?;
+            } else {
+                break choice;
+            }
+        };
 
         // If the action was an attack, get the user to pick which direction to aim it
         if let combat::Action::AttackStraight(i) = options[choice] {
             let options = &[
-                "Attack Left".to_string(),
-                "Attack Straight".to_string(),
-                "Attack Right".to_string(),
+                strings::get("combat.direction.left").to_string(),
+                strings::get("combat.direction.straight").to_string(),
+                strings::get("combat.direction.right").to_string(),
             ];
-            let list = OptionList::new(options, "Which way do you attack?");
+            let list = OptionList::new(options, strings::get("combat.direction.prompt"));
 
-            let direction = menu.show_option_list(list);
+            let direction = menu.show_option_list(list)?;
 
             match direction {
-                0 => combat::Action::AttackLeft(i),
-                1 => combat::Action::AttackStraight(i),
-                2 => combat::Action::AttackRight(i),
+                0 => Ok(combat::Action::AttackLeft(i)),
+                1 => Ok(combat::Action::AttackStraight(i)),
+                2 => Ok(combat::Action::AttackRight(i)),
                 _ => unreachable!(),
             }
         } else {
-            options.swap_remove(choice)
+            Ok(options.swap_remove(choice))
         }
     }
 
@@ -342,53 +1044,82 @@ I've not hidden them but Juuran knows there'll be trouble if they take them."
         use combat::Action::*;
 
         match action {
-            AttackLeft(w) => format!(
-                "You attack to the left with your {}",
-                self.inventory[w].get_name()
-            ),
-            AttackRight(w) => format!(
-                "You attack to the right with your {}",
-                self.inventory[w].get_name()
-            ),
-            AttackStraight(w) => format!(
-                "You attack in front of you with your {}",
-                self.inventory[w].get_name()
-            ),
-            EatFood(f) => format!("You attempt to eat your {}", self.inventory[f].get_name()),
+            AttackLeft(w) => strings::format("combat.player_action.attack_left", &[&self.inventory[w].get_name()]),
+            AttackRight(w) => strings::format("combat.player_action.attack_right", &[&self.inventory[w].get_name()]),
+            AttackStraight(w) => strings::format("combat.player_action.attack_straight", &[&self.inventory[w].get_name()]),
+            EatFood(f) => strings::format("combat.player_action.eat_food", &[&self.inventory[f].get_name()]),
 
-            DodgeLeft => "You dodge to the left".to_string(),
-            DodgeRight => "You dodge to the right".to_string(),
-            Nothing => "You do nothing".to_string(),
+            DodgeLeft => strings::get("combat.player_action.dodge_left").to_string(),
+            DodgeRight => strings::get("combat.player_action.dodge_right").to_string(),
+            Flee => strings::get("combat.player_action.flee").to_string(),
+            Throw(w) => strings::format("combat.player_action.throw", &[&self.inventory[w].get_name()]),
+            Disarm => strings::get("combat.player_action.disarm").to_string(),
+            ThrowFood(f) => strings::format("combat.player_action.throw_food", &[&self.inventory[f].get_name()]),
+            Nothing => strings::get("combat.player_action.nothing").to_string(),
         }
     }
 
     /// Shows the player a win screen
-    pub fn show_win_screen(&self, menu: &mut impl Menu) {
-        if self.inventory.iter().any(|item|matches!(item, Item::Food(_))) {
-            menu.show_screen(Screen {
-                title: "Freedom at long last",
-                content: "Or maybe not so long - it's only been a few minutes, after all. You buckle in for the long ride and allow yourself to relax, finally. You won't get back to New Arnith for a cycle and a half, but at least you brought some food."
-            });
+    pub fn show_win_screen(&self, menu: &mut impl Menu) -> Result<(), MenuSignal> {
+        let content = if self.inventory.iter().any(|item|matches!(item, Item::Food(_))) {
+            strings::get("screen.win.content.food")
         } else {
-            menu.show_screen(Screen {
-                title: "Freedom at long last",
-                content: "Or maybe not so long - it's only been a few minutes, after all. You buckle in for the long ride and allow yourself to relax, finally."
-            });
-        }
+            strings::get("screen.win.content")
+        };
+
+        menu.show_screen(Screen::new(strings::get("screen.win.title"), content).with_banner(ESCAPE_POD_BANNER))?;
+        Ok(())
+    }
+
+    /// Shows the player a summary of the run so far
+    ///
+    /// ### Params:
+    /// * `menu`: the [`Menu`] to display to
+    /// * `loops`: the number of time loops the player has been through, including the current one
+    /// * `total_turns`: the number of turns taken across all loops, including the current one
+    pub fn show_run_summary(&self, menu: &mut impl Menu, loops: usize, total_turns: usize) -> Result<(), MenuSignal> {
+        menu.show_screen(Screen::new(
+            strings::get("screen.summary.title"),
+            &strings::format("screen.summary.content", &[&loops, &self.enemies_defeated, &self.items_collected, &total_turns]),
+        ))?;
+        Ok(())
     }
 }
 
 impl Player {
-    /// Initialise a new [`Player`]
-    pub fn init() -> Self {
+    /// Initialise a new [`Player`] for a game on the given [`Difficulty`]
+    pub fn init(difficulty: Difficulty) -> Self {
+        Self::init_with(difficulty, STARTING_ROOM, Vec::new())
+    }
+
+    /// Initialise a new [`Player`] for a game on the given [`Difficulty`], starting somewhere other than [`STARTING_ROOM`] or already holding some items.
+    /// The items don't count towards the [`Player`]'s [collected items][Player::items_collected].
+    ///
+    /// ### Params:
+    /// * `difficulty`: the [`Difficulty`] the game is being played on
+    /// * `room`: the [`Room`] the [`Player`] starts in
+    /// * `inventory`: the items the [`Player`] starts with
+    pub fn init_with(difficulty: Difficulty, room: Room, inventory: Vec<Item>) -> Self {
         Self {
-            room: STARTING_ROOM,
-            inventory: Vec::new(),
+            room,
+            previous_room: None,
+            inventory,
             health: config::PLAYER_START_HEALTH,
             max_health: config::PLAYER_START_MAX_HEALTH,
+            max_inventory_size: config::MAX_INVENTORY_SIZE,
+            status_effects: Vec::new(),
+            thrown_weapons: Vec::new(),
+            stunned_turns: 0,
             remaining_turns: config::MAX_TURNS,
+            enemies_defeated: 0,
+            items_collected: 0,
+
+            difficulty,
+            last_action: None,
+            last_hint_turn: None,
+            last_regen_turn: 0,
 
-            room_graph: map::init(),
+            room_graph: map::init(difficulty),
         }
     }
 }
@@ -1,13 +1,13 @@
 #![cfg(test)]
 
-use crate::{items::Food, combat::Damage, menu::tests::MockMenu};
+use crate::{items::{Food, Upgrade, Weapon}, combat::{Damage, tests::test_enemy}, effects::{NoEffects, tests::MockEffects}, menu::tests::MockMenu};
 
 use super::*;
 
 /// Tests that the [`Player::get_remaining_time`] function returns correct results
 #[test]
 fn test_time_format() {
-    let mut player = Player::init();
+    let mut player = Player::init(Difficulty::Normal);
 
     player.remaining_turns = 0;
     assert_eq!(player.get_remaining_time(), "0:00");
@@ -25,11 +25,160 @@ fn test_time_format() {
     assert_eq!(player.get_remaining_time(), "3:20");
 }
 
+/// Tests that moving between rooms plays the room change effect, but looking around and undoing don't
+#[test]
+fn test_room_change_effect() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::builder()
+        .choose_option("Look around the room again")
+        .choose_option("Go to the Upper Corridor")
+        .choose_option("Undo your last action")
+        .build();
+    let mut effects = MockEffects::default();
+
+    player.take_passive_action(&mut menu, &mut effects).unwrap();
+    assert_eq!(effects.room_changes, 0);
+    player.take_passive_action(&mut menu, &mut effects).unwrap();
+    assert_eq!(effects.room_changes, 1);
+    player.take_passive_action(&mut menu, &mut effects).unwrap();
+    assert_eq!(effects.room_changes, 1);
+    assert_eq!(effects.hits + effects.deaths, 0);
+}
+
+/// Tests that waiting uses up a turn without doing anything else, and that the line shown only depends on the turn number
+#[test]
+fn test_wait() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::builder()
+        .choose_option("Wait and listen")
+        .choose_option("Wait and listen")
+        .choose_option("Undo your last action")
+        .build();
+
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.turns_taken(), 2);
+    assert_eq!(player.room, STARTING_ROOM);
+    assert!(player.inventory.is_empty());
+
+    let lines: Vec<_> = menu.screens().iter().filter(|(title, _)| title == "You wait and listen").map(|(_, line)| line.as_str()).collect();
+    assert_eq!(lines, [wait_line(0), wait_line(1)]);
+    assert_ne!(lines[0], lines[1]);
+    assert!(lines[0].starts_with("The hull creaks"));
+
+    // Waiting can't be undone
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.turns_taken(), 2);
+    assert_eq!(menu.last_screen().unwrap().1, "You can't get back the time you spent waiting.");
+}
+
+/// Tests that rooms with ambient lines show one after their description, which changes with the turn, and that other rooms are shown as they are
+#[test]
+fn test_ambient_lines() {
+    assert_eq!(room_description(Room::UpperCorridor, 3), Room::UpperCorridor.get_description());
+    assert!(Room::UpperCorridor.ambient_line(3).is_none());
+
+    let lines = Room::MessHall.ambient_lines();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(Room::MessHall.ambient_line(1), Some(lines[1]));
+    assert_eq!(Room::MessHall.ambient_line(4), Some(lines[1]));
+
+    // Looking around on a later turn shows the next line, without changing the description
+    let mut player = Player::init_with(Difficulty::Normal, Room::MessHall, Vec::new());
+    let mut menu = MockMenu::builder()
+        .choose_option("Look around the room again")
+        .choose_option("Wait and listen")
+        .choose_option("Look around the room again")
+        .build();
+    for _ in 0..3 {
+        player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    }
+
+    let looks: Vec<_> = menu.screens().iter().filter(|(title, _)| title == "You are in the Mess Hall.").map(|(_, content)| content.as_str()).collect();
+    assert_eq!(looks, [
+        format!("{} {}", Room::MessHall.get_description(), lines[0]),
+        format!("{} {}", Room::MessHall.get_description(), lines[1]),
+    ]);
+    assert!(lines[0].contains("12-9"));
+}
+
+/// Tests that the time left shown as the [`Menu`]'s status keeps up as turns are spent and refunded
+#[test]
+fn test_remaining_time_status() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::builder()
+        .choose_option("Go to the Upper Corridor")
+        .choose_option("Undo your last action")
+        .build();
+
+    player.show_remaining_time(&mut menu);
+    assert_eq!(menu.status().unwrap().text, "10:00 left");
+
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(menu.status().unwrap().text, "9:40 left");
+
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(menu.status().unwrap().text, "10:00 left");
+    assert_eq!(menu.status().unwrap().warning, None);
+
+    // The status warns the player once they have little time left
+    player.remaining_turns = config::LOW_TIME_TURNS + 1;
+    player.show_remaining_time(&mut menu);
+    assert_eq!(menu.status().unwrap().warning, None);
+
+    player.remaining_turns = config::LOW_TIME_TURNS;
+    player.show_remaining_time(&mut menu);
+    assert_eq!(menu.status().unwrap().text, "1:00 left");
+    assert!(menu.status().unwrap().warning.is_some());
+}
+
+/// Tests that the [`Player`] slowly recovers health on passive turns, up to their max health
+#[test]
+fn test_health_regen() {
+    let mut player = Player::init(Difficulty::Normal);
+    player.health = Health::new(1);
+
+    // Walk back and forth between the cells and the upper corridor
+    let mut builder = MockMenu::builder();
+    for _ in 0..config::HEALTH_REGEN_TURNS * 2 {
+        builder = builder.choose_option("Go to the");
+    }
+    let mut menu = builder.build();
+
+    for _ in 0..config::HEALTH_REGEN_TURNS - 1 {
+        player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    }
+    assert_eq!(player.health, Health::new(1));
+    assert_eq!(menu.status().unwrap().text, "9:20 left");
+
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.health, Health::new(1) + config::HEALTH_REGEN);
+    assert_eq!(menu.status().unwrap().text, format!("+{} HP - 9:00 left", config::HEALTH_REGEN));
+
+    for _ in 0..config::HEALTH_REGEN_TURNS {
+        player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    }
+    assert_eq!(player.health, Health::new(1) + config::HEALTH_REGEN + config::HEALTH_REGEN);
+
+    // Free actions don't count towards recovering health
+    let mut menu = MockMenu::builder().choose_option("Check how you're doing").build();
+    player.last_regen_turn = 0;
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.health, Health::new(1) + config::HEALTH_REGEN + config::HEALTH_REGEN);
+
+    // Health doesn't go over the max, and the status doesn't mention recovering nothing
+    player.health = player.max_health;
+    let mut menu = MockMenu::builder().choose_option("Go to the").build();
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.health, player.max_health);
+    assert!(!menu.status().unwrap().text.contains("HP"));
+}
+
 #[test]
 fn test_use_item() {
     // Eating food should heal by the right number of health
     {
-        let mut player = Player::init();
+        let mut player = Player::init(Difficulty::Normal);
         player.health = Health::new(5);
         player.max_health = Health::new(10);
 
@@ -37,15 +186,16 @@ fn test_use_item() {
         name: "",
             description: "",
             heals_for: Damage::new(3),
+            count: 1,
         }));
 
-        player.use_item(&mut MockMenu::default(), 0);
+        player.use_item(&mut MockMenu::default(), 0).unwrap();
         assert_eq!(player.health, Health::new(8));
     }
 
     // Eating food should not heal past the player's maximum health
     {
-        let mut player = Player::init();
+        let mut player = Player::init(Difficulty::Normal);
         player.health = Health::new(5);
         player.max_health = Health::new(10);
 
@@ -53,9 +203,526 @@ fn test_use_item() {
         name: "",
             description: "",
             heals_for: Damage::new(10),
+            count: 1,
         }));
 
-        player.use_item(&mut MockMenu::new().unwrap(), 0);
+        player.use_item(&mut MockMenu::new().unwrap(), 0).unwrap();
         assert_eq!(player.health, Health::new(10));
     }
-}
\ No newline at end of file
+
+    // Eating food at full health should only happen if the player confirms it
+    {
+        let mut player = Player::init(Difficulty::Normal);
+        let mut menu = MockMenu::default();
+        player.health = Health::new(10);
+        player.max_health = Health::new(10);
+
+        player.inventory.push(Item::Food(Food {
+            name: "",
+            description: "",
+            heals_for: Damage::new(3),
+            count: 1,
+        }));
+
+        menu.push_choice(None);
+        assert!(!player.use_item(&mut menu, 0).unwrap());
+        assert_eq!(player.inventory.len(), 1);
+
+        menu.push_choice(Some(0));
+        assert!(player.use_item(&mut menu, 0).unwrap());
+        assert!(player.inventory.is_empty());
+    }
+    // Using an upgrade should raise max health and heal the player fully
+    {
+        let mut player = Player::init(Difficulty::Normal);
+        player.health = Health::new(5);
+        player.max_health = Health::new(10);
+
+        player.inventory.push(Item::Upgrade(Upgrade {
+            name: "",
+            description: "",
+            max_health_boost: Damage::new(5),
+        }));
+
+        assert!(player.use_item(&mut MockMenu::default(), 0).unwrap());
+        assert_eq!(player.max_health, Health::new(15));
+        assert_eq!(player.health, Health::new(15));
+        assert!(player.inventory.is_empty());
+    }
+}
+/// Tests that picking up items is counted towards the run summary
+#[test]
+fn test_items_collected() {
+    let mut player = Player::init(Difficulty::Normal);
+    assert_eq!(player.items_collected, 0);
+
+    player.pick_up_item(Item::Dust).unwrap();
+    player.pick_up_item(Item::Shame).unwrap();
+    assert_eq!(player.items_collected, 2);
+    assert_eq!(player.inventory.len(), 2);
+}
+
+/// Tests that checking the player's state and looking around don't use up a turn, but other actions do
+#[test]
+fn test_check_state_is_free() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+
+    // The first option is always checking the player's state
+    menu.push_choice(Some(0));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.remaining_turns, config::MAX_TURNS);
+
+    // Looking around the room again is free too
+    menu.push_choice(Some(1));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.remaining_turns, config::MAX_TURNS);
+    assert!(menu.last_screen().unwrap().1.starts_with(player.room.get_description()));
+
+    // In the cells, the option after opening the settings menu is going to the upper corridor
+    menu.push_choice(Some(3));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.room, Room::UpperCorridor);
+    assert_eq!(player.remaining_turns, config::MAX_TURNS - 1);
+}
+
+/// Tests that room actions can ask the user for a follow-up choice
+#[test]
+fn test_room_action_follow_up_choice() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+
+    // Climb into the vents and give up
+    menu.push_choice(Some(4));
+    menu.push_choice(Some(1));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert!(player.inventory.is_empty());
+
+    // Climb into the vents again and push harder
+    menu.push_choice(Some(4));
+    menu.push_choice(Some(0));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert!(matches!(player.inventory[..], [Item::Dust]));
+}
+
+/// Tests that a movement can be undone once, refunding its turn, but other actions can't be
+#[test]
+fn test_undo_movement() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+
+    // There's nothing to undo at the start of a loop
+    assert_eq!(player.last_action, None);
+
+    // Go to the upper corridor, check how the player's doing, then undo the movement
+    menu.push_choice(Some(3));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    menu.push_choice(Some(0));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(player.remaining_turns, config::MAX_TURNS - 1);
+
+    let undo_index = menu_options(&player).len() - 1;
+    menu.push_choice(Some(undo_index));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+
+    assert_eq!(player.room, STARTING_ROOM);
+    assert_eq!(player.previous_room, None);
+    assert_eq!(player.remaining_turns, config::MAX_TURNS);
+    assert_eq!(player.last_action, None);
+
+    // Climbing into the vents changes the room, so it can't be undone
+    menu.push_choice(Some(4));
+    menu.push_choice(Some(1));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert!(matches!(player.last_action, Some(LastAction::Irreversible(_))));
+
+    let turns = player.remaining_turns;
+    let undo_index = menu_options(&player).len() - 1;
+    menu.push_choice(Some(undo_index));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+
+    assert_eq!(player.remaining_turns, turns);
+    assert_eq!(menu.last_screen().unwrap().0, "You can't undo that");
+}
+
+/// Tests that backing out of restarting the loop carries on with the current one without using a turn
+#[test]
+fn test_cancel_reset_loop() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::builder().choose_option("Give up and restart the loop").build();
+    menu.push_choice(None);
+
+    let result = player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, PassiveActionResult::Continue);
+    assert_eq!(player.turns_taken(), 0);
+    assert_eq!(player.last_action, None);
+
+    menu.push_choice(Some(menu_options(&player).len() - 1));
+    menu.push_choice(Some(0));
+    let result = player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, PassiveActionResult::ResetLoop);
+}
+
+/// Tests that trying to go through a guarded door starts a battle with the guard, and that beating them completes the move
+#[test]
+fn test_guarded_transition() {
+    let mut player = Player::init(Difficulty::Normal);
+    player.get_room_state_mut().connections[0].guard = Some(test_enemy(0));
+
+    let mut menu = MockMenu::builder().choose_option("Go to the Upper Corridor").build();
+    // An enemy with no health is beaten after one turn, and then don't review the fight
+    menu.push_choice(Some(0));
+    menu.push_choice(Some(0));
+
+    let result = player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, PassiveActionResult::Continue);
+    assert!(menu.screens().iter().any(|(title, _)| title == "You are spotted by the Test Enemy"));
+    assert_eq!(player.room, Room::UpperCorridor);
+    assert_eq!(player.previous_room, Some(STARTING_ROOM));
+
+    // Only the fight took time, and it can't be undone
+    assert_eq!(player.turns_taken(), 1);
+    assert!(matches!(player.last_action, Some(LastAction::Irreversible(_))));
+    assert!(player.room_graph.get_state(STARTING_ROOM).connections[0].guard.is_none());
+}
+
+/// Tests that running away from a guard leaves the player where they were, with the guard still at the door
+#[test]
+fn test_flee_guard() {
+    let mut player = Player::init(Difficulty::Normal);
+    player.get_room_state_mut().connections[0].guard = Some(test_enemy(10));
+
+    let mut menu = MockMenu::builder().choose_option("Go to the Upper Corridor").choose_option("Try to run away").build();
+    menu.push_choice(Some(0));
+
+    let result = player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    assert_eq!(result, PassiveActionResult::GuardBattle(BattleResult::PlayerFled));
+    assert_eq!(player.room, STARTING_ROOM);
+    assert_eq!(player.previous_room, None);
+    assert_eq!(player.turns_taken(), 1);
+
+    let state = player.get_room_state();
+    assert!(state.enemy.is_none());
+    let guard = state.connections[0].guard.as_ref().unwrap();
+    assert_eq!(guard.health, guard.max_health);
+}
+
+/// Gets the options the player would be shown for their next passive action
+fn menu_options(player: &Player) -> Vec<String> {
+    let mut menu = MockMenu::default();
+    menu.push_choice(Some(0));
+    player.choose_passive_action(&mut menu).unwrap();
+    menu.last_list().unwrap().1.clone()
+}
+
+/// Tests that pages of the captain's diary which have been read can be picked to read again
+#[test]
+fn test_diary_pages() {
+    let mut player = Player::init_with(Difficulty::Normal, STARTING_ROOM, vec![Item::CaptainsDiary(0)]);
+    let mut menu = MockMenu::default();
+
+    // The first page is read without asking which page to read
+    player.read_diary(&mut menu, 0).unwrap();
+    assert!(matches!(player.inventory[..], [Item::CaptainsDiary(1)]));
+    assert_eq!(menu.last_screen().unwrap().0, format!("You read the last page: {}", diary_page(0).title));
+
+    // Read the next page, which is the option after the pages which have been read
+    menu.push_choice(Some(1));
+    player.read_diary(&mut menu, 0).unwrap();
+    assert!(matches!(player.inventory[..], [Item::CaptainsDiary(2)]));
+
+    // Read the first page again
+    menu.push_choice(Some(0));
+    player.read_diary(&mut menu, 0).unwrap();
+    assert!(matches!(player.inventory[..], [Item::CaptainsDiary(2)]));
+    assert_eq!(menu.last_screen().unwrap().0, diary_page(0).title);
+    assert_eq!(menu.last_list().unwrap().1.len(), 3);
+
+    // Once every page has been read, there's no option to read the next one
+    player.inventory[0] = Item::CaptainsDiary(8);
+    menu.push_choice(None);
+    player.read_diary(&mut menu, 0).unwrap();
+    assert_eq!(menu.last_list().unwrap().1.len(), DIARY_PAGE_KEYS.len());
+}
+
+/// Tests that backing out of taking off without food doesn't use up a turn
+#[test]
+fn test_cancel_take_off() {
+    let mut player = Player::init_with(Difficulty::Normal, Room::EscapePod, vec![Item::Maps]);
+    let mut menu = MockMenu::default();
+
+    let take_off = map::RoomAction::EscapePodTakeOff.get_description();
+    let take_off_index = menu_options(&player).iter().position(|option| option == take_off).unwrap();
+    menu.push_choice(Some(take_off_index));
+    menu.push_choice(None);
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+
+    assert_eq!(player.room, Room::EscapePod);
+    assert_eq!(player.remaining_turns, config::MAX_TURNS);
+    assert_eq!(player.last_action, None);
+    assert!(menu_options(&player).iter().any(|option| option == take_off));
+}
+
+/// Tests that reading the captain's diary only shows information, so the movement before it can still be undone
+#[test]
+fn test_read_diary_keeps_undo() {
+    let mut player = Player::init_with(Difficulty::Normal, STARTING_ROOM, vec![Item::CaptainsDiary(0)]);
+    let mut menu = MockMenu::default();
+
+    // Go to the upper corridor, then read the diary
+    menu.push_choice(Some(3));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    let movement = player.last_action;
+
+    let read_index = menu_options(&player).iter().position(|option| option == "Read the captain's diary").unwrap();
+    menu.push_choice(Some(read_index));
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+
+    assert!(matches!(movement, Some(LastAction::Movement { .. })));
+    assert_eq!(player.last_action, movement);
+}
+
+/// Tests that identical food stacks in the inventory, and that eating takes one from the stack
+#[test]
+fn test_food_stacks() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::default();
+    player.health = Health::new(1);
+
+    let bread_roll = || Item::Food(Food {
+        name: "Bread roll",
+        description: "",
+        heals_for: Damage::new(1),
+        count: 1,
+    });
+
+    player.pick_up_item(bread_roll()).unwrap();
+    player.pick_up_item(Item::Dust).unwrap();
+    player.pick_up_item(bread_roll()).unwrap();
+    player.pick_up_item(bread_roll()).unwrap();
+
+    assert_eq!(player.inventory.len(), 2);
+    assert_eq!(player.items_collected, 4);
+    assert_eq!(player.inventory[0].get_display_name(), "Bread roll ×3");
+    assert_eq!(player.inventory[1].get_display_name(), "A thin layer of dust");
+
+    player.use_item(&mut menu, 0).unwrap();
+    assert_eq!(player.inventory[0].get_display_name(), "Bread roll ×2");
+    player.use_item(&mut menu, 0).unwrap();
+    assert_eq!(player.inventory[0].get_display_name(), "Bread roll");
+    player.use_item(&mut menu, 0).unwrap();
+    assert!(matches!(player.inventory[..], [Item::Dust]));
+    assert_eq!(player.health, Health::new(4));
+}
+
+/// Tests that inspecting a weapon in combat shows its stats, then asks for an action again without using one up
+#[test]
+fn test_inspect_weapon_in_combat() {
+    let weapon = Item::Weapon(Weapon {
+        name: "Test Weapon",
+        description: "A weapon for testing",
+        straight_damage: Damage::new(4),
+        dodge_damage: Damage::new(2),
+        speed: 3,
+        inflicts: None,
+        throwable: false,
+        heavy: true,
+    });
+    let player = Player::init_with(Difficulty::Normal, STARTING_ROOM, vec![weapon]);
+
+    let mut menu = MockMenu::default();
+    // Inspect is after do nothing, the two dodges, attacking with the weapon and disarming
+    menu.push_choice(Some(5));
+    menu.push_choice(Some(0));
+    menu.push_choice(Some(1));
+
+    let action = player.choose_combat_action(None, &mut menu).unwrap();
+    assert!(matches!(action, combat::Action::DodgeLeft));
+
+    let (title, content) = menu.last_screen().unwrap();
+    assert_eq!(title, "You inspect your Test Weapon");
+    assert_eq!(
+        content,
+        "A weapon for testing\n\nStraight damage: 4\nDodge damage: 2\nSpeed: 3 (lower is faster)\nHeavy - you can't avoid a straight attack while swinging it"
+    );
+}
+
+/// Tests that checking gear in combat shows the player's health and items, then asks for an action again without using one up
+#[test]
+fn test_check_gear_in_combat() {
+    let mut player = Player::init(Difficulty::Normal);
+    player.health = Health::new(4);
+
+    let mut menu = MockMenu::builder()
+        .choose_option("Check your gear")
+        .choose_option("Dodge to the right")
+        .build();
+
+    let action = player.choose_combat_action(None, &mut menu).unwrap();
+    assert!(matches!(action, combat::Action::DodgeRight));
+    assert_eq!(menu.remaining_choices(), 0);
+
+    let (title, content) = menu.last_screen().unwrap();
+    assert_eq!(title, "You check your gear");
+    assert!(content.starts_with("You are at 4/10 HP\n"));
+    assert!(content.contains("Nothing but the clothes on your back"));
+
+    // Once the player has items, they are listed instead
+    player.inventory.push(Item::Food(Food {
+        name: "Test Food",
+        description: "Food for testing",
+        heals_for: Damage::new(1),
+        count: 1,
+    }));
+    let mut menu = MockMenu::builder()
+        .choose_option("Check your gear")
+        .choose_option("Do nothing")
+        .build();
+
+    player.choose_combat_action(None, &mut menu).unwrap();
+    let (_, content) = menu.last_screen().unwrap();
+    assert!(content.contains("• Test Food - Food for testing\n"));
+    assert!(!content.contains("Nothing but the clothes on your back"));
+}
+
+/// Tests that the player's weapons are listed in battle in the order they were picked up,
+/// unless they've chosen to list the best weapon first, in which case food stays where it was
+#[test]
+fn test_best_weapon_first() {
+    let weapon = |name, straight_damage| Item::Weapon(Weapon {
+        name,
+        description: "",
+        straight_damage: Damage::new(straight_damage),
+        dodge_damage: Damage::new(1),
+        speed: 3,
+        inflicts: None,
+        throwable: false,
+        heavy: false,
+    });
+    let food = Item::Food(Food {
+        name: "Test Food",
+        description: "",
+        heals_for: Damage::new(1),
+        count: 1,
+    });
+    let player = Player::init_with(Difficulty::Normal, STARTING_ROOM, vec![weapon("Stick", 1), food, weapon("Sword", 5)]);
+
+    let attacks = |menu: &MockMenu| -> Vec<String> {
+        let (_, options) = menu.last_list().unwrap();
+        options.iter().filter(|o| o.starts_with("Attack with") || o.starts_with("Eat")).cloned().collect()
+    };
+
+    let mut menu = MockMenu::builder().choose_option("Do nothing").build();
+    player.choose_combat_action(None, &mut menu).unwrap();
+    assert_eq!(attacks(&menu), ["Attack with your Stick", "Eat your Test Food", "Attack with your Sword"]);
+
+    // Turn the setting on from the settings menu, then leave it
+    let mut menu = MockMenu::builder().choose_option("Best weapon first in battle: Off").build();
+    menu.push_choice(None);
+    crate::settings::open_settings_menu(&mut menu).unwrap();
+    assert!(menu.best_weapon_first());
+
+    menu.push_choice(Some(0));
+    player.choose_combat_action(None, &mut menu).unwrap();
+    assert_eq!(attacks(&menu), ["Attack with your Sword", "Eat your Test Food", "Attack with your Stick"]);
+
+    // The options still use the right weapons out of the inventory
+    let mut menu = MockMenu::builder().choose_option("Attack with your Sword").choose_option("Attack Straight").build();
+    menu.set_best_weapon_first(true);
+    let action = player.choose_combat_action(None, &mut menu).unwrap();
+    assert!(matches!(action, combat::Action::AttackStraight(2)));
+}
+
+/// Tests the table of weapon stats, using the weapons placed around the map
+#[test]
+fn test_weapon_table() {
+    let mut player = Player::init(Difficulty::Normal);
+
+    // Take every weapon out of the map
+    let mut weapons = Vec::new();
+    for state in player.room_graph.rooms.values_mut() {
+        let (room_weapons, others) = std::mem::take(&mut state.items)
+            .into_iter()
+            .partition(|item| matches!(item, Item::Weapon(_)));
+        state.items = others;
+        weapons.extend(room_weapons);
+    }
+    // Sort the weapons so the table's rows are in a consistent order
+    weapons.sort_by_key(Item::get_name);
+    player.inventory = weapons;
+
+    let table = player.weapon_table(None);
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(lines.len(), player.inventory.len() + 1);
+    assert_eq!(lines[0], "Weapon                 Straight  Dodge  Speed");
+    assert_eq!(lines[2], "Intruders Blaster             5      3      3");
+
+    // Names are cut off to fit a narrow screen, but the numbers are kept in full
+    let table = player.weapon_table(Some(30));
+    for (line, full_line) in table.lines().zip(&lines) {
+        assert_eq!(line.chars().count(), 30);
+        assert_eq!(line[line.len() - 24..], full_line[full_line.len() - 24..]);
+    }
+    assert!(table.lines().nth(2).unwrap().starts_with("Intru⋯ "));
+}
+
+/// Tests that [`next_hint`] points the player at whatever they still need to escape
+#[test]
+fn test_next_hint() {
+    let mut player = Player::init(Difficulty::Normal);
+    assert!(next_hint(&player).contains("Strategy Room"));
+
+    // Having the keys but not the maps still points at the maps
+    player.inventory.push(Item::EscapePodKeys);
+    assert!(next_hint(&player).contains("Strategy Room"));
+
+    player.inventory.clear();
+    player.inventory.push(Item::Maps);
+    assert!(next_hint(&player).contains("Engine Room"));
+
+    player.inventory.push(Item::EscapePodKeys);
+    assert!(next_hint(&player).contains("escape pod"));
+}
+
+/// Tests that [`next_hint`] names wherever the maps ended up on a randomised map
+#[test]
+fn test_next_hint_randomized() {
+    let mut player = Player::init(Difficulty::Normal);
+
+    // Find a layout where the maps aren't in the strategy room
+    let (graph, maps_room) = (0..100)
+        .map(|seed| crate::map::init_randomized(Difficulty::Normal, seed))
+        .find_map(|graph| {
+            let room = room_with_action(&graph, |action| matches!(action, crate::map::RoomAction::StrategyRoomTakeMaps))?;
+            (room != Room::StrategyRoom).then_some((graph, room))
+        })
+        .expect("Some seed should have moved the maps");
+    player.room_graph = graph;
+
+    let hint = next_hint(&player);
+    assert!(hint.contains(maps_room.get_name()), "{hint}");
+    assert!(!hint.contains("Strategy Room"), "{hint}");
+}
+
+/// Tests that hints are only offered once every [few turns][config::HINT_COOLDOWN_TURNS]
+#[test]
+fn test_hint_cooldown() {
+    let mut player = Player::init(Difficulty::Normal);
+    let mut menu = MockMenu::builder()
+        .choose_option("Think about what to do next")
+        .build();
+
+    player.take_passive_action(&mut menu, &mut NoEffects).unwrap();
+    menu.assert_last_screen_title_contains("You stop and think");
+    // Getting a hint is free
+    assert_eq!(player.turns_taken(), 0);
+    assert!(!player.can_get_hint());
+
+    for _ in 0..config::HINT_COOLDOWN_TURNS - 1 {
+        player.use_turn();
+        assert!(!player.can_get_hint());
+    }
+
+    player.use_turn();
+    assert!(player.can_get_hint());
+}
@@ -1,13 +1,13 @@
 //! Functionality related to rooms
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::{combat::Enemy, items::Item, map::RoomAction};
+use crate::{combat::Enemy, items::Item, map::{RoomAction, RoomTrigger}, strings};
 
 /// One of the game's rooms.
 /// This does not store the room's state, and is only an identifier.
 /// For the state of a room, use [`RoomState`].
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Room {
     /// The bridge
     Bridge,
@@ -36,6 +36,8 @@ pub enum Room {
     Bunks,
     /// The engine room
     EngineRoom,
+    /// The cargo hold, off the lower corridor
+    CargoHold,
 
     /// The escape pod
     EscapePod,
@@ -45,52 +47,98 @@ pub enum Room {
 
 impl Room {
     /// Get the name of a room
-    pub const fn get_name(self) -> &'static str {
+    pub fn get_name(self) -> &'static str {
+        strings::get(self.string_keys().0)
+    }
+
+    /// Get a short description of a room
+    pub fn get_description(self) -> &'static str {
+        strings::get(self.string_keys().1)
+    }
+
+    /// Gets the room's ambient lines, one of which is added to its description each time the player enters or looks around it.
+    /// Rooms with nothing going on in them have none.
+    pub fn ambient_lines(self) -> Vec<&'static str> {
+        self.ambient_keys().iter().map(|&key| strings::get(key)).collect()
+    }
+
+    /// Gets the [ambient line][Room::ambient_lines] to add to the room's description on the given turn, if the room has any.
+    /// The line only depends on the turn, so revisiting the room at the same point in a loop always shows the same line.
+    pub fn ambient_line(self, turn: usize) -> Option<&'static str> {
+        let lines = self.ambient_lines();
+        lines.get(turn % lines.len().max(1)).copied()
+    }
+
+    /// Gets the [keys][strings::get] of the room's [ambient lines][Room::ambient_lines]
+    const fn ambient_keys(self) -> &'static [&'static str] {
         match self {
-            Self::Bridge => "Bridge",
-            Self::UpperCorridor => "Upper Corridor",
-            Self::StrategyRoom => "Strategy Room",
-            Self::Cells => "Cells",
-            Self::MessHall => "Mess Hall",
-            Self::Kitchen => "Kitchen",
-            Self::Stairwell => "Stairwell",
-
-            Self::CrewArea => "Crew Area",
-            Self::StoreRoom => "Store Room",
-            Self::LowerCorridor => "Lower Corridor",
-            Self::WashRoom => "Wash Room",
-            Self::Bunks => "Bunks",
-            Self::EngineRoom => "Engine Room",
-
-            Self::EscapePod => "Escape Pod",
-            Self::Escape => "",
+            Self::Bridge => &["room.bridge.ambient.0", "room.bridge.ambient.1"],
+            Self::Cells => &["room.cells.ambient.0", "room.cells.ambient.1"],
+            Self::MessHall => &["room.mess_hall.ambient.0", "room.mess_hall.ambient.1", "room.mess_hall.ambient.2"],
+            Self::Stairwell => &["room.stairwell.ambient.0", "room.stairwell.ambient.1"],
+            Self::EngineRoom => &["room.engine_room.ambient.0", "room.engine_room.ambient.1"],
+            Self::CargoHold => &["room.cargo_hold.ambient.0", "room.cargo_hold.ambient.1"],
+            _ => &[],
         }
     }
 
-    /// Get a short description of a room
-    pub const fn get_description(self) -> &'static str {
+    /// Gets the [keys][strings::get] of the room's name and description
+    const fn string_keys(self) -> (&'static str, &'static str) {
         match self {
-            Self::Bridge => "The control centre of the ship. Through the front window you can see into the darkness of space.",
-            Self::UpperCorridor => "A corridor connecting the bridge to the rest of the ship.",
-            Self::StrategyRoom => "Where important tactical decisions are made. Before you arrived, the most important decision since since leaving the front lines had been what galactic time zone to use.",
-            Self::Cells => "Where they keep prisoners such as yourself. The ship is on a skeleton crew on its way to pick up troops and the security isn't up to scratch, so you managed to force open the door.",
-            Self::MessHall => "Where the crew eat their meals. A holo-screen in the corner is playing a game of half-G volleyball.",
-            Self::Kitchen => "An immaculately clean kitchen area. All the appliances are electric - no open flames are allowed on the ship.",
-            Self::Stairwell => "A stairwell. There's not much to do, but out the window you can see the ship's engines pushing you forward into your captors' grip.",
-            
-            Self::CrewArea => "Where the soldiers relax after a long cycle. If there were any, that is. There's a dart board on the wall, but no darts anywhere.",
-            Self::StoreRoom => "A small room with many shelves containing various things. The light is broken so you can only make out shapes close to the door.",
-            Self::LowerCorridor => "A corridor connecting the crew area to the engine room.",
-            Self::WashRoom => "A spotless wash room containing a few showers and a few toilets. This is a military vessel, so there's no need for privacy.",
-            Self::Bunks => "The soldiers will sleep here when they are on board",
-            Self::EngineRoom => "Where the ship's internals are serviced from. The actual engines are at the back of the ship, but this is where the boiler and the electrical breakers are.",
-
-            Self::EscapePod => "A pod big enough for only two people. It has enough fuel to get you to safety, but only just.",
-            Self::Escape => "",
+            Self::Bridge => ("room.bridge.name", "room.bridge.description"),
+            Self::UpperCorridor => ("room.upper_corridor.name", "room.upper_corridor.description"),
+            Self::StrategyRoom => ("room.strategy_room.name", "room.strategy_room.description"),
+            Self::Cells => ("room.cells.name", "room.cells.description"),
+            Self::MessHall => ("room.mess_hall.name", "room.mess_hall.description"),
+            Self::Kitchen => ("room.kitchen.name", "room.kitchen.description"),
+            Self::Stairwell => ("room.stairwell.name", "room.stairwell.description"),
+
+            Self::CrewArea => ("room.crew_area.name", "room.crew_area.description"),
+            Self::StoreRoom => ("room.store_room.name", "room.store_room.description"),
+            Self::LowerCorridor => ("room.lower_corridor.name", "room.lower_corridor.description"),
+            Self::WashRoom => ("room.wash_room.name", "room.wash_room.description"),
+            Self::Bunks => ("room.bunks.name", "room.bunks.description"),
+            Self::EngineRoom => ("room.engine_room.name", "room.engine_room.description"),
+            Self::CargoHold => ("room.cargo_hold.name", "room.cargo_hold.description"),
+
+            Self::EscapePod => ("room.escape_pod.name", "room.escape_pod.description"),
+            // The escape isn't a real room, so it has no text
+            Self::Escape => ("", ""),
         }
     }
 }
 
+/// Which way a [`RoomTransition`] leads, to give the player a sense of how the ship is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Towards the front of the ship
+    North,
+    /// Towards the back of the ship
+    South,
+    /// To the right, facing the front of the ship
+    East,
+    /// To the left, facing the front of the ship
+    West,
+    /// Up to the floor above
+    Up,
+    /// Down to the floor below
+    Down,
+}
+
+impl Direction {
+    /// Gets the direction's name, as it's written in the middle of a sentence
+    pub fn name(self) -> &'static str {
+        strings::get(match self {
+            Self::North => "direction.north",
+            Self::South => "direction.south",
+            Self::East => "direction.east",
+            Self::West => "direction.west",
+            Self::Up => "direction.up",
+            Self::Down => "direction.down",
+        })
+    }
+}
+
 /// A transition between two [`Room`]s
 #[derive(Debug)]
 pub struct RoomTransition {
@@ -100,16 +148,48 @@ pub struct RoomTransition {
     pub to: Room,
     /// What option to show the player. If [`None`], it will default to the name of [Self::to]
     pub prompt_text: Option<&'static str>,
+    /// Which way the transition leads, if it's worth saying
+    pub direction: Option<Direction>,
+    /// An [`Enemy`] guarding the door, who the player has to beat before they can go through it.
+    /// Unlike a [room's enemy][RoomState::enemy], they are only fought when the player tries to take this transition.
+    pub guard: Option<Enemy>,
+    /// The [`Room`] behind the door if it's locked or jammed, or [`None`] if the door opens.
+    /// A locked door's [`to`][Self::to] is the room it's in, so trying it leaves the player where they are.
+    pub locked: Option<Room>,
+}
+
+impl RoomTransition {
+    /// Gets the name of where the transition leads, which is the [prompt text][Self::prompt_text] if there is one
+    pub fn destination_name(&self) -> &'static str {
+        self.prompt_text.unwrap_or_else(|| self.to.get_name())
+    }
+
+    /// Gets the option to show the player for taking the transition, e.g. "Go to the Kitchen (east)"
+    pub fn option_text(&self) -> String {
+        match self.direction {
+            Some(direction) => strings::format("transition.option.direction", &[&self.destination_name(), &direction.name()]),
+            None => strings::format("transition.option", &[&self.destination_name()]),
+        }
+    }
+
+    /// Gets the title of the screen shown when taking the transition, e.g. "You go east to the Kitchen"
+    pub fn title(&self) -> String {
+        match self.direction {
+            Some(direction) => strings::format("transition.title.direction", &[&direction.name(), &self.destination_name()]),
+            None => strings::format("transition.title", &[&self.destination_name()]),
+        }
+    }
 }
 
 /// The state of a room. 
 /// [`RoomState`]s can be constructed with [`new`][Self::new] and properties can be added using 
-/// [`add_item`][Self::add_item], [`add_action`][Self::add_action], and [`with_enemy`][Self::with_enemy]
+/// [`add_item`][Self::add_item], [`add_action`][Self::add_action], [`with_enemy`][Self::with_enemy], and [`with_trigger`][Self::with_trigger]
 /// ```
 /// let room_state = RoomState::new(Room::Bridge, vec![...])
 ///     .add_item(...)
 ///     .add_action(...)
-///     .with_enemy(...);
+///     .with_enemy(...)
+///     .with_trigger(...);
 /// ```
 #[derive(Debug)]
 pub struct RoomState {
@@ -122,12 +202,14 @@ pub struct RoomState {
     /// Which other rooms the player can go to from this one
     pub connections: Vec<RoomTransition>,
     /// Which actions can be performed in this room
-    pub actions: Vec<RoomAction>
+    pub actions: Vec<RoomAction>,
+    /// What happens when the player enters this room, if anything. This is taken when it fires, so it only fires once per loop.
+    pub on_enter: Option<RoomTrigger>,
 }
 
 impl RoomState {
     /// Creates a new [`RoomState`] from a provided [`Room`] and connections.
-    /// [`items`][Self::items] and [`actions`][Self::actions] are set to empty [`Vec`]s and [`enemy`][Self::enemy] is set to [`None`]
+    /// [`items`][Self::items] and [`actions`][Self::actions] are set to empty [`Vec`]s, and [`enemy`][Self::enemy] and [`on_enter`][Self::on_enter] are set to [`None`]
     pub fn new(room: Room, connections: Vec<RoomTransition>) -> Self {
         Self {
             room,
@@ -135,6 +217,7 @@ impl RoomState {
             enemy: None,
             connections,
             actions: Vec::new(),
+            on_enter: None,
         }
     }
 
@@ -162,8 +245,52 @@ impl RoomState {
         self.enemy = Some(enemy);
         self
     }
+
+    /// Takes a [`RoomState`] by value and returns a new one with [`on_enter`][Self::on_enter] set to the given [`RoomTrigger`].
+    /// See [`RoomState`] docs for usage.
+    ///
+    /// ### Panics
+    /// * If [`on_enter`][Self::on_enter] is already [`Some`], most likely if this method was called twice
+    pub fn with_trigger(mut self, trigger: RoomTrigger) -> Self {
+        assert!(self.on_enter.is_none());
+        self.on_enter = Some(trigger);
+        self
+    }
 }
 
+/// A mistake in a [`RoomGraph`], found by [`RoomGraph::validate`]
+#[derive(Debug, PartialEq, Eq)]
+pub enum GraphError {
+    /// A [`RoomState`] was stored under the key of a different [`Room`]
+    MismatchedRoom {
+        /// The key the state is stored under
+        key: Room,
+        /// The room the state says it is for
+        room: Room,
+    },
+    /// A [`RoomTransition`] leads to a [`Room`] which has no [`RoomState`]
+    MissingRoom {
+        /// The room the transition is from
+        from: Room,
+        /// The room the transition leads to
+        to: Room,
+    },
+    /// Some rooms can't be reached from the starting room
+    UnreachableRooms(Vec<Room>),
+}
+
+impl std::fmt::Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MismatchedRoom { key, room } => write!(f, "the state for {room:?} is stored under {key:?}"),
+            Self::MissingRoom { from, to } => write!(f, "{from:?} has a connection to {to:?}, which isn't in the map"),
+            Self::UnreachableRooms(rooms) => write!(f, "these rooms can't be reached: {rooms:?}"),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
 /// The state of all rooms
 #[derive(Debug)]
 pub struct RoomGraph {
@@ -173,12 +300,93 @@ pub struct RoomGraph {
 
 impl RoomGraph {
     /// Get a shared reference to the [`RoomState`] for a given [`Room`]
+    ///
+    /// ### Panics
+    /// * If the room is not in the graph. [`validate`][Self::validate] checks that this can't happen.
     pub fn get_state(&self, room: Room) -> &RoomState {
-        self.rooms.get(&room).unwrap()
+        self.rooms.get(&room).unwrap_or_else(|| panic!("{room:?} is not in the room graph"))
     }
 
     /// Get a mutable reference to the [`RoomState`] for a given [`Room`]
+    ///
+    /// ### Panics
+    /// * If the room is not in the graph. [`validate`][Self::validate] checks that this can't happen.
     pub fn get_state_mut(&mut self, room: Room) -> &mut RoomState {
-        self.rooms.get_mut(&room).unwrap()
+        self.rooms.get_mut(&room).unwrap_or_else(|| panic!("{room:?} is not in the room graph"))
+    }
+
+    /// Checks the graph for mistakes made while editing the map:
+    /// * Every [`RoomState`] is stored under the key of its own [`Room`]
+    /// * Every [`RoomTransition`] leads to a room which is in the graph
+    /// * Every room can be reached from `start`
+    ///
+    /// ### Returns:
+    /// The first [`GraphError`] found, if there are any
+    pub fn validate(&self, start: Room) -> Result<(), GraphError> {
+        if let Some((&key, state)) = self.rooms.iter().find(|(&key, state)| key != state.room) {
+            return Err(GraphError::MismatchedRoom { key, room: state.room });
+        }
+
+        if let Some(&(from, to)) = self.missing_rooms().first() {
+            return Err(GraphError::MissingRoom { from, to });
+        }
+
+        let unreachable = self.unreachable_rooms(start);
+        if !unreachable.is_empty() {
+            return Err(GraphError::UnreachableRooms(unreachable));
+        }
+
+        Ok(())
+    }
+
+    /// Searches outwards from the given [`Room`] to find which rooms the player can get to.
+    ///
+    /// ### Params:
+    /// * `start`: the room to search from
+    /// * `follow_unlocks`: whether rooms which are locked at the start of a loop but get [unlocked][RoomAction::unlocks]
+    ///   by an action in a reached room count as reachable
+    pub fn reachable_rooms(&self, start: Room, follow_unlocks: bool) -> HashSet<Room> {
+        let mut reached = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(room) = queue.pop_front() {
+            // A connection to a room which isn't in the graph is reported by `missing_rooms` instead
+            let Some(state) = self.rooms.get(&room) else { continue };
+
+            let unlocked = state.actions.iter().filter_map(RoomAction::unlocks).filter(|_| follow_unlocks);
+            let neighbours = state.connections.iter().map(|t| t.to).chain(unlocked);
+
+            for neighbour in neighbours {
+                if reached.insert(neighbour) {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        reached
+    }
+
+    /// Finds the rooms which the player can't get to from the given [`Room`], even by unlocking doors.
+    ///
+    /// ### Returns:
+    /// Every [`Room`] in [`rooms`][Self::rooms] which isn't [reachable][Self::reachable_rooms] from `start`
+    pub fn unreachable_rooms(&self, start: Room) -> Vec<Room> {
+        let reached = self.reachable_rooms(start, true);
+
+        self.rooms.keys()
+            .copied()
+            .filter(|room| !reached.contains(room))
+            .collect()
+    }
+
+    /// Finds connections which lead to a [`Room`] with no [`RoomState`] in [`rooms`][Self::rooms].
+    ///
+    /// ### Returns:
+    /// The room each broken connection is from, and the room it leads to
+    pub fn missing_rooms(&self) -> Vec<(Room, Room)> {
+        self.rooms.iter()
+            .flat_map(|(&from, state)| state.connections.iter().map(move |t| (from, t.to)))
+            .filter(|(_, to)| !self.rooms.contains_key(to))
+            .collect()
     }
 }
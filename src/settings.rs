@@ -0,0 +1,102 @@
+//! The settings menu, which the player can open at any point outside of a battle
+
+use crate::menu::{Menu, MenuSignal, OptionList, ScrollSpeed, CONTROLS_SCREEN};
+
+/// The text scroll speeds which the player can pick between, and their names
+const SCROLL_SPEEDS: [(ScrollSpeed, &str); 4] = [
+    (ScrollSpeed::CharsPerSecond(25), "Slow"),
+    (ScrollSpeed::CharsPerSecond(50), "Normal"),
+    (ScrollSpeed::CharsPerSecond(150), "Fast"),
+    (ScrollSpeed::Instant, "Instant"),
+];
+
+/// An option in the settings menu
+#[derive(Debug)]
+enum SettingsOption {
+    /// Move to the next text scroll speed
+    ChangeScrollSpeed,
+    /// Turn the bell which rings when the player is hurt on or off
+    ToggleDamageBell,
+    /// Turn listing the player's best weapon first in battle on or off
+    ToggleBestWeaponFirst,
+    /// Turn showing the numbers of options in lists on or off
+    ToggleNumberedOptions,
+    /// Show the [`CONTROLS_SCREEN`]
+    ViewControls,
+    /// Quit the game
+    Quit,
+}
+
+/// Gets the name of a [`ScrollSpeed`] to show the player
+fn scroll_speed_name(speed: ScrollSpeed) -> String {
+    match SCROLL_SPEEDS.iter().find(|(s, _)| *s == speed) {
+        Some((_, name)) => (*name).to_string(),
+        None => match speed {
+            ScrollSpeed::CharsPerSecond(chars_per_second) => format!("{chars_per_second} characters per second"),
+            ScrollSpeed::Instant => "Instant".to_string(),
+        },
+    }
+}
+
+/// Gets the [`ScrollSpeed`] after the given one in [`SCROLL_SPEEDS`].
+/// If the given speed isn't in the list (e.g. if it was set by an environment variable), the first speed is returned.
+fn next_scroll_speed(speed: ScrollSpeed) -> ScrollSpeed {
+    let next_index = SCROLL_SPEEDS
+        .iter()
+        .position(|(s, _)| *s == speed)
+        .map_or(0, |i| (i + 1) % SCROLL_SPEEDS.len());
+
+    SCROLL_SPEEDS[next_index].0
+}
+
+/// Shows the settings menu until the player goes back to the game.
+/// Returns [`MenuSignal::Quit`] if the player chooses to quit.
+pub fn open_settings_menu(menu: &mut impl Menu) -> Result<(), MenuSignal> {
+    let options = [
+        SettingsOption::ChangeScrollSpeed,
+        SettingsOption::ToggleDamageBell,
+        SettingsOption::ToggleBestWeaponFirst,
+        SettingsOption::ToggleNumberedOptions,
+        SettingsOption::ViewControls,
+        SettingsOption::Quit,
+    ];
+
+    loop {
+        let options_str = [
+            format!("Text scroll speed: {}", scroll_speed_name(menu.scroll_speed())),
+            format!("Bell when you're hurt: {}", if menu.damage_bell() { "On" } else { "Off" }),
+            format!("Best weapon first in battle: {}", if menu.best_weapon_first() { "On" } else { "Off" }),
+            format!("Numbers next to options: {}", if menu.numbered_options() { "On" } else { "Off" }),
+            "View the controls".to_string(),
+            "Quit the game".to_string(),
+        ];
+
+        let list = OptionList::new(&options_str, "Settings");
+        let Some(choice) = menu.show_option_list_cancellable(list)? else {
+            return Ok(());
+        };
+
+        match options[choice] {
+            SettingsOption::ChangeScrollSpeed => {
+                let speed = next_scroll_speed(menu.scroll_speed());
+                menu.set_scroll_speed(speed);
+            }
+            SettingsOption::ToggleDamageBell => {
+                let bell = menu.damage_bell();
+                menu.set_damage_bell(!bell);
+            }
+            SettingsOption::ToggleBestWeaponFirst => {
+                let best_first = menu.best_weapon_first();
+                menu.set_best_weapon_first(!best_first);
+            }
+            SettingsOption::ToggleNumberedOptions => {
+                let numbered = menu.numbered_options();
+                menu.set_numbered_options(!numbered);
+            }
+            SettingsOption::ViewControls => {
+                menu.show_screen(CONTROLS_SCREEN)?;
+            }
+            SettingsOption::Quit => return Err(MenuSignal::Quit),
+        }
+    }
+}
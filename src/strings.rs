@@ -0,0 +1,109 @@
+//! The game's text, looked up by key so that it can be translated or rewritten without changing the code.
+//!
+//! The default text is embedded in the binary from `strings/en.txt`.
+//! A text pack given by the `TEXT_PACK` environment variable can replace any of it, in the same format.
+//! If a key is missing from both, the key itself is shown so that the gap is easy to spot.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+mod tests;
+
+/// The name of the environment variable holding the path of a text pack to load over the defaults
+const TEXT_PACK_VAR: &str = "TEXT_PACK";
+/// The default text, which every text pack is loaded on top of
+const DEFAULT_TEXT: &str = include_str!("strings/en.txt");
+
+/// The [`Strings`] the game is using, set once at startup by [`load_from_env`]
+static STRINGS: OnceLock<Strings> = OnceLock::new();
+
+/// A registry of the game's text, keyed by identifiers such as `room.bridge.name`
+#[derive(Debug, Default)]
+pub struct Strings {
+    /// The text for each key
+    text: HashMap<String, String>,
+}
+
+impl Strings {
+    /// Adds the text from a text pack, replacing any keys which were already set.
+    /// Each line is `key = text`, where `\n` in the text starts a new line. Blank lines and lines starting with `#` are ignored.
+    ///
+    /// ### Returns:
+    /// The numbers of any lines which couldn't be read, starting from 1
+    fn load(&mut self, pack: &str) -> Vec<usize> {
+        let mut invalid_lines = Vec::new();
+
+        for (i, line) in pack.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match line.split_once('=') {
+                Some((key, text)) if !key.trim().is_empty() => {
+                    self.text.insert(key.trim().to_string(), text.trim().replace("\\n", "\n"));
+                }
+                _ => invalid_lines.push(i + 1),
+            }
+        }
+
+        invalid_lines
+    }
+
+    /// Gets the default text
+    fn with_defaults() -> Self {
+        let mut strings = Self::default();
+        strings.load(DEFAULT_TEXT);
+        strings
+    }
+
+    /// Gets the text for a key, or [`None`] if it isn't set
+    fn lookup(&self, key: &str) -> Option<&str> {
+        self.text.get(key).map(String::as_str)
+    }
+}
+
+/// Loads the text pack given by the `TEXT_PACK` environment variable over the default text.
+/// This should be called once at startup, before any text is looked up. If `TEXT_PACK` is not set, only the default text is used.
+///
+/// ### Returns:
+/// An error message if the text pack couldn't be read, or had lines which aren't `key = text`
+pub fn load_from_env() -> Result<(), String> {
+    let mut strings = Strings::with_defaults();
+
+    let result = match std::env::var_os(TEXT_PACK_VAR) {
+        Some(path) => match std::fs::read_to_string(&path) {
+            Ok(pack) => match &strings.load(&pack)[..] {
+                [] => Ok(()),
+                lines => Err(format!("Couldn't read lines {lines:?} of the text pack {}", path.to_string_lossy())),
+            },
+            Err(e) => Err(format!("Couldn't read the text pack {}: {e}", path.to_string_lossy())),
+        },
+        None => Ok(()),
+    };
+
+    // If text has already been looked up, the text pack is too late to be used
+    let _ = STRINGS.set(strings);
+    result
+}
+
+/// Gets the text for a key from the loaded text pack, or the default text if the pack doesn't have it.
+/// If neither has the key, the key itself is returned.
+pub fn get(key: &'static str) -> &'static str {
+    STRINGS.get_or_init(Strings::with_defaults).lookup(key).unwrap_or(key)
+}
+
+/// Gets the text for a key like [`get`], with each `{0}`, `{1}` and so on replaced by the argument at that index
+pub fn format(key: &'static str, args: &[&dyn std::fmt::Display]) -> String {
+    fill(get(key), args)
+}
+
+/// Replaces each `{0}`, `{1}` and so on in `text` with the argument at that index.
+/// Other braces, such as colour markup like `{red}`, are left as they are.
+fn fill(text: &str, args: &[&dyn std::fmt::Display]) -> String {
+    let mut text = text.to_string();
+    for (i, arg) in args.iter().enumerate() {
+        text = text.replace(&format!("{{{i}}}"), &arg.to_string());
+    }
+    text
+}
@@ -0,0 +1,50 @@
+#![cfg(test)]
+
+use super::*;
+use crate::rooms::Room;
+
+/// Tests reading a text pack, including comments, escaped newlines and invalid lines
+#[test]
+fn test_load_text_pack() {
+    let mut strings = Strings::default();
+    let invalid_lines = strings.load("# A comment\n\ngreeting = Hello\\nthere\nnot a valid line\n  spaced.key   =   Padded text  \n= no key");
+
+    assert_eq!(strings.lookup("greeting"), Some("Hello\nthere"));
+    assert_eq!(strings.lookup("spaced.key"), Some("Padded text"));
+    assert_eq!(strings.lookup("# A comment"), None);
+    assert_eq!(invalid_lines, [4, 6]);
+
+    // A later pack replaces only the keys it sets
+    strings.load("greeting = Bonjour");
+    assert_eq!(strings.lookup("greeting"), Some("Bonjour"));
+    assert_eq!(strings.lookup("spaced.key"), Some("Padded text"));
+}
+
+/// Tests that the default text is valid and has the text for every room, and that missing keys fall back to the key
+#[test]
+fn test_default_text() {
+    assert_eq!(Strings::default().load(DEFAULT_TEXT), Vec::<usize>::new());
+
+    assert_eq!(Room::Bridge.get_name(), "Bridge");
+    assert_eq!(Room::Escape.get_name(), "");
+    assert!(Room::Cells.get_description().starts_with("Where they keep prisoners"));
+    assert!(get("screen.intro.content").contains("and then\nYou wake up"));
+
+    // Every room's ambient lines should have text, rather than falling back to their keys
+    for &room in crate::map::init(crate::difficulty::Difficulty::Normal).rooms.keys() {
+        assert!(room.ambient_lines().iter().all(|line| !line.starts_with("room.")), "{room:?}");
+    }
+
+    assert_eq!(crate::rooms::Direction::North.name(), "north");
+    assert_eq!(format("combat.health", &[&3, &10, &"Cook", &0, &8]), "You are now at 3/10 HP.\nThe Cook is now at 0/8 HP");
+
+    assert_eq!(get("no.such.key"), "no.such.key");
+}
+
+/// Tests that numbered placeholders are filled in, while colour markup is left alone
+#[test]
+fn test_fill() {
+    let text = "The {red}{0}{/} sees you. {0} has {1} HP.";
+    assert_eq!(fill(text, &[&"Cook", &12]), "The {red}Cook{/} sees you. Cook has 12 HP.");
+    assert_eq!(fill("No placeholders", &[&1]), "No placeholders");
+}
@@ -0,0 +1,245 @@
+#![cfg(test)]
+
+use super::*;
+use crate::{effects::{NoEffects, tests::MockEffects}, items::Item, menu::tests::MockMenu};
+
+/// The choices which win the game in one loop on [`Difficulty::Normal`][difficulty::Difficulty::Normal], by taking the fastest route:
+/// arm yourself on the bridge, beat the cook for a second blaster, beat the skipper for the maps, fight through to the engine room for the keys, then take off.
+/// The enemies' moves only depend on their state and the turn number, so these choices win every time.
+const WIN_SCRIPT: &[&str] = &[
+    "Normal",
+    // Get a weapon from the bridge
+    "Go to the Upper Corridor",
+    "Go to the Bridge",
+    "Pick up the Intruders Blaster",
+    "Go to the Upper Corridor",
+    // Beat the cook first, as the skipper's blaster is easier to control than the intruders blaster
+    "Go to the Mess Hall",
+    "Dodge to the left",
+    "Attack with your Intruders Blaster",
+    "Attack Straight",
+    "Attack with your Intruders Blaster",
+    "Attack Right",
+    "Continue",
+    // Beat the skipper for the maps
+    "Go to the Upper Corridor",
+    "Go to the Strategy Room",
+    "Dodge to the left",
+    "Attack with your Intruders Blaster",
+    "Attack Straight",
+    "Attack with your Intruders Blaster",
+    "Attack Straight",
+    "Attack with your Intruders Blaster",
+    "Attack Right",
+    "Attack with your Intruders Blaster",
+    "Attack Straight",
+    "Continue",
+    "Take the drive from the computer",
+    // Beat the mechanic for the keys
+    "Go to the Upper Corridor",
+    "Go to the Mess Hall",
+    "Go to the Stairwell",
+    "Go to the Crew Area",
+    "Go to the Lower Corridor",
+    "Go to the Engine Room",
+    "Dodge to the left",
+    "Attack with your Captain's Blaster",
+    "Attack Straight",
+    "Attack with your Captain's Blaster",
+    "Attack Straight",
+    "Continue",
+    "Check out the cabinet in the corner",
+    // Escape
+    "Go to the Lower Corridor",
+    "Go to the Crew Area",
+    "Go to the Escape Pod",
+    "Take off",
+    "Launch anyway",
+];
+
+/// Builds a [`MockMenu`] which makes the given choices, by the text of the options
+fn scripted_menu(choices: &[&str]) -> MockMenu {
+    choices
+        .iter()
+        .fold(MockMenu::builder(), |builder, choice| builder.choose_option(choice))
+        .build()
+}
+
+/// Tests winning the game by making the choices in [`WIN_SCRIPT`]
+#[test]
+fn test_win_the_game() {
+    let mut menu = scripted_menu(WIN_SCRIPT);
+
+    let player = play(&mut menu, &mut NoEffects, None).unwrap();
+
+    assert_eq!(player.room, Room::Escape);
+    assert!(player.inventory.iter().any(|item| matches!(item, Item::Maps)));
+    assert!(player.inventory.iter().any(|item| matches!(item, Item::EscapePodKeys)));
+    assert_eq!(menu.remaining_choices(), 0);
+    assert_eq!(menu.status(), None);
+
+    let titles: Vec<_> = menu.screens().iter().map(|(title, _)| title.as_str()).collect();
+    assert!(titles.contains(&"Freedom at long last"));
+
+    menu.assert_last_screen_title_contains("Run Summary");
+    let (_, summary) = menu.last_screen().unwrap();
+    assert!(summary.contains("Loops taken: 1"));
+    assert!(summary.contains("Enemies defeated: 3"));
+}
+
+/// Tests that giving up on a loop starts the next one straight away, and that it still counts as a loop
+#[test]
+fn test_reset_loop() {
+    let script: Vec<&str> = ["Normal", "Go to the Upper Corridor", "Give up and restart the loop", "Restart the loop"]
+        .into_iter()
+        .chain(WIN_SCRIPT[1..].iter().copied())
+        .collect();
+    let mut menu = scripted_menu(&script);
+
+    let player = play(&mut menu, &mut NoEffects, None).unwrap();
+    assert_eq!(player.room, Room::Escape);
+    assert_eq!(menu.remaining_choices(), 0);
+
+    let titles: Vec<_> = menu.screens().iter().map(|(title, _)| title.as_str()).collect();
+    assert!(titles.contains(&loop_screen().title));
+
+    let (_, summary) = menu.last_screen().unwrap();
+    assert!(summary.contains("Loops taken: 2"));
+}
+
+/// Tests that the run summary counts the enemies defeated in every loop, not just the last one
+#[test]
+fn test_run_summary_totals() {
+    // Beat the cook, then give up and win the game in the next loop
+    let script: Vec<&str> = WIN_SCRIPT[..12]
+        .iter()
+        .copied()
+        .chain(["Give up and restart the loop", "Restart the loop"])
+        .chain(WIN_SCRIPT[1..].iter().copied())
+        .collect();
+    let mut menu = scripted_menu(&script);
+
+    let player = play(&mut menu, &mut NoEffects, None).unwrap();
+    assert_eq!(player.room, Room::Escape);
+    assert_eq!(player.enemies_defeated, 4);
+
+    let (_, summary) = menu.last_screen().unwrap();
+    assert!(summary.contains("Loops taken: 2"));
+    assert!(summary.contains("Enemies defeated: 4"));
+}
+
+/// Tests that winning a battle on the last turn of a loop ends the loop, rather than letting the player act with no time left
+#[test]
+fn test_win_battle_on_last_turn() {
+    // Wait until the fight with the cook will end on the last turn, then win the game in the next loop
+    let script: Vec<&str> = WIN_SCRIPT[..5]
+        .iter()
+        .copied()
+        .chain(std::iter::repeat_n("Wait and listen", 23))
+        .chain(["Go to the Mess Hall", "Attack with your Intruders Blaster", "Attack Straight", "Attack with your Intruders Blaster", "Attack Straight", "Continue"])
+        .chain(WIN_SCRIPT[1..].iter().copied())
+        .collect();
+    let mut menu = scripted_menu(&script);
+
+    let player = play(&mut menu, &mut NoEffects, None).unwrap();
+    assert_eq!(player.room, Room::Escape);
+
+    let titles: Vec<_> = menu.screens().iter().map(|(title, _)| title.as_str()).collect();
+    let won = titles.iter().position(|&title| title == "Battle Result").unwrap();
+    assert_eq!(titles[won + 1], max_turns_screen().title);
+
+    let (_, summary) = menu.last_screen().unwrap();
+    assert!(summary.contains("Loops taken: 2"));
+}
+
+/// Tests that a script which runs out of choices says which prompt was left unanswered
+#[test]
+#[should_panic(expected = "Ran out of scripted choices at prompt \"10:00 - What do you do?\"")]
+fn test_script_running_out() {
+    let mut menu = MockMenu::builder().choose_option("Normal").build();
+    let _ = play(&mut menu, &mut NoEffects, None);
+}
+
+/// Tests that a run which stops partway through can be continued from its autosave, and ends the same way as if it hadn't stopped
+#[test]
+fn test_continue_from_autosave() {
+    let path = std::env::temp_dir().join(format!("time-loop-autosave-test-{}.txt", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    // Stop in the middle of the fight with the cook, as if the game crashed. The script running out panics.
+    let crashed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut menu = AutosaveMenu::start(scripted_menu(&WIN_SCRIPT[..9]), path.clone(), None).unwrap();
+        play(&mut menu, &mut NoEffects, None)
+    }));
+    assert!(crashed.is_err());
+
+    // The fight isn't saved until it's over, so the save goes back to walking into the mess hall
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(saved.lines().count(), 1 + 6);
+
+    let script: Vec<&str> = std::iter::once("Continue").chain(WIN_SCRIPT[6..].iter().copied()).collect();
+    let mut menu = AutosaveMenu::start(scripted_menu(&script), path.clone(), None).unwrap();
+    let seed = menu.seed();
+    let mut effects = MockEffects::default();
+    let player = play(&mut menu, &mut effects, seed).unwrap();
+
+    let mut expected_effects = MockEffects::default();
+    let expected = play(&mut scripted_menu(WIN_SCRIPT), &mut expected_effects, None).unwrap();
+    assert_eq!(player.room, expected.room);
+    assert_eq!(player.health, expected.health);
+    assert_eq!(player.remaining_turns, expected.remaining_turns);
+    assert_eq!(player.inventory.len(), expected.inventory.len());
+
+    // Walking back to the mess hall while catching up doesn't play effects, but the fight with the cook does
+    assert!(effects.room_changes < expected_effects.room_changes, "{effects:?}");
+    assert_eq!(effects.hits, expected_effects.hits);
+
+    menu.delete_save();
+    assert!(!path.exists());
+}
+
+/// Tests that replaying a recorded run shows exactly the same screens, and ends with the [`Player`] in the same state
+#[test]
+fn test_record_and_replay() {
+    let autosave_path = std::env::temp_dir().join(format!("time-loop-record-test-autosave-{}.txt", std::process::id()));
+    let record_path = std::env::temp_dir().join(format!("time-loop-record-test-{}.txt", std::process::id()));
+    let _ = std::fs::remove_file(&autosave_path);
+
+    // The recording is written in full when the menu is dropped
+    let (recorded_player, recorded_screens) = {
+        let mut menu = AutosaveMenu::start(scripted_menu(WIN_SCRIPT), autosave_path.clone(), None)
+            .unwrap()
+            .with_recording(record_path.clone());
+        let player = play(&mut menu, &mut NoEffects, None).unwrap();
+        menu.delete_save();
+
+        (player, menu.menu().screens().to_vec())
+    };
+
+    // Every choice comes from the recording, so the mock menu doesn't need a script
+    let recording = autosave::Save::load(&record_path).unwrap();
+    assert_eq!(recording.choices.len(), WIN_SCRIPT.len());
+
+    let mut menu = AutosaveMenu::replay(MockMenu::default(), recording);
+    let seed = menu.seed();
+    let replayed_player = play(&mut menu, &mut NoEffects, seed).unwrap();
+
+    assert_eq!(menu.menu().screens(), recorded_screens);
+    assert_eq!(replayed_player.room, recorded_player.room);
+    assert_eq!(replayed_player.health, recorded_player.health);
+    assert_eq!(replayed_player.remaining_turns, recorded_player.remaining_turns);
+    assert_eq!(replayed_player.inventory.len(), recorded_player.inventory.len());
+
+    let _ = std::fs::remove_file(&record_path);
+}
+
+/// Tests reading the values of command line flags
+#[test]
+fn test_flag_value() {
+    let args = ["--record".to_string(), "run.txt".to_string(), "--replay".to_string()];
+
+    assert_eq!(flag_value(&args, RECORD_FLAG), Some(PathBuf::from("run.txt")));
+    // A flag without a value after it is ignored
+    assert_eq!(flag_value(&args, REPLAY_FLAG), None);
+    assert_eq!(flag_value(&[], RECORD_FLAG), None);
+}